@@ -26,6 +26,11 @@ export interface BPEOptions {
    * The BPE dropout to use. Must be an float between 0 and 1
    */
   dropout?: number;
+  /**
+   * The seed used by the dropout. Each encode call draws its own random generator from it,
+   * so the same seed always replays the same segmentations
+   */
+  seed?: number;
   /**
    * The unknown token to be used by the model
    */
@@ -38,6 +43,12 @@ export interface BPEOptions {
    * The suffix to attach to subword units that represent an end of word
    */
   endOfWordSuffix?: string;
+  /**
+   * Whether to use the `<0xXX>` byte tokens of the vocabulary for the characters
+   * that are not part of it, instead of the unknown token
+   * @default false
+   */
+  byteFallback?: boolean;
 }
 
 export namespace BPE {
@@ -1,5 +1,5 @@
 import { Decoder } from "./decoders";
-import { PaddingDirection, TruncationStrategy } from "./enums";
+import { PaddingDirection, TruncationDirection, TruncationStrategy } from "./enums";
 import { Model } from "./models";
 import { Normalizer } from "./normalizers";
 import { PostProcessor } from "./post-processors";
@@ -19,9 +19,22 @@ export interface TruncationOptions {
    * starting from the longest one at each token (when there is a pair of input sequences).
    * - `TruncationStrategy.OnlyFirst` Only truncate the first sequence.
    * - `TruncationStrategy.OnlySecond` Only truncate the second sequence.
+   * - `{ head_tail: { head } }` Distribute the length like `LongestFirst`, but keep the `head`
+   * first tokens of each sequence, and as many of its last tokens as possible.
    * @default TruncationStrategy.LongestFirst
    */
-  strategy?: TruncationStrategy;
+  strategy?: TruncationStrategy | { head_tail: { head: number } };
+  /**
+   * The side from which the tokens are removed
+   * @default TruncationDirection.Right
+   */
+  direction?: TruncationDirection;
+  /**
+   * Whether to cut between words only, never splitting the tokens of a word
+   * across the overflowing sequences
+   * @default false
+   */
+  wordBoundaries?: boolean;
 }
 
 export interface TruncationConfiguration extends Required<TruncationOptions> {
@@ -32,9 +45,9 @@ export interface TruncationConfiguration extends Required<TruncationOptions> {
 }
 
 export type PaddingConfiguration = Required<
-  Omit<PaddingOptions, "maxLength" | "padToMultipleOf">
+  Omit<PaddingOptions, "maxLength" | "buckets" | "lengthCap" | "padToMultipleOf">
 > &
-  Pick<PaddingOptions, "maxLength" | "padToMultipleOf">;
+  Pick<PaddingOptions, "maxLength" | "buckets" | "lengthCap" | "padToMultipleOf">;
 
 export interface PaddingOptions {
   /**
@@ -47,6 +60,18 @@ export interface PaddingOptions {
    * - No padding will be applied when single encoding
    */
   maxLength?: number;
+  /**
+   * If specified, the padding length will be the smallest of these lengths that fits
+   * the longest sequence, or the longest sequence if none of them fits
+   * @default undefined
+   */
+  buckets?: number[];
+  /**
+   * If specified, the padding length will be the longest sequence, but never beyond
+   * this length: the longer sequences get truncated to it
+   * @default undefined
+   */
+  lengthCap?: number;
   /**
    * If specified, the padding will snap to a multiple of the given value.
    * @default undefined
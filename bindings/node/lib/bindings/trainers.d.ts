@@ -62,3 +62,37 @@ export function bpeTrainer(options?: TrainerOptions): Trainer;
  * @param [options] WordPiece Trainer options
  */
 export function wordPieceTrainer(options?: TrainerOptions): Trainer;
+
+export interface WordLevelTrainerOptions {
+  /**
+   * The minimum frequency a word should have in order to be kept.
+   * @default 0
+   */
+  minFrequency?: number;
+  /**
+   * Whether to show progress bars while training.
+   * @default true
+   */
+  showProgress?: boolean;
+  /**
+   * A list of special tokens the model should know of.
+   * @default []
+   */
+  specialTokens?: (string | AddedToken)[];
+  /**
+   * The size of the final vocabulary, including all tokens.
+   * @default 30000
+   */
+  vocabSize?: number;
+  /**
+   * The token used for unknown words, added to the vocabulary after the special tokens.
+   * @default "<unk>"
+   */
+  unkToken?: string;
+}
+
+/**
+ * Instantiate a new WordLevel Trainer
+ * @param [options] WordLevel Trainer options
+ */
+export function wordLevelTrainer(options?: WordLevelTrainerOptions): Trainer;
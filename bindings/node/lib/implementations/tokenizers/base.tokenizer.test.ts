@@ -1,4 +1,4 @@
-import { PaddingDirection, TruncationStrategy } from "../../bindings/enums";
+import { PaddingDirection, TruncationDirection, TruncationStrategy } from "../../bindings/enums";
 import { BPE } from "../../bindings/models";
 import {
   PaddingConfiguration,
@@ -30,6 +30,8 @@ describe("BaseTokenizer", () => {
         maxLength: 2,
         strategy: TruncationStrategy.LongestFirst,
         stride: 0,
+        direction: TruncationDirection.Right,
+        wordBoundaries: false,
       };
       expect(tokenizer.truncation).toEqual(expectedConfig);
     });
@@ -2,6 +2,7 @@
 
 extern crate neon;
 extern crate neon_serde;
+extern crate rand;
 #[macro_use]
 extern crate serde;
 extern crate tokenizers as tk;
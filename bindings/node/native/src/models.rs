@@ -3,6 +3,7 @@ extern crate tokenizers as tk;
 use crate::extraction::*;
 use crate::tasks::models::{BPEFromFilesTask, WordPieceFromFilesTask};
 use neon::prelude::*;
+use rand::RngCore;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -39,6 +40,24 @@ impl tk::Model for Model {
             .tokenize(sequence)
     }
 
+    fn tokenize_nbest(&self, sequence: &str, n: usize) -> tk::Result<Vec<(Vec<Token>, f64)>> {
+        self.model
+            .as_ref()
+            .ok_or("Uninitialized Model")?
+            .tokenize_nbest(sequence, n)
+    }
+
+    fn tokenize_with_rng(&self, sequence: &str, rng: &mut dyn RngCore) -> tk::Result<Vec<Token>> {
+        self.model
+            .as_ref()
+            .ok_or("Uninitialized Model")?
+            .tokenize_with_rng(sequence, rng)
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        self.model.as_ref()?.next_seed()
+    }
+
     fn token_to_id(&self, token: &str) -> Option<u32> {
         self.model.as_ref()?.token_to_id(token)
     }
@@ -102,10 +121,12 @@ declare_types! {
 struct BpeOptions {
     cache_capacity: Option<usize>,
     dropout: Option<f32>,
+    seed: Option<u64>,
     unk_token: Option<String>,
     continuing_subword_prefix: Option<String>,
     end_of_word_suffix: Option<String>,
     fuse_unk: Option<bool>,
+    byte_fallback: Option<bool>,
 }
 impl BpeOptions {
     fn apply_to_bpe_builder(self, mut builder: BpeBuilder) -> BpeBuilder {
@@ -115,6 +136,9 @@ impl BpeOptions {
         if let Some(dropout) = self.dropout {
             builder = builder.dropout(dropout);
         }
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
         if let Some(unk_token) = self.unk_token {
             builder = builder.unk_token(unk_token);
         }
@@ -127,6 +151,9 @@ impl BpeOptions {
         if let Some(fuse_unk) = self.fuse_unk {
             builder = builder.fuse_unk(fuse_unk);
         }
+        if let Some(byte_fallback) = self.byte_fallback {
+            builder = builder.byte_fallback(byte_fallback);
+        }
 
         builder
     }
@@ -135,6 +162,7 @@ impl BpeOptions {
 /// bpe_init(vocab: Map<String, u32>, merges: Map<(u32, u32), (u32, u32)>, options: {
 ///   cacheCapacity?: number,
 ///   dropout?: number,
+///   seed?: number,
 ///   unkToken?: String,
 ///   continuingSubwordPrefix?: String,
 ///   endOfWordSuffix?: String
@@ -163,6 +191,7 @@ pub fn bpe_init(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 /// bpe_from_file(vocab: String, merges: String, options: {
 ///   cacheCapacity?: number,
 ///   dropout?: number,
+///   seed?: number,
 ///   unkToken?: String,
 ///   continuingSubwordPrefix?: String,
 ///   endOfWordSuffix?: String
@@ -33,6 +33,24 @@ impl tk::PostProcessor for Processor {
             .ok_or("Uninitialized PostProcessor")?
             .process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        self.processor
+            .as_ref()
+            .expect("Uninitialized PostProcessor")
+            .added_tokens_multi(n_sequences)
+    }
+
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> tk::Result<Encoding> {
+        self.processor
+            .as_ref()
+            .ok_or("Uninitialized PostProcessor")?
+            .process_multi(encodings, add_special_tokens)
+    }
 }
 
 declare_types! {
@@ -256,6 +256,14 @@ pub enum TruncationStrategyDef {
     LongestFirst,
     OnlyFirst,
     OnlySecond,
+    HeadTail { head: usize },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "tk::TruncationDirection", rename_all = "camelCase")]
+pub enum TruncationDirectionDef {
+    Left,
+    Right,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -269,6 +277,9 @@ pub struct TruncationParamsDef {
     #[serde(with = "TruncationStrategyDef")]
     strategy: tk::TruncationStrategy,
     stride: usize,
+    #[serde(with = "TruncationDirectionDef")]
+    direction: tk::TruncationDirection,
+    word_boundaries: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -288,6 +299,7 @@ pub enum PaddingDirectionDef {
 // we want it to actually be very different from the classic representation.
 // In Rust, we use an enum to define the strategy, but in JS, we just want to have a optional
 // length number => If defined we use the Fixed(n) strategy and otherwise the BatchLongest.
+// The optional `buckets` and `lengthCap` select the Buckets and BatchLongestCapped strategies.
 pub mod padding_strategy_serde {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -296,18 +308,27 @@ pub mod padding_strategy_serde {
     struct Strategy {
         #[serde(skip_serializing_if = "Option::is_none")]
         max_length: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        buckets: Option<Vec<usize>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        length_cap: Option<usize>,
     }
 
     pub fn serialize<S>(value: &tk::PaddingStrategy, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let s = Strategy {
-            max_length: match value {
-                tk::PaddingStrategy::BatchLongest => None,
-                tk::PaddingStrategy::Fixed(s) => Some(*s),
-            },
+        let mut s = Strategy {
+            max_length: None,
+            buckets: None,
+            length_cap: None,
         };
+        match value {
+            tk::PaddingStrategy::BatchLongest => {}
+            tk::PaddingStrategy::Fixed(size) => s.max_length = Some(*size),
+            tk::PaddingStrategy::Buckets(buckets) => s.buckets = Some(buckets.clone()),
+            tk::PaddingStrategy::BatchLongestCapped(cap) => s.length_cap = Some(*cap),
+        }
         s.serialize(serializer)
     }
 
@@ -318,6 +339,10 @@ pub mod padding_strategy_serde {
         let v = Strategy::deserialize(deserializer)?;
         if let Some(length) = v.max_length {
             Ok(tk::PaddingStrategy::Fixed(length))
+        } else if let Some(buckets) = v.buckets {
+            Ok(tk::PaddingStrategy::Buckets(buckets))
+        } else if let Some(cap) = v.length_cap {
+            Ok(tk::PaddingStrategy::BatchLongestCapped(cap))
         } else {
             Ok(tk::PaddingStrategy::BatchLongest)
         }
@@ -698,7 +723,9 @@ declare_types! {
             //   padId?: number,
             //   padTypeId?: number,
             //   padToken?: string,
-            //   maxLength?: number
+            //   maxLength?: number,
+            //   buckets?: number[],
+            //   lengthCap?: number
             //  })
 
             let options = cx.extract_opt::<PaddingParams>(0)?
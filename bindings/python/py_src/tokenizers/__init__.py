@@ -7,10 +7,15 @@ Offsets = Tuple[int, int]
 
 TextInputSequence = str
 PreTokenizedInputSequence = Union[List[str], Tuple[str]]
-TextEncodeInput = Union[TextInputSequence, Tuple[TextInputSequence, TextInputSequence]]
+TextEncodeInput = Union[
+    TextInputSequence,
+    Tuple[TextInputSequence, TextInputSequence],
+    List[TextInputSequence],
+]
 PreTokenizedEncodeInput = Union[
     PreTokenizedInputSequence,
     Tuple[PreTokenizedInputSequence, PreTokenizedInputSequence],
+    List[PreTokenizedInputSequence],
 ]
 
 InputSequence = Union[TextInputSequence, PreTokenizedInputSequence]
@@ -38,6 +43,7 @@ class SplitDelimiterBehavior(Enum):
 from .tokenizers import (
     Tokenizer,
     Encoding,
+    BatchEncoding,
     AddedToken,
     Regex,
     NormalizedString,
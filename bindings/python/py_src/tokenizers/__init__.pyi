@@ -12,9 +12,11 @@ from .implementations import (
     BertWordPieceTokenizer as BertWordPieceTokenizer,
 )
 
-from typing import Optional, Union, List, Tuple, Callable
+from typing import Optional, Union, List, Tuple, Callable, Iterator
 from enum import Enum
 
+import numpy as np
+
 Offsets = Tuple[int, int]
 
 TextInputSequence = str
@@ -381,6 +383,32 @@ class Encoding:
             The index of the word that contains this char
         """
         pass
+    def window_start(self, sequence_index: int = 0) -> int:
+        """
+        Get the index of the first token of the given sequence in this encoding, within the
+        original sequence. It is not 0 when the truncation produced this window.
+
+        Args:
+            sequence_index: int:
+                The index of the input sequence, like 1 for the pair
+
+        Returns:
+            The index of the first token of this window in its sequence
+        """
+        pass
+    def token_to_sequence_index(self, token_index: int) -> Optional[Tuple[int, int]]:
+        """
+        Get the input sequence of the given token, and its index in this sequence, accounting
+        for the window. The special tokens and the padding belong to no sequence.
+
+        Args:
+            token_index: int:
+                The index of the token in the encoded sequence.
+
+        Returns:
+            The index of the input sequence and the index of the token in this sequence
+        """
+        pass
     def pad(
         self,
         length: int,
@@ -408,7 +436,13 @@ class Encoding:
                 The pad token to be used when padding
         """
         pass
-    def truncate(self, max_length: int, stride: Optional[int] = 0):
+    def truncate(
+        self,
+        max_length: int,
+        stride: Optional[int] = 0,
+        direction: Optional[str] = "right",
+        word_boundaries: Optional[bool] = False,
+    ):
         """Truncate the current Encoding at the given max_length
 
         Args:
@@ -418,9 +452,49 @@ class Encoding:
             stride: (`optional`) unsigned int:
                 The length of the previous first sequence to be included
                 in the overflowing sequence
+
+            direction: (`optional`) str:
+                Can be one of `right` or `left`, to remove the last tokens, or the first ones
+
+            word_boundaries: (`optional`) bool:
+                Whether to cut between words only, never splitting the tokens of a word
+                across the overflowing sequences
         """
         pass
 
+class BatchEncoding:
+    """A batch of padded encodings, as returned by `Tokenizer.encode_batch_arrays`.
+
+    Each attribute is a numpy array of shape `(n_sequences, sequence_length)`, except the
+    offsets which are of shape `(n_sequences, sequence_length, 2)`. These arrays are built
+    once, when encoding, and each access returns the same array.
+    """
+
+    @property
+    def shape(self) -> Tuple[int, int]:
+        """ The shape of the batch, as `(n_sequences, sequence_length)` """
+        pass
+    @property
+    def ids(self) -> np.ndarray:
+        """ The tokenized ids, as an array of uint32 """
+        pass
+    @property
+    def type_ids(self) -> np.ndarray:
+        """ The type ids, as an array of uint32 """
+        pass
+    @property
+    def offsets(self) -> np.ndarray:
+        """ The offsets, as an array of int64 """
+        pass
+    @property
+    def attention_mask(self) -> np.ndarray:
+        """ The attention mask, as an array of uint32 """
+        pass
+    @property
+    def special_tokens_mask(self) -> np.ndarray:
+        """ The special tokens mask, as an array of uint32 """
+        pass
+
 class AddedToken:
     """AddedToken represents a token to be added to a Tokenizer
 
@@ -579,6 +653,13 @@ class Tokenizer:
     def post_processor(self, processor: processors.PostProcessor):
         """ Change the post processor to use with this Tokenizer """
     @property
+    def chat_template(self) -> Optional[ChatTemplate]:
+        """ Get the chat template used by `encode_chat` """
+        pass
+    @chat_template.setter
+    def chat_template(self, template: Optional[processors.ChatTemplate]):
+        """ Change the chat template used by `encode_chat` """
+    @property
     def normalizer(self) -> Optional[Normalizer]:
         """ Get the normalizer in use with this Tokenizer """
         pass
@@ -614,7 +695,15 @@ class Tokenizer:
             The size of the vocabulary
         """
         pass
-    def enable_truncation(self, max_length: int, stride: Optional[int], strategy: Optional[str]):
+    def enable_truncation(
+        self,
+        max_length: int,
+        stride: Optional[int],
+        strategy: Optional[str],
+        direction: Optional[str],
+        head: Optional[int],
+        word_boundaries: Optional[bool],
+    ):
         """Enable the truncation
 
         Args:
@@ -626,7 +715,18 @@ class Tokenizer:
                 in the overflowing sequence
 
             strategy: (`optional) str:
-                Can be one of `longest_first`, `only_first` or `only_second`
+                Can be one of `longest_first`, `only_first`, `only_second` or `head_tail`
+
+            direction: (`optional`) str:
+                Can be one of `right` or `left`, to remove the last tokens, or the first ones
+
+            head: (`optional`) unsigned int:
+                With the `head_tail` strategy, the number of first tokens to keep, the
+                rest of the length being filled with the last tokens
+
+            word_boundaries: (`optional`) bool:
+                Whether to cut between words only, never splitting the tokens of a word
+                across the overflowing sequences
         """
         pass
     def no_truncation(self):
@@ -649,6 +749,8 @@ class Tokenizer:
         pad_type_id: Optional[int] = 0,
         pad_token: Optional[str] = "[PAD]",
         length: Optional[int] = None,
+        buckets: Optional[List[int]] = None,
+        length_cap: Optional[int] = None,
     ):
         """Enable the padding
 
@@ -673,6 +775,14 @@ class Tokenizer:
             length: (`optional`) unsigned int:
                 If specified, the length at which to pad. If not specified
                 we pad using the size of the longest sequence in a batch
+
+            buckets: (`optional`) List[unsigned int]:
+                If specified, we pad to the smallest of these lengths that fits the longest
+                sequence in a batch, or to the longest sequence if none of them fits
+
+            length_cap: (`optional`) unsigned int:
+                If specified, we pad using the size of the longest sequence in a batch, but
+                never beyond this length, truncating the longer sequences to it
         """
         pass
     def no_padding(self):
@@ -687,12 +797,47 @@ class Tokenizer:
             if the padding is enabled.
         """
         pass
+    def enable_sampling(
+        self, alpha: float, nbest_size: Optional[int] = None, seed: Optional[int] = None
+    ):
+        """Enable the sampling of the segmentations, for subword regularization
+
+        The model must be a `Unigram`. The tokenizer then uses an updated copy of its model,
+        so any `Model` previously retrieved with `tokenizer.model` is left unchanged.
+
+        Args:
+            alpha: float:
+                The smoothing parameter applied to the scores of the segmentations
+
+            nbest_size: (`optional`) unsigned int:
+                Sample from the `nbest_size` best segmentations only, instead of the
+                whole lattice
+
+            seed: (`optional`) unsigned int:
+                The seed used while sampling. Each call gets a different segmentation, but
+                the same seed always replays the same sequence of segmentations.
+        """
+        pass
+    def no_sampling(self):
+        """ Disable the sampling, going back to the best segmentation """
+        pass
+    @property
+    def sampling(self) -> Optional[dict]:
+        """Get the current sampling parameters
+
+        Returns:
+            None if sampling is disabled, a dict with the currently set parameters
+            if the sampling is enabled.
+        """
+        pass
     def encode(
         self,
         sequence: InputSequence,
         pair: Optional[InputSequence],
         is_pretokenized: bool = False,
         add_special_tokens: bool = True,
+        seed: Optional[int] = None,
+        others: Optional[List[InputSequence]] = None,
     ) -> Encoding:
         """Encode the given sequence and pair. This method can process raw text sequences as well
         as already pre-tokenized sequences.
@@ -712,15 +857,74 @@ class Tokenizer:
             add_special_tokens: bool:
                 Whether to add the special tokens while encoding.
 
+            seed: (`optional`) int:
+                The seed to use for the randomness of the model (like the BPE dropout),
+                instead of the one of the model. The same input and seed always give
+                the same encoding.
+
+            others: (`optional`) List[InputSequence]:
+                The sequences to encode after `sequence` and `pair`, to encode more than
+                two sequences together. They are of the same kind as `sequence`.
+
         Returns:
             An Encoding
         """
         pass
+    def encode_chat(
+        self, messages: List[Tuple[str, str]], add_generation_prompt: bool = False
+    ) -> Tuple[Encoding, List[int]]:
+        """Encode a conversation using the chat template of this tokenizer
+
+        Args:
+            messages: List[Tuple[str, str]]:
+                The messages of the conversation, as `(role, content)`
+
+            add_generation_prompt: bool:
+                Whether to end the conversation with the generation prompt of the template
+
+        Returns:
+            The `Encoding`, with a mask marking the tokens of the masked roles
+            (usually the assistant) with 1
+        """
+        pass
+    def encode_nbest(
+        self,
+        sequence: InputSequence,
+        n: int,
+        pair: Optional[InputSequence] = None,
+        is_pretokenized: bool = False,
+        add_special_tokens: bool = True,
+    ) -> List[Tuple[Encoding, float]]:
+        """Encode the given sequence and pair in their `n` best alternative encodings.
+        Only the models that support multiple tokenizations (like `Unigram` or `BPE`) can
+        return more than one encoding.
+
+        Args:
+            sequence: InputSequence:
+                The sequence we want to encode, raw or pre-tokenized as with `encode`
+
+            n: int:
+                The maximum number of alternative encodings to return
+
+            pair: (`optional`) InputSequence:
+                The optional pair sequence
+
+            is_pretokenized: bool:
+                Whether the input is already pre-tokenized
+
+            add_special_tokens: bool:
+                Whether to add the special tokens while encoding.
+
+        Returns:
+            A list of `(Encoding, score)`, the best ones first
+        """
+        pass
     def encode_batch(
         self,
         inputs: List[EncodeInput],
         is_pretokenized: bool = False,
         add_special_tokens: bool = True,
+        seed: Optional[int] = None,
     ) -> List[Encoding]:
         """Encode the given inputs. This method accept both raw text sequences as well as already
         pre-tokenized sequences.
@@ -744,10 +948,64 @@ class Tokenizer:
             add_special_tokens: bool:
                 Whether to add the special tokens while encoding.
 
+            seed: (`optional`) int:
+                The seed to use for the randomness of the model (like the BPE dropout),
+                instead of the one of the model. Each input gets its own random generator,
+                built from the seed and its index, so the same inputs and seed always give
+                the same encodings.
+
         Returns:
             A list of Encoding
         """
         pass
+    def encode_batch_flattened(
+        self,
+        inputs: List[EncodeInput],
+        is_pretokenized: bool = False,
+        add_special_tokens: bool = True,
+    ) -> Tuple[List[Encoding], List[int]]:
+        """Encode the given inputs like `encode_batch`, and flatten the result: the overflowing
+        windows of each input are moved into the batch, right after their input, and the
+        whole batch gets padded together.
+
+        Args:
+            inputs: List[EncodeInput]:
+                A list of single sequences or pair sequences to encode, as with `encode_batch`
+
+            is_pretokenized: bool:
+                Whether the input is already pre-tokenized.
+
+            add_special_tokens: bool:
+                Whether to add the special tokens while encoding.
+
+        Returns:
+            A list of Encoding, and the index of the input of each of them
+        """
+        pass
+    def encode_batch_arrays(
+        self,
+        inputs: List[EncodeInput],
+        is_pretokenized: bool = False,
+        add_special_tokens: bool = True,
+    ) -> BatchEncoding:
+        """Encode the given inputs like `encode_batch`, and return the padded batch as
+        contiguous numpy arrays. The batch gets padded to its longest input when no padding
+        has been enabled.
+
+        Args:
+            inputs: List[EncodeInput]:
+                A list of single sequences or pair sequences to encode, as with `encode_batch`
+
+            is_pretokenized: bool:
+                Whether the input is already pre-tokenized.
+
+            add_special_tokens: bool:
+                Whether to add the special tokens while encoding.
+
+        Returns:
+            A BatchEncoding
+        """
+        pass
     def decode(self, ids: List[int], skip_special_tokens: Optional[bool] = True) -> str:
         """Decode the given list of ids to a string sequence
 
@@ -827,6 +1085,23 @@ class Tokenizer:
             The number of tokens that were added to the vocabulary
         """
         pass
+    def train_from_iterator(
+        self, trainer: Trainer, iterator: Iterator[str], length: Optional[int] = None
+    ):
+        """Train the model using the given trainer on all the sequences yielded by the
+        given iterator
+
+        Args:
+            trainer: Trainer:
+                The trainer to use
+
+            iterator: Iterator[str]:
+                Any iterable of `str` (a list, a generator, ...)
+
+            length: (`optional`) int:
+                The number of sequences in the iterator, used to show the progress
+        """
+        pass
     def post_process(
         self,
         encoding: Encoding,
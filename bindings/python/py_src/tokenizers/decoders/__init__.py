@@ -5,3 +5,8 @@ ByteLevel = decoders.ByteLevel
 WordPiece = decoders.WordPiece
 Metaspace = decoders.Metaspace
 BPEDecoder = decoders.BPEDecoder
+ByteFallback = decoders.ByteFallback
+Replace = decoders.Replace
+Strip = decoders.Strip
+Fuse = decoders.Fuse
+Sequence = decoders.Sequence
@@ -1,4 +1,5 @@
-from typing import List
+from .. import Regex
+from typing import List, Union
 
 class Decoder:
     """Base class for all decoders
@@ -63,3 +64,79 @@ class BPEDecoder(Decoder):
                 be replaced by whitespaces during the decoding
         """
         pass
+
+class ByteFallback(Decoder):
+    """ ByteFallback decoder """
+
+    def __init__(self) -> None:
+        """Instantiate a new ByteFallback decoder
+
+        Converts the `<0xXX>` byte tokens back to their bytes, and decodes them as
+        UTF-8 with the surrounding tokens. Invalid bytes are replaced by `�`.
+        """
+        pass
+
+class Replace(Decoder):
+    """ Replace decoder """
+
+    def __init__(
+        self, pattern: Union[str, Regex], content: str, captures: bool = False
+    ) -> None:
+        """Instantiate a new Replace decoder
+
+        Args:
+            pattern: Union[str, Regex]:
+                The pattern to look for in each token
+
+            content: str:
+                The content used to replace each occurrence of the pattern
+
+            captures: (`optional`) bool:
+                Whether the content references the capture groups of a `Regex` pattern with
+                `$1` or `${1}`. Otherwise the content is literal.
+        """
+        pass
+
+class Strip(Decoder):
+    """ Strip decoder """
+
+    def __init__(self, content: str = " ", start: int = 0, stop: int = 0) -> None:
+        """Instantiate a new Strip decoder
+
+        Args:
+            content: str:
+                The character to strip. Must be exactly one character.
+
+            start: int:
+                The maximum number of occurrences of `content` to strip at the beginning
+                of each token
+
+            stop: int:
+                The maximum number of occurrences of `content` to strip at the end of
+                each token
+        """
+        pass
+
+class Fuse(Decoder):
+    """ Fuse decoder """
+
+    def __init__(self) -> None:
+        """Instantiate a new Fuse decoder
+
+        Fuses all the tokens into a single one, which is useful when some other decoders
+        need to run on the whole string in a Sequence
+        """
+        pass
+
+class Sequence(Decoder):
+    """ Sequence decoder """
+
+    def __init__(self, decoders: List[Decoder]) -> None:
+        """Instantiate a new Sequence decoder
+
+        Args:
+            decoders: List[Decoder]:
+                The decoders to chain. Each of them processes the tokens returned by the
+                previous one, and the final tokens are then joined together.
+        """
+        pass
@@ -1,4 +1,4 @@
-from tokenizers import Tokenizer, Encoding, AddedToken, InputSequence, EncodeInput
+from tokenizers import Tokenizer, Encoding, BatchEncoding, AddedToken, InputSequence, EncodeInput
 
 from typing import List, Union, Tuple, Optional, Dict
 
@@ -56,6 +56,8 @@ class BaseTokenizer:
         pad_type_id: Optional[int] = 0,
         pad_token: Optional[str] = "[PAD]",
         length: Optional[int] = None,
+        buckets: Optional[List[int]] = None,
+        length_cap: Optional[int] = None,
     ):
         """Change the padding strategy
 
@@ -80,6 +82,14 @@ class BaseTokenizer:
             length: (`optional`) unsigned int:
                 If specified, the length at which to pad. If not specified
                 we pad using the size of the longest sequence in a batch
+
+            buckets: (`optional`) List[unsigned int]:
+                If specified, we pad to the smallest of these lengths that fits the longest
+                sequence in a batch, or to the longest sequence if none of them fits
+
+            length_cap: (`optional`) unsigned int:
+                If specified, we pad using the size of the longest sequence in a batch, but
+                never beyond this length, truncating the longer sequences to it
         """
         return self._tokenizer.enable_padding(
             direction=direction,
@@ -88,6 +98,8 @@ class BaseTokenizer:
             pad_type_id=pad_type_id,
             pad_token=pad_token,
             length=length,
+            buckets=buckets,
+            length_cap=length_cap,
         )
 
     def no_padding(self):
@@ -104,8 +116,46 @@ class BaseTokenizer:
         """
         return self._tokenizer.padding
 
+    def enable_sampling(
+        self, alpha: float, nbest_size: Optional[int] = None, seed: Optional[int] = None
+    ):
+        """Enable the sampling of the segmentations, for subword regularization
+
+        Args:
+            alpha: float:
+                The smoothing parameter applied to the scores of the segmentations
+
+            nbest_size: (`optional`) unsigned int:
+                Sample from the `nbest_size` best segmentations only, instead of the
+                whole lattice
+
+            seed: (`optional`) unsigned int:
+                The seed used while sampling
+        """
+        return self._tokenizer.enable_sampling(alpha, nbest_size=nbest_size, seed=seed)
+
+    def no_sampling(self):
+        """ Disable the sampling """
+        return self._tokenizer.no_sampling()
+
+    @property
+    def sampling(self) -> Optional[dict]:
+        """Get the current sampling parameters
+
+        Returns:
+            None if sampling is disabled, a dict with the currently set parameters
+            if the sampling is enabled.
+        """
+        return self._tokenizer.sampling
+
     def enable_truncation(
-        self, max_length: int, stride: Optional[int] = 0, strategy: Optional[str] = "longest_first"
+        self,
+        max_length: int,
+        stride: Optional[int] = 0,
+        strategy: Optional[str] = "longest_first",
+        direction: Optional[str] = "right",
+        head: Optional[int] = None,
+        word_boundaries: Optional[bool] = False,
     ):
         """Change the truncation options
 
@@ -118,9 +168,27 @@ class BaseTokenizer:
                 in the overflowing sequence
 
             strategy: (`optional) str:
-                Can be one of `longest_first`, `only_first` or `only_second`
+                Can be one of `longest_first`, `only_first`, `only_second` or `head_tail`
+
+            direction: (`optional`) str:
+                Can be one of `right` or `left`, to remove the last tokens, or the first ones
+
+            head: (`optional`) unsigned int:
+                With the `head_tail` strategy, the number of first tokens to keep
+
+            word_boundaries: (`optional`) bool:
+                Whether to cut between words only, never splitting the tokens of a word
+                across the overflowing sequences
         """
-        return self._tokenizer.enable_truncation(max_length, stride=stride, strategy=strategy)
+        kwargs = {
+            "stride": stride,
+            "strategy": strategy,
+            "direction": direction,
+            "word_boundaries": word_boundaries,
+        }
+        if head is not None:
+            kwargs["head"] = head
+        return self._tokenizer.enable_truncation(max_length, **kwargs)
 
     def no_truncation(self):
         """ Disable truncation """
@@ -247,6 +315,43 @@ class BaseTokenizer:
 
         return self._tokenizer.encode_batch(inputs, is_pretokenized, add_special_tokens)
 
+    def encode_batch_flattened(
+        self,
+        inputs: List[EncodeInput],
+        is_pretokenized: bool = False,
+        add_special_tokens: bool = True,
+    ) -> Tuple[List[Encoding], List[int]]:
+        """Encode the given inputs like `encode_batch`, and flatten the result: the overflowing
+        windows of each input are moved into the batch, right after their input, and the
+        whole batch gets padded together.
+
+        Returns:
+            A list of Encoding, and the index of the input of each of them
+        """
+
+        if inputs is None:
+            raise ValueError("encode_batch_flattened: `inputs` can't be `None`")
+
+        return self._tokenizer.encode_batch_flattened(inputs, is_pretokenized, add_special_tokens)
+
+    def encode_batch_arrays(
+        self,
+        inputs: List[EncodeInput],
+        is_pretokenized: bool = False,
+        add_special_tokens: bool = True,
+    ) -> BatchEncoding:
+        """Encode the given inputs like `encode_batch`, and return the padded batch as
+        contiguous numpy arrays.
+
+        Returns:
+            A BatchEncoding
+        """
+
+        if inputs is None:
+            raise ValueError("encode_batch_arrays: `inputs` can't be `None`")
+
+        return self._tokenizer.encode_batch_arrays(inputs, is_pretokenized, add_special_tokens)
+
     def decode(self, ids: List[int], skip_special_tokens: Optional[bool] = True) -> str:
         """Decode the given list of ids to a string sequence
 
@@ -46,6 +46,10 @@ class BPE(Model):
        dropout: (`optional`) Optional[float] [0, 1]:
            The BPE dropout to use. Must be an float between 0 and 1
 
+       seed: (`optional`) int:
+           The seed used by the dropout. Each encode call draws its own random generator
+           from it, so the same seed always replays the same segmentations
+
        unk_token: (`optional`) str:
            The unknown token to be used by the model.
 
@@ -57,6 +61,10 @@ class BPE(Model):
 
        fuse_unk: (`optional`) bool:
            Multiple unk tokens get fused into only 1
+
+       byte_fallback: (`optional`) bool:
+           Use the `<0xXX>` byte tokens of the vocabulary for the characters that
+           are not part of it, instead of the unknown token
     """
 
     def __init__(
@@ -65,10 +73,12 @@ class BPE(Model):
         merges: Optional[Union[str, List[Tuple[str, str]]]],
         cache_capacity: Optional[int],
         dropout: Optional[float],
+        seed: Optional[int],
         unk_token: Optional[str],
         continuing_subword_prefix: Optional[str],
         end_of_word_suffix: Optional[str],
         fuse_unk: Optional[bool],
+        byte_fallback: Optional[bool],
     ):
         pass
     @staticmethod
@@ -164,8 +174,35 @@ class Unigram(Model):
        vocab: ('`optional`) string:
            A list of vocabulary items and their relative score [("am", -0.2442),...]
 
+       unk_id: ('`optional`) int:
+           The id of the unknown token in the vocabulary
+
+       byte_fallback: (`optional`) bool:
+           Use the `<0xXX>` byte tokens of the vocabulary for the characters that
+           are not part of it, instead of the unknown token
+
+       alpha: (`optional`) float:
+           Enables the sampling of the segmentations (subword regularization), using
+           this smoothing parameter
+
+       nbest_size: (`optional`) int:
+           Samples the segmentations among the `nbest_size` best ones, instead of the
+           whole lattice
+
+       seed: (`optional`) int:
+           The seed to use while sampling. Each encode call gets a different segmentation,
+           but the same seed always replays the same sequence of segmentations
+
     """
 
     @staticmethod
-    def __init__(self, vocab: Optional[List[Tuple[str, float]]]):
+    def __init__(
+        self,
+        vocab: Optional[List[Tuple[str, float]]],
+        unk_id: Optional[int],
+        byte_fallback: Optional[bool],
+        alpha: Optional[float],
+        nbest_size: Optional[int],
+        seed: Optional[int],
+    ):
         pass
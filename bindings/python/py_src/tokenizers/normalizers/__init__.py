@@ -13,6 +13,9 @@ StripAccents = normalizers.StripAccents
 Nmt = normalizers.Nmt
 Precompiled = normalizers.Precompiled
 Replace = normalizers.Replace
+CharMap = normalizers.CharMap
+Scoped = normalizers.Scoped
+CaseFold = normalizers.CaseFold
 
 
 NORMALIZERS = {"nfc": NFC, "nfd": NFD, "nfkc": NFKC, "nfkd": NFKD}
@@ -1,5 +1,5 @@
-from .. import NormalizedString
-from typing import Optional, List
+from .. import NormalizedString, Regex
+from typing import Optional, List, Union, Dict, Tuple
 
 class Normalizer:
     """Base class for all normalizers
@@ -124,11 +124,108 @@ class Precompiled(Normalizer):
 
     def __init__(self, precompiled_charsmap: bytes) -> Normalizer:
         pass
+    @staticmethod
+    def from_rules(rules: List[Tuple[str, str]]) -> Precompiled:
+        """Instantiate a new Precompiled by compiling the given normalization rules
+
+        Args:
+            rules: List[Tuple[str, str]]:
+                Each source sequence with its target, which can be empty to remove the source
+        """
+        pass
+    @staticmethod
+    def from_rules_file(path: str) -> Precompiled:
+        """Instantiate a new Precompiled from a TSV file of normalization rules
+
+        This is the format used by the `--normalization_rule_tsv` option of `spm_train`: each
+        line has a source and a target, both given as space separated hexadecimal codepoints.
+        """
+        pass
 
 class Replace(Normalizer):
-    """ Replace normalizer """
+    """Replace normalizer
+
+    With `captures=True` and a `Regex` pattern, the content can reference its capture groups
+    with `$1` or `${1}`, and `$$` stands for a literal `$`. The characters of the capture groups
+    then keep their alignments with the original string. Otherwise the content is literal.
+    """
+
+    def __init__(
+        self, pattern: Union[str, Regex], content: str, captures: bool = False
+    ) -> Normalizer:
+        pass
+
+class CharMap(Normalizer):
+    """CharMap normalizer
+
+    Maps some characters to a string each, like to fold full-width characters to ASCII, or to
+    expand ligatures. A character mapped to an empty string gets removed.
+    """
+
+    def __init__(self, mapping: Dict[str, str]) -> Normalizer:
+        """Instantiate a new CharMap
+
+        Args:
+            mapping: Dict[str, str]:
+                The string to use for each single character
+        """
+        pass
+    @staticmethod
+    def from_file(path: str) -> CharMap:
+        """Instantiate a new CharMap from the given TSV file
+
+        Each line contains a `<source>\\t<target>` mapping, where each field is either the
+        literal text, or a list of codepoints like `U+0066 U+0069`. Empty lines and lines
+        starting with `#` are ignored.
+        """
+        pass
+
+class Scoped(Normalizer):
+    """Scoped normalizer
+
+    Applies the given normalizer only to the parts of the string that belong to some Unicode
+    scripts, or that match a pattern. The rest of the string is left as is.
+    """
+
+    def __init__(
+        self,
+        normalizer: Normalizer,
+        scripts: Optional[List[str]] = None,
+        pattern: Optional[Union[str, Regex]] = None,
+    ) -> Normalizer:
+        """Instantiate a new Scoped
+
+        Args:
+            normalizer: Normalizer:
+                The normalizer to apply. Custom normalizers are not supported.
+
+            scripts: (`optional`) List[str]:
+                The names of the Unicode scripts to normalize, like "Latin" or "Cyrillic".
+                The combining marks belong to the script of the character they follow.
 
-    def __init__(self, pattern: str, content: str) -> Normalizer:
+            pattern: (`optional`) Union[str, Regex]:
+                The pattern matching the spans to normalize
+
+        Exactly one of `scripts` or `pattern` must be provided.
+        """
+        pass
+
+class CaseFold(Normalizer):
+    """CaseFold normalizer
+
+    Applies the full Unicode case folding, which is like lowercasing, except that some
+    characters get expanded (`ß` to `ss`, `ﬁ` to `fi`, ...), and that the characters which
+    only differ by their case in some contexts are folded to the same one.
+    """
+
+    def __init__(self, locale: Optional[str] = None) -> Normalizer:
+        """Instantiate a new CaseFold
+
+        Args:
+            locale: (`optional`) str:
+                One of `tr`, `az` or `lt`, to apply the language-specific lowercasing of
+                the dotted and dotless `I` first
+        """
         pass
 
 def unicode_normalizer_from_str(normalizer: str) -> Normalizer:
@@ -9,5 +9,6 @@ WhitespaceSplit = pre_tokenizers.WhitespaceSplit
 BertPreTokenizer = pre_tokenizers.BertPreTokenizer
 Metaspace = pre_tokenizers.Metaspace
 CharDelimiterSplit = pre_tokenizers.CharDelimiterSplit
+Split = pre_tokenizers.Split
 Digits = pre_tokenizers.Digits
 UnicodeScripts = pre_tokenizers.UnicodeScripts
@@ -1,5 +1,5 @@
-from .. import PreTokenizedString
-from typing import Optional, List, Tuple
+from .. import PreTokenizedString, Regex
+from typing import Optional, List, Tuple, Union
 
 Offsets = Tuple[int, int]
 
@@ -112,6 +112,33 @@ class CharDelimiterSplit(PreTokenizer):
         """
         pass
 
+class Split(PreTokenizer):
+    """Split PreTokenizer
+
+    This versatile pre-tokenizer splits using the provided pattern and
+    according to the provided behavior. The pattern can be inverted by
+    making use of the invert flag.
+    """
+
+    def __init__(
+        self, pattern: Union[str, Regex], behavior: str, invert: bool = False
+    ) -> None:
+        """Instantiate a new Split PreTokenizer
+
+        Args:
+            pattern: Union[str, Regex]:
+                A pattern used to split the string. Usually a string or a Regex
+
+            behavior: str:
+                The behavior to use when splitting.
+                Choices: "removed", "isolated", "merged_with_previous", "merged_with_next",
+                "contiguous"
+
+            invert: bool:
+                Whether to invert the pattern.
+        """
+        pass
+
 class Punctuation(PreTokenizer):
     """Punctuation PreTokenizer
 
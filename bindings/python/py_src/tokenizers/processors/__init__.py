@@ -5,3 +5,5 @@ BertProcessing = processors.BertProcessing
 RobertaProcessing = processors.RobertaProcessing
 ByteLevel = processors.ByteLevel
 TemplateProcessing = processors.TemplateProcessing
+Sequence = processors.Sequence
+ChatTemplate = processors.ChatTemplate
@@ -1,5 +1,5 @@
 from .. import Encoding
-from typing import Tuple, Union, List
+from typing import Dict, Tuple, Union, List, Optional
 
 class PostProcessor:
     """Base class for all post-processors
@@ -143,13 +143,28 @@ class TemplateProcessing(PostProcessor):
 
     The same construct is used for special tokens: `<identifier>(:<type_id>)?`.
 
+    Special tokens get an attention mask and a special tokens mask of `1` by default. Both can
+    be changed with some flags, following the `type_id` if any:
+    - `noattn` to exclude the special token from the attention: `[CLS]:0:noattn`
+    - `nospecial` to not mark the token as special, like for task prefixes: `[2FR]:nospecial`
+    - `attn` and `special` to restore the defaults
+
+    Inputs with more than two sequences can use the `multi` templates, where the following
+    sequences are identified by `$C`, `$D`, ... up to `$Z`.
+
     **Warning**: You must ensure that you are giving the correct tokens/ids as these
     will be added to the Encoding without any further check. If the given ids correspond
     to something totally different in a `Tokenizer` using this `PostProcessor`, it
     might lead to unexpected results.
     """
 
-    def __init__(self, single: Template, pair: Template, special_tokens: Tokens) -> None:
+    def __init__(
+        self,
+        single: Template,
+        pair: Template,
+        special_tokens: Tokens,
+        multi: Optional[List[Template]] = None,
+    ) -> None:
         """Instantiate a new TemplateProcessing
 
         Args:
@@ -162,6 +177,10 @@ class TemplateProcessing(PostProcessor):
             special_tokens: Tokens:
                 The list of special tokens used in each sequences
 
+            multi: Optional[List[Template]]:
+                The templates used with more than two sequences. Each of them is used for
+                the number of sequences it uses.
+
         Template: Union[str, List[str]]:
             - If a `str` is provided, the whitespace is used as delimiter between tokens
             - If a `List[str]` is provided, a list of tokens
@@ -176,3 +195,68 @@ class TemplateProcessing(PostProcessor):
              the same length.
         """
         pass
+
+class Sequence(PostProcessor):
+    """Sequence PostProcessor
+
+    This post-processor composes other post-processors and applies them in sequence.
+    Each of them receives the encodings returned by the previous one: `ByteLevel`
+    processes each sequence separately, while the other post-processors merge them.
+    """
+
+    def __init__(self, processors: List[PostProcessor]) -> None:
+        """Instantiate a new Sequence PostProcessor
+
+        Args:
+            processors: List[PostProcessor]:
+                The post-processors to apply, in order
+        """
+        pass
+
+class ChatTemplate:
+    """ChatTemplate
+
+    Formats conversations, made of a list of `(role, content)` messages, before encoding
+    them with `Tokenizer.encode_chat`. Each role has its own template, where `$A` represents
+    the content of the message, following the same syntax as `TemplateProcessing`:
+    ```
+    ChatTemplate(
+        roles={
+            "user": "<|user|> $A <|end|>",
+            "assistant": ("<|assistant|> $A:1 <|end|>:1", True),
+        },
+        prefix="<s>",
+        generation_prompt="<|assistant|>:1",
+        special_tokens=[("<s>", 0), ("<|end|>", 1), ("<|user|>", 2), ("<|assistant|>", 3)],
+    )
+    ```
+
+    The tokens of the messages of the masked roles (here the assistant) are marked with `1`
+    in the mask returned by `Tokenizer.encode_chat`.
+    """
+
+    def __init__(
+        self,
+        roles: Dict[str, Union[Template, Tuple[Template, bool]]],
+        prefix: Optional[Template] = None,
+        generation_prompt: Optional[Template] = None,
+        special_tokens: Optional[Tokens] = None,
+    ) -> None:
+        """Instantiate a new ChatTemplate
+
+        Args:
+            roles: Dict[str, Union[Template, Tuple[Template, bool]]]:
+                The template of each role, optionally with a flag to mark the tokens
+                of its messages in the mask
+
+            prefix: Optional[Template]:
+                The template added once at the beginning of the conversation
+
+            generation_prompt: Optional[Template]:
+                The template added at the end of the conversation, when asked to prompt
+                for a new message
+
+            special_tokens: Optional[Tokens]:
+                The list of special tokens used in the templates
+        """
+        pass
@@ -3,4 +3,5 @@ from .. import trainers
 Trainer = trainers.Trainer
 BpeTrainer = trainers.BpeTrainer
 WordPieceTrainer = trainers.WordPieceTrainer
+WordLevelTrainer = trainers.WordLevelTrainer
 UnigramTrainer = trainers.UnigramTrainer
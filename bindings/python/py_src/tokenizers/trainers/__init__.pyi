@@ -112,6 +112,44 @@ class WordPieceTrainer(Trainer):
         """
         pass
 
+class WordLevelTrainer(Trainer):
+    """WordLevelTrainer
+
+    Capable of training a WordLevel model
+    """
+
+    def __init__(
+        self,
+        vocab_size: int = 30000,
+        min_frequency: int = 0,
+        show_progress: bool = True,
+        special_tokens: List[Union[str, AddedToken]] = [],
+        unk_token: str = "<unk>",
+    ) -> Trainer:
+        """Instantiate a new WordLevelTrainer with the given options:
+
+        Args:
+            vocab_size: unsigned int:
+                The size of the final vocabulary, including all tokens.
+
+            min_frequency: unsigned int:
+                The minimum frequency a word should have in order to be kept.
+
+            show_progress: boolean:
+                Whether to show progress bars while training.
+
+            special_tokens: List[Union[str, AddedToken]]:
+                A list of special tokens the model should know of.
+
+            unk_token: str:
+                The token used for unknown words, added to the vocabulary after the
+                special tokens.
+
+        Returns:
+            Trainer
+        """
+        pass
+
 class UnigramTrainer(Trainer):
     """UnigramTrainer
 
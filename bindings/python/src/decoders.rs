@@ -6,14 +6,20 @@ use pyo3::types::*;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tk::decoders::bpe::BPEDecoder;
+use tk::decoders::byte_fallback::ByteFallback;
 use tk::decoders::byte_level::ByteLevel;
+use tk::decoders::fuse::Fuse;
 use tk::decoders::metaspace::Metaspace;
+use tk::decoders::replace::Replace;
+use tk::decoders::sequence::Sequence;
+use tk::decoders::strip::Strip;
 use tk::decoders::wordpiece::WordPiece;
 use tk::decoders::DecoderWrapper;
 use tk::Decoder;
 use tokenizers as tk;
 
 use super::error::ToPyResult;
+use super::utils::PyPattern;
 
 #[pyclass(dict, module = "tokenizers.decoders", name=Decoder)]
 #[derive(Clone, Deserialize, Serialize)]
@@ -38,6 +44,15 @@ impl PyDecoder {
                 DecoderWrapper::WordPiece(_) => Py::new(py, (PyWordPieceDec {}, base))?.into_py(py),
                 DecoderWrapper::ByteLevel(_) => Py::new(py, (PyByteLevelDec {}, base))?.into_py(py),
                 DecoderWrapper::BPE(_) => Py::new(py, (PyBPEDecoder {}, base))?.into_py(py),
+                DecoderWrapper::ByteFallback(_) => {
+                    Py::new(py, (PyByteFallbackDec {}, base))?.into_py(py)
+                }
+                DecoderWrapper::Sequence(_) => {
+                    Py::new(py, (PySequenceDecoder {}, base))?.into_py(py)
+                }
+                DecoderWrapper::Replace(_) => Py::new(py, (PyReplaceDec {}, base))?.into_py(py),
+                DecoderWrapper::Strip(_) => Py::new(py, (PyStrip {}, base))?.into_py(py),
+                DecoderWrapper::Fuse(_) => Py::new(py, (PyFuse {}, base))?.into_py(py),
             },
         })
     }
@@ -47,6 +62,10 @@ impl Decoder for PyDecoder {
     fn decode(&self, tokens: Vec<String>) -> tk::Result<String> {
         self.decoder.decode(tokens)
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        self.decoder.decode_chain(tokens)
+    }
 }
 
 #[pymethods]
@@ -176,6 +195,92 @@ impl PyBPEDecoder {
     }
 }
 
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=ByteFallback)]
+pub struct PyByteFallbackDec {}
+#[pymethods]
+impl PyByteFallbackDec {
+    #[new]
+    fn new() -> PyResult<(Self, PyDecoder)> {
+        Ok((PyByteFallbackDec {}, ByteFallback::new().into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Replace)]
+pub struct PyReplaceDec {}
+#[pymethods]
+impl PyReplaceDec {
+    #[new]
+    #[args(captures = "false")]
+    fn new(pattern: PyPattern, content: String, captures: bool) -> PyResult<(Self, PyDecoder)> {
+        let replace = if captures {
+            Replace::with_captures(pattern, content)
+        } else {
+            Replace::new(pattern, content)
+        };
+        Ok((PyReplaceDec {}, ToPyResult(replace).into_py()?.into()))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[" ", " "]))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Strip)]
+pub struct PyStrip {}
+#[pymethods]
+impl PyStrip {
+    #[new]
+    #[args(content = "\" \"", start = "0", stop = "0")]
+    fn new(content: &str, start: usize, stop: usize) -> PyResult<(Self, PyDecoder)> {
+        let mut chars = content.chars();
+        let content = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => {
+                return Err(exceptions::PyValueError::new_err(
+                    "content must be a character",
+                ))
+            }
+        };
+        Ok((PyStrip {}, Strip::new(content, start, stop).into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Fuse)]
+pub struct PyFuse {}
+#[pymethods]
+impl PyFuse {
+    #[new]
+    fn new() -> PyResult<(Self, PyDecoder)> {
+        Ok((PyFuse {}, Fuse::new().into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Sequence)]
+pub struct PySequenceDecoder {}
+#[pymethods]
+impl PySequenceDecoder {
+    #[new]
+    fn new(decoders: &PyList) -> PyResult<(Self, PyDecoder)> {
+        let mut sequence = Vec::with_capacity(decoders.len());
+        for d in decoders.iter() {
+            let decoder: PyRef<PyDecoder> = d.extract()?;
+            match &decoder.decoder {
+                PyDecoderWrapper::Wrapped(inner) => sequence.push(inner.as_ref().clone()),
+                PyDecoderWrapper::Custom(_) => {
+                    return Err(exceptions::PyTypeError::new_err(
+                        "Custom decoders cannot be used in a Sequence",
+                    ))
+                }
+            }
+        }
+        Ok((PySequenceDecoder {}, Sequence::new(sequence).into()))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[PyList::empty(py)]))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CustomDecoder {
     inner: PyObject,
@@ -253,6 +358,13 @@ impl Decoder for PyDecoderWrapper {
             PyDecoderWrapper::Custom(inner) => inner.decode(tokens),
         }
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        match self {
+            PyDecoderWrapper::Wrapped(inner) => inner.decode_chain(tokens),
+            PyDecoderWrapper::Custom(inner) => inner.decode_chain(tokens),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
+use ndarray::{Array2, Array3};
+use numpy::{IntoPyArray, PyArray2, PyArray3};
 use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::*;
 use pyo3::{PyObjectProtocol, PySequenceProtocol};
-use tk::tokenizer::{Offsets, PaddingDirection};
+use tk::tokenizer::{Offsets, PaddingDirection, TruncationDirection};
 use tokenizers as tk;
 
 use crate::error::PyError;
@@ -150,6 +152,15 @@ impl PyEncoding {
         self.encoding.char_to_word(char_pos)
     }
 
+    #[args(sequence_index = "0")]
+    fn window_start(&self, sequence_index: usize) -> usize {
+        self.encoding.get_window_start(sequence_index)
+    }
+
+    fn token_to_sequence_index(&self, token_index: usize) -> Option<(usize, usize)> {
+        self.encoding.token_to_sequence_index(token_index)
+    }
+
     #[args(kwargs = "**")]
     fn pad(&mut self, length: usize, kwargs: Option<&PyDict>) -> PyResult<()> {
         let mut pad_id = 0;
@@ -189,17 +200,122 @@ impl PyEncoding {
     #[args(kwargs = "**")]
     fn truncate(&mut self, max_length: usize, kwargs: Option<&PyDict>) -> PyResult<()> {
         let mut stride = 0;
+        let mut direction = TruncationDirection::Right;
+        let mut word_boundaries = false;
 
         if let Some(kwargs) = kwargs {
             for (key, value) in kwargs {
                 let key: &str = key.extract()?;
                 match key {
                     "stride" => stride = value.extract()?,
+                    "direction" => {
+                        let value: &str = value.extract()?;
+                        direction = match value {
+                            "left" => Ok(TruncationDirection::Left),
+                            "right" => Ok(TruncationDirection::Right),
+                            other => Err(PyError(format!(
+                                "Unknown `direction`: `{}`. Use \
+                                 one of `left` or `right`",
+                                other
+                            ))
+                            .into_pyerr::<exceptions::PyValueError>()),
+                        }?;
+                    }
+                    "word_boundaries" => word_boundaries = value.extract()?,
                     _ => println!("Ignored unknown kwarg option {}", key),
                 }
             }
         }
-        self.encoding.truncate(max_length, stride);
+        self.encoding
+            .truncate_with_direction(max_length, stride, direction, word_boundaries);
         Ok(())
     }
 }
+
+/// The arrays of a `BatchEncoding`, moved into numpy arrays once, without any copy. Each
+/// getter then returns the same array.
+#[pyclass(module = "tokenizers", name=BatchEncoding)]
+pub struct PyBatchEncoding {
+    shape: (usize, usize),
+    ids: Py<PyArray2<u32>>,
+    type_ids: Py<PyArray2<u32>>,
+    attention_mask: Py<PyArray2<u32>>,
+    special_tokens_mask: Py<PyArray2<u32>>,
+    offsets: Py<PyArray3<i64>>,
+}
+
+impl PyBatchEncoding {
+    pub fn new(py: Python, batch: tk::tokenizer::BatchEncoding) -> Self {
+        let [n, l] = batch.shape();
+        let (ids, type_ids, attention_mask, special_tokens_mask, offsets) = batch.into_arrays();
+        let to_array = |data: Vec<u32>| -> Py<PyArray2<u32>> {
+            Array2::from_shape_vec((n, l), data)
+                .expect("BatchEncoding arrays always match their shape")
+                .into_pyarray(py)
+                .into()
+        };
+
+        Self {
+            shape: (n, l),
+            ids: to_array(ids),
+            type_ids: to_array(type_ids),
+            attention_mask: to_array(attention_mask),
+            special_tokens_mask: to_array(special_tokens_mask),
+            offsets: Array3::from_shape_vec((n, l, 2), offsets)
+                .expect("BatchEncoding arrays always match their shape")
+                .into_pyarray(py)
+                .into(),
+        }
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyBatchEncoding {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "BatchEncoding(shape={:?}, attributes=[ids, type_ids, offsets, attention_mask, \
+             special_tokens_mask])",
+            self.shape
+        ))
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for PyBatchEncoding {
+    fn __len__(self) -> PyResult<usize> {
+        Ok(self.shape.0)
+    }
+}
+
+#[pymethods]
+impl PyBatchEncoding {
+    #[getter]
+    fn get_shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    #[getter]
+    fn get_ids(&self, py: Python) -> Py<PyArray2<u32>> {
+        self.ids.clone_ref(py)
+    }
+
+    #[getter]
+    fn get_type_ids(&self, py: Python) -> Py<PyArray2<u32>> {
+        self.type_ids.clone_ref(py)
+    }
+
+    #[getter]
+    fn get_attention_mask(&self, py: Python) -> Py<PyArray2<u32>> {
+        self.attention_mask.clone_ref(py)
+    }
+
+    #[getter]
+    fn get_special_tokens_mask(&self, py: Python) -> Py<PyArray2<u32>> {
+        self.special_tokens_mask.clone_ref(py)
+    }
+
+    #[getter]
+    fn get_offsets(&self, py: Python) -> Py<PyArray3<i64>> {
+        self.offsets.clone_ref(py)
+    }
+}
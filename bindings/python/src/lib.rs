@@ -44,6 +44,7 @@ fn trainers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<trainers::PyTrainer>()?;
     m.add_class::<trainers::PyBpeTrainer>()?;
     m.add_class::<trainers::PyWordPieceTrainer>()?;
+    m.add_class::<trainers::PyWordLevelTrainer>()?;
     m.add_class::<trainers::PyUnigramTrainer>()?;
     Ok(())
 }
@@ -69,6 +70,7 @@ fn pre_tokenizers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<pre_tokenizers::PyBertPreTokenizer>()?;
     m.add_class::<pre_tokenizers::PyMetaspace>()?;
     m.add_class::<pre_tokenizers::PyCharDelimiterSplit>()?;
+    m.add_class::<pre_tokenizers::PySplit>()?;
     m.add_class::<pre_tokenizers::PyPunctuation>()?;
     m.add_class::<pre_tokenizers::PySequence>()?;
     m.add_class::<pre_tokenizers::PyDigits>()?;
@@ -84,6 +86,11 @@ fn decoders(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<decoders::PyWordPieceDec>()?;
     m.add_class::<decoders::PyMetaspaceDec>()?;
     m.add_class::<decoders::PyBPEDecoder>()?;
+    m.add_class::<decoders::PyByteFallbackDec>()?;
+    m.add_class::<decoders::PyReplaceDec>()?;
+    m.add_class::<decoders::PyStrip>()?;
+    m.add_class::<decoders::PyFuse>()?;
+    m.add_class::<decoders::PySequenceDecoder>()?;
     Ok(())
 }
 
@@ -95,6 +102,8 @@ fn processors(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<processors::PyRobertaProcessing>()?;
     m.add_class::<processors::PyByteLevel>()?;
     m.add_class::<processors::PyTemplateProcessing>()?;
+    m.add_class::<processors::PySequence>()?;
+    m.add_class::<processors::PyChatTemplate>()?;
     Ok(())
 }
 
@@ -114,6 +123,9 @@ fn normalizers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<normalizers::PyNmt>()?;
     m.add_class::<normalizers::PyPrecompiled>()?;
     m.add_class::<normalizers::PyReplace>()?;
+    m.add_class::<normalizers::PyCharMap>()?;
+    m.add_class::<normalizers::PyScoped>()?;
+    m.add_class::<normalizers::PyCaseFold>()?;
     Ok(())
 }
 
@@ -135,6 +147,7 @@ fn tokenizers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<tokenizer::PyAddedToken>()?;
     m.add_class::<token::PyToken>()?;
     m.add_class::<encoding::PyEncoding>()?;
+    m.add_class::<encoding::PyBatchEncoding>()?;
     m.add_class::<utils::PyRegex>()?;
     m.add_class::<utils::PyNormalizedString>()?;
     m.add_class::<utils::PyPreTokenizedString>()?;
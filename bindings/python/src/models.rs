@@ -6,6 +6,7 @@ use crate::token::PyToken;
 use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::*;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use tk::models::bpe::{BpeBuilder, Merges, Vocab, BPE};
 use tk::models::unigram::Unigram;
@@ -49,6 +50,18 @@ impl Model for PyModel {
         self.model.tokenize(tokens)
     }
 
+    fn tokenize_nbest(&self, tokens: &str, n: usize) -> tk::Result<Vec<(Vec<Token>, f64)>> {
+        self.model.tokenize_nbest(tokens, n)
+    }
+
+    fn tokenize_with_rng(&self, tokens: &str, rng: &mut dyn RngCore) -> tk::Result<Vec<Token>> {
+        self.model.tokenize_with_rng(tokens, rng)
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        self.model.next_seed()
+    }
+
     fn token_to_id(&self, token: &str) -> Option<u32> {
         self.model.token_to_id(token)
     }
@@ -159,6 +172,12 @@ impl PyBPE {
                     }
                     "end_of_word_suffix" => builder = builder.end_of_word_suffix(value.extract()?),
                     "fuse_unk" => builder = builder.fuse_unk(value.extract()?),
+                    "byte_fallback" => builder = builder.byte_fallback(value.extract()?),
+                    "seed" => {
+                        if let Some(seed) = value.extract()? {
+                            builder = builder.seed(seed);
+                        }
+                    }
                     _ => println!("Ignored unknown kwarg option {}", key),
                 };
             }
@@ -415,23 +434,35 @@ pub struct PyUnigram {}
 #[pymethods]
 impl PyUnigram {
     #[new]
-    fn new(vocab: Option<Vec<(String, f64)>>, unk_id: Option<usize>) -> PyResult<(Self, PyModel)> {
-        if vocab.is_some() && unk_id.is_none() || vocab.is_none() && unk_id.is_some() {}
-        match (vocab, unk_id) {
-            (Some(vocab), Some(unk_id)) => {
-                let model = Unigram::from(vocab, unk_id).map_err(|e| {
-                    exceptions::PyException::new_err(format!("Error while loading Unigram: {}", e))
-                })?;
-                Ok((PyUnigram {}, PyModel::new(Arc::new(model.into()))))
+    #[args(
+        byte_fallback = "false",
+        alpha = "None",
+        nbest_size = "None",
+        seed = "None"
+    )]
+    fn new(
+        vocab: Option<Vec<(String, f64)>>,
+        unk_id: Option<usize>,
+        byte_fallback: bool,
+        alpha: Option<f64>,
+        nbest_size: Option<usize>,
+        seed: Option<u64>,
+    ) -> PyResult<(Self, PyModel)> {
+        let mut model = match (vocab, unk_id) {
+            (Some(vocab), Some(unk_id)) => Unigram::from(vocab, unk_id).map_err(|e| {
+                exceptions::PyException::new_err(format!("Error while loading Unigram: {}", e))
+            })?,
+            (None, None) => Unigram::default(),
+            _ => {
+                return Err(exceptions::PyValueError::new_err(
+                    "`vocab` and `unk_id` must be both specified",
+                ))
             }
-            (None, None) => Ok((
-                PyUnigram {},
-                PyModel::new(Arc::new(Unigram::default().into())),
-            )),
-            _ => Err(exceptions::PyValueError::new_err(
-                "`vocab` and `unk_id` must be both specified",
-            )),
-        }
+        };
+        model.set_byte_fallback(byte_fallback);
+        model.set_sampling(alpha, nbest_size);
+        model.set_seed(seed);
+        Ok((PyUnigram {}, PyModel::new(Arc::new(model.into()))))
     }
 }
 
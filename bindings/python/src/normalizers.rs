@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use pyo3::exceptions;
@@ -9,8 +10,8 @@ use crate::utils::{PyNormalizedString, PyNormalizedStringRefMut, PyPattern};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tk::normalizers::{
-    BertNormalizer, Lowercase, Nmt, NormalizerWrapper, Precompiled, Replace, Strip, StripAccents,
-    NFC, NFD, NFKC, NFKD,
+    BertNormalizer, CaseFold, CharMap, Lowercase, Nmt, NormalizerWrapper, Precompiled, Replace,
+    Scope, Scoped, Sequence, Strip, StripAccents, NFC, NFD, NFKC, NFKD,
 };
 use tk::{NormalizedString, Normalizer};
 use tokenizers as tk;
@@ -58,6 +59,11 @@ impl PyNormalizer {
                         Py::new(py, (PyPrecompiled {}, base))?.into_py(py)
                     }
                     NormalizerWrapper::Replace(_) => Py::new(py, (PyReplace {}, base))?.into_py(py),
+                    NormalizerWrapper::CharMap(_) => Py::new(py, (PyCharMap {}, base))?.into_py(py),
+                    NormalizerWrapper::Scoped(_) => Py::new(py, (PyScoped {}, base))?.into_py(py),
+                    NormalizerWrapper::CaseFold(_) => {
+                        Py::new(py, (PyCaseFold {}, base))?.into_py(py)
+                    }
                     NormalizerWrapper::Nmt(_) => Py::new(py, (PyNmt {}, base))?.into_py(py),
                 },
             },
@@ -418,6 +424,39 @@ impl PyPrecompiled {
                 .into(),
         ))
     }
+
+    #[staticmethod]
+    fn from_rules(py: Python, rules: Vec<(String, String)>) -> PyResult<Py<Self>> {
+        let precompiled: PyNormalizer = tk::normalizers::precompiled::from_rules(rules)
+            .map_err(|e| {
+                exceptions::PyException::new_err(format!(
+                    "Error while attempting to build Precompiled normalizer: {}",
+                    e
+                ))
+            })?
+            .into();
+        Py::new(py, (PyPrecompiled {}, precompiled))
+    }
+
+    #[staticmethod]
+    fn from_rules_file(py: Python, path: &str) -> PyResult<Py<Self>> {
+        let precompiled: PyNormalizer = tk::normalizers::precompiled::from_rules_file(path)
+            .map_err(|e| {
+                exceptions::PyException::new_err(format!(
+                    "Error while attempting to build Precompiled normalizer: {}",
+                    e
+                ))
+            })?
+            .into();
+        Py::new(py, (PyPrecompiled {}, precompiled))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        let rules: Vec<(&str, &str)> = vec![];
+        let charsmap =
+            ToPyResult(tk::normalizers::precompiled::compile_charsmap(rules)).into_py()?;
+        Ok(PyTuple::new(py, &[PyBytes::new(py, &charsmap)]))
+    }
 }
 
 #[pyclass(extends=PyNormalizer, module = "tokenizers.normalizers", name=Replace)]
@@ -425,12 +464,142 @@ pub struct PyReplace {}
 #[pymethods]
 impl PyReplace {
     #[new]
-    fn new(pattern: PyPattern, content: String) -> PyResult<(Self, PyNormalizer)> {
+    #[args(captures = "false")]
+    fn new(pattern: PyPattern, content: String, captures: bool) -> PyResult<(Self, PyNormalizer)> {
+        let replace = if captures {
+            Replace::with_captures(pattern, content)
+        } else {
+            Replace::new(pattern, content)
+        };
+        Ok((PyReplace {}, ToPyResult(replace).into_py()?.into()))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &["", ""]))
+    }
+}
+
+#[pyclass(extends=PyNormalizer, module = "tokenizers.normalizers", name=CharMap)]
+pub struct PyCharMap {}
+#[pymethods]
+impl PyCharMap {
+    #[new]
+    fn new(mapping: HashMap<String, String>) -> PyResult<(Self, PyNormalizer)> {
+        let mapping = mapping
+            .into_iter()
+            .map(|(source, target)| {
+                let mut chars = source.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok((c, target)),
+                    _ => Err(exceptions::PyValueError::new_err(format!(
+                        "CharMap expects single characters as keys, got `{}`",
+                        source
+                    ))),
+                }
+            })
+            .collect::<PyResult<_>>()?;
+        Ok((PyCharMap {}, CharMap::new(mapping).into()))
+    }
+
+    #[staticmethod]
+    fn from_file(py: Python, path: &str) -> PyResult<Py<Self>> {
+        let charmap: PyNormalizer = CharMap::from_file(path)
+            .map_err(|e| {
+                exceptions::PyValueError::new_err(format!(
+                    "Error while reading CharMap file: {}",
+                    e
+                ))
+            })?
+            .into();
+        Py::new(py, (PyCharMap {}, charmap))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[PyDict::new(py)]))
+    }
+}
+
+#[pyclass(extends=PyNormalizer, module = "tokenizers.normalizers", name=Scoped)]
+pub struct PyScoped {}
+#[pymethods]
+impl PyScoped {
+    #[new]
+    #[args(scripts = "None", pattern = "None")]
+    fn new(
+        normalizer: PyRef<PyNormalizer>,
+        scripts: Option<Vec<String>>,
+        pattern: Option<PyPattern>,
+    ) -> PyResult<(Self, PyNormalizer)> {
+        let scope = match (scripts, pattern) {
+            (Some(scripts), None) => Scope::Scripts(
+                scripts
+                    .into_iter()
+                    .map(|script| {
+                        serde_json::from_value(serde_json::Value::String(script.clone())).map_err(
+                            |_| {
+                                exceptions::PyValueError::new_err(format!(
+                                    "Unknown script `{}`",
+                                    script
+                                ))
+                            },
+                        )
+                    })
+                    .collect::<PyResult<_>>()?,
+            ),
+            (None, Some(pattern)) => Scope::Pattern(pattern.into()),
+            _ => {
+                return Err(exceptions::PyValueError::new_err(
+                    "Scoped expects exactly one of `scripts` or `pattern`",
+                ))
+            }
+        };
+
+        let custom_err =
+            || exceptions::PyValueError::new_err("Scoped does not support custom normalizers");
+        let wrapped = |n: &PyNormalizerWrapper| match n {
+            PyNormalizerWrapper::Wrapped(inner) => Ok(inner.clone()),
+            PyNormalizerWrapper::Custom(_) => Err(custom_err()),
+        };
+        let inner: NormalizerWrapper = match &normalizer.normalizer {
+            PyNormalizerTypeWrapper::Single(inner) => wrapped(inner)?,
+            PyNormalizerTypeWrapper::Sequence(inner) => {
+                Sequence::new(inner.iter().map(|n| wrapped(n)).collect::<PyResult<_>>()?).into()
+            }
+        };
+
         Ok((
-            PyReplace {},
-            ToPyResult(Replace::new(pattern, content)).into_py()?.into(),
+            PyScoped {},
+            ToPyResult(Scoped::new(scope, inner)).into_py()?.into(),
         ))
     }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        let base: PyNormalizer = Lowercase.into();
+        let normalizer = Py::new(py, (PyLowercase {}, base))?;
+        let args: Vec<PyObject> = vec![normalizer.into_py(py), PyList::empty(py).into_py(py)];
+        Ok(PyTuple::new(py, args))
+    }
+}
+
+#[pyclass(extends=PyNormalizer, module = "tokenizers.normalizers", name=CaseFold)]
+pub struct PyCaseFold {}
+#[pymethods]
+impl PyCaseFold {
+    #[new]
+    #[args(locale = "None")]
+    fn new(locale: Option<String>) -> PyResult<(Self, PyNormalizer)> {
+        let locale = locale
+            .map(|locale| {
+                serde_json::from_value(serde_json::Value::String(locale.clone())).map_err(|_| {
+                    exceptions::PyValueError::new_err(format!(
+                        "Unknown locale `{}`, expected one of `tr`, `az` or `lt`",
+                        locale
+                    ))
+                })
+            })
+            .transpose()?;
+        Ok((PyCaseFold {}, CaseFold::new(locale).into()))
+    }
 }
 
 #[cfg(test)]
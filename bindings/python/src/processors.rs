@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::sync::Arc;
 
@@ -10,7 +11,9 @@ use crate::error::ToPyResult;
 use serde::{Deserialize, Serialize};
 use tk::processors::bert::BertProcessing;
 use tk::processors::byte_level::ByteLevel;
+use tk::processors::chat::ChatTemplate;
 use tk::processors::roberta::RobertaProcessing;
+use tk::processors::sequence::Sequence;
 use tk::processors::template::{SpecialToken, Template};
 use tk::processors::PostProcessorWrapper;
 use tk::{Encoding, PostProcessor};
@@ -41,6 +44,7 @@ impl PyPostProcessor {
             PostProcessorWrapper::Template(_) => {
                 Py::new(py, (PyTemplateProcessing {}, base))?.into_py(py)
             }
+            PostProcessorWrapper::Sequence(_) => Py::new(py, (PySequence {}, base))?.into_py(py),
         })
     }
 }
@@ -59,6 +63,18 @@ impl PostProcessor for PyPostProcessor {
         self.processor
             .process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        self.processor.added_tokens_multi(n_sequences)
+    }
+
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> tk::Result<Encoding> {
+        self.processor.process_multi(encodings, add_special_tokens)
+    }
 }
 
 #[pymethods]
@@ -249,11 +265,17 @@ pub struct PyTemplateProcessing {}
 #[pymethods]
 impl PyTemplateProcessing {
     #[new]
-    #[args(single = "None", pair = "None", special_tokens = "None")]
+    #[args(
+        single = "None",
+        pair = "None",
+        special_tokens = "None",
+        multi = "None"
+    )]
     fn new(
         single: Option<PyTemplate>,
         pair: Option<PyTemplate>,
         special_tokens: Option<Vec<PySpecialToken>>,
+        multi: Option<Vec<PyTemplate>>,
     ) -> PyResult<(Self, PyPostProcessor)> {
         let mut builder = tk::processors::template::TemplateProcessing::builder();
 
@@ -266,6 +288,9 @@ impl PyTemplateProcessing {
         if let Some(sp) = special_tokens {
             builder.special_tokens(sp);
         }
+        for seq in multi.unwrap_or_default() {
+            builder.multi(seq.into());
+        }
         let processor = builder.build().map_err(exceptions::PyValueError::new_err)?;
 
         Ok((
@@ -275,6 +300,110 @@ impl PyTemplateProcessing {
     }
 }
 
+#[pyclass(extends=PyPostProcessor, module = "tokenizers.processors", name=Sequence)]
+pub struct PySequence {}
+#[pymethods]
+impl PySequence {
+    #[new]
+    fn new(processors: &PyList) -> PyResult<(Self, PyPostProcessor)> {
+        let mut sequence = Vec::with_capacity(processors.len());
+        for p in processors.iter() {
+            let processor: PyRef<PyPostProcessor> = p.extract()?;
+            sequence.push(processor.processor.as_ref().clone());
+        }
+        Ok((
+            PySequence {},
+            PyPostProcessor::new(Arc::new(Sequence::new(sequence).into())),
+        ))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[PyList::empty(py)]))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PyChatRole(Template, bool);
+
+impl FromPyObject<'_> for PyChatRole {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Ok((template, mask)) = ob.extract::<(PyTemplate, bool)>() {
+            Ok(Self(template.into(), mask))
+        } else if let Ok(template) = ob.extract::<PyTemplate>() {
+            Ok(Self(template.into(), false))
+        } else {
+            Err(exceptions::PyTypeError::new_err(
+                "Expected Union[Template, Tuple[Template, bool]]",
+            ))
+        }
+    }
+}
+
+#[pyclass(module = "tokenizers.processors", name=ChatTemplate)]
+#[derive(Clone)]
+pub struct PyChatTemplate {
+    pub template: ChatTemplate,
+}
+
+#[pymethods]
+impl PyChatTemplate {
+    #[new]
+    #[args(prefix = "None", generation_prompt = "None", special_tokens = "None")]
+    fn new(
+        roles: HashMap<String, PyChatRole>,
+        prefix: Option<PyTemplate>,
+        generation_prompt: Option<PyTemplate>,
+        special_tokens: Option<Vec<PySpecialToken>>,
+    ) -> PyResult<Self> {
+        let mut builder = ChatTemplate::builder();
+
+        for (role, PyChatRole(template, mask)) in roles {
+            builder.role(role, template, mask);
+        }
+        if let Some(seq) = prefix {
+            builder.prefix(seq.into());
+        }
+        if let Some(seq) = generation_prompt {
+            builder.generation_prompt(seq.into());
+        }
+        if let Some(sp) = special_tokens {
+            builder.special_tokens(sp);
+        }
+        let template = builder.build().map_err(exceptions::PyValueError::new_err)?;
+
+        Ok(PyChatTemplate { template })
+    }
+
+    fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
+        let data = serde_json::to_string(&self.template).map_err(|e| {
+            exceptions::PyException::new_err(format!(
+                "Error while attempting to pickle ChatTemplate: {}",
+                e.to_string()
+            ))
+        })?;
+        Ok(PyBytes::new(py, data.as_bytes()).to_object(py))
+    }
+
+    fn __setstate__(&mut self, py: Python, state: PyObject) -> PyResult<()> {
+        match state.extract::<&PyBytes>(py) {
+            Ok(s) => {
+                self.template = serde_json::from_slice(s.as_bytes()).map_err(|e| {
+                    exceptions::PyException::new_err(format!(
+                        "Error while attempting to unpickle ChatTemplate: {}",
+                        e.to_string()
+                    ))
+                })?;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[PyDict::new(py)]))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -7,20 +7,22 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 use pyo3::PyObjectProtocol;
 use tk::models::bpe::BPE;
+use tk::models::ModelWrapper;
 use tk::tokenizer::{
     PaddingDirection, PaddingParams, PaddingStrategy, PostProcessor, TokenizerImpl,
-    TruncationParams, TruncationStrategy,
+    TruncationDirection, TruncationParams, TruncationStrategy,
 };
 use tokenizers as tk;
 
 use super::decoders::PyDecoder;
-use super::encoding::PyEncoding;
+use super::encoding::{PyBatchEncoding, PyEncoding};
 use super::error::{PyError, ToPyResult};
 use super::models::PyModel;
 use super::normalizers::PyNormalizer;
 use super::pre_tokenizers::PyPreTokenizer;
 use super::trainers::PyTrainer;
-use crate::processors::PyPostProcessor;
+use super::utils::PySequenceIterator;
+use crate::processors::{PyChatTemplate, PyPostProcessor};
 
 #[pyclass(dict, module = "tokenizers", name=AddedToken)]
 pub struct PyAddedToken {
@@ -322,9 +324,17 @@ impl<'s> FromPyObject<'s> for TextEncodeInput<'s> {
                 let second = arr[1].extract::<TextInputSequence>()?;
                 return Ok(Self((first, second).into()));
             }
+            if arr.len() > 2 {
+                let sequences = arr
+                    .into_iter()
+                    .map(|s| Ok(s.extract::<TextInputSequence>()?.into()))
+                    .collect::<PyResult<Vec<_>>>()?;
+                return Ok(Self(tk::EncodeInput::Multi(sequences)));
+            }
         }
         Err(exceptions::PyTypeError::new_err(
-            "TextEncodeInput must be Union[TextInputSequence, Tuple[InputSequence, InputSequence]]",
+            "TextEncodeInput must be Union[TextInputSequence, Tuple[InputSequence, InputSequence], \
+            List[InputSequence]]",
         ))
     }
 }
@@ -349,10 +359,18 @@ impl<'s> FromPyObject<'s> for PreTokenizedEncodeInput<'s> {
                 let second = arr[1].extract::<PreTokenizedInputSequence>()?;
                 return Ok(Self((first, second).into()));
             }
+            if arr.len() > 2 {
+                let sequences = arr
+                    .into_iter()
+                    .map(|s| Ok(s.extract::<PreTokenizedInputSequence>()?.into()))
+                    .collect::<PyResult<Vec<_>>>()?;
+                return Ok(Self(tk::EncodeInput::Multi(sequences)));
+            }
         }
         Err(exceptions::PyTypeError::new_err(
             "PreTokenizedEncodeInput must be Union[PreTokenizedInputSequence, \
-            Tuple[PreTokenizedInputSequence, PreTokenizedInputSequence]]",
+            Tuple[PreTokenizedInputSequence, PreTokenizedInputSequence], \
+            List[PreTokenizedInputSequence]]",
         ))
     }
 }
@@ -378,6 +396,30 @@ impl PyTokenizer {
     fn from_model(model: PyModel) -> Self {
         PyTokenizer::new(TokenizerImpl::new(model))
     }
+
+    /// Update the sampling of the model, which must be a `Unigram`. The models are shared
+    /// and immutable on the Python side, so the tokenizer gets an updated copy of its model.
+    fn update_sampling(
+        &mut self,
+        alpha: Option<f64>,
+        nbest_size: Option<usize>,
+        seed: Option<u64>,
+    ) -> PyResult<()> {
+        let mut model = (*self.tokenizer.get_model().model).clone();
+        match &mut model {
+            ModelWrapper::Unigram(unigram) => {
+                unigram.set_sampling(alpha, nbest_size);
+                unigram.set_seed(seed);
+            }
+            _ => {
+                return Err(exceptions::PyException::new_err(
+                    "Sampling is only supported by the Unigram model",
+                ))
+            }
+        }
+        self.tokenizer.with_model(PyModel::new(Arc::new(model)));
+        Ok(())
+    }
 }
 
 #[pymethods]
@@ -472,6 +514,7 @@ impl PyTokenizer {
     fn enable_truncation(&mut self, max_length: usize, kwargs: Option<&PyDict>) -> PyResult<()> {
         let mut params = TruncationParams::default();
         params.max_length = max_length;
+        let mut head = None;
 
         if let Some(kwargs) = kwargs {
             for (key, value) in kwargs {
@@ -484,19 +527,41 @@ impl PyTokenizer {
                             "longest_first" => Ok(TruncationStrategy::LongestFirst),
                             "only_first" => Ok(TruncationStrategy::OnlyFirst),
                             "only_second" => Ok(TruncationStrategy::OnlySecond),
+                            "head_tail" => Ok(TruncationStrategy::HeadTail { head: 0 }),
                             _ => Err(PyError(format!(
                                 "Unknown `strategy`: `{}`. Use \
-                                 one of `longest_first`, `only_first`, or `only_second`",
+                                 one of `longest_first`, `only_first`, `only_second` or `head_tail`",
                                 value
                             ))
                             .into_pyerr::<exceptions::PyValueError>()),
                         }?
                     }
+                    "direction" => {
+                        let value: &str = value.extract()?;
+                        params.direction = match value {
+                            "left" => Ok(TruncationDirection::Left),
+                            "right" => Ok(TruncationDirection::Right),
+                            other => Err(PyError(format!(
+                                "Unknown `direction`: `{}`. Use \
+                                 one of `left` or `right`",
+                                other
+                            ))
+                            .into_pyerr::<exceptions::PyValueError>()),
+                        }?;
+                    }
+                    "head" => head = Some(value.extract()?),
+                    "word_boundaries" => params.word_boundaries = value.extract()?,
                     _ => println!("Ignored unknown kwarg option {}", key),
                 }
             }
         }
 
+        if let TruncationStrategy::HeadTail { head: h } = &mut params.strategy {
+            *h = head.ok_or_else(|| {
+                exceptions::PyValueError::new_err("The `head_tail` strategy requires `head`")
+            })?;
+        }
+
         self.tokenizer.with_truncation(Some(params));
 
         Ok(())
@@ -514,6 +579,11 @@ impl PyTokenizer {
             dict.set_item("max_length", params.max_length)?;
             dict.set_item("stride", params.stride)?;
             dict.set_item("strategy", params.strategy.as_ref())?;
+            dict.set_item("direction", params.direction.as_ref())?;
+            dict.set_item("word_boundaries", params.word_boundaries)?;
+            if let TruncationStrategy::HeadTail { head } = params.strategy {
+                dict.set_item("head", head)?;
+            }
 
             Ok(Some(dict))
         })
@@ -566,6 +636,16 @@ impl PyTokenizer {
                             params.strategy = PaddingStrategy::BatchLongest;
                         }
                     }
+                    "buckets" => {
+                        if let Some(buckets) = value.extract()? {
+                            params.strategy = PaddingStrategy::Buckets(buckets);
+                        }
+                    }
+                    "length_cap" => {
+                        if let Some(cap) = value.extract()? {
+                            params.strategy = PaddingStrategy::BatchLongestCapped(cap);
+                        }
+                    }
                     _ => println!("Ignored unknown kwarg option {}", key),
                 }
             }
@@ -585,13 +665,15 @@ impl PyTokenizer {
         self.tokenizer.get_padding().map_or(Ok(None), |params| {
             let dict = PyDict::new(py);
 
-            dict.set_item(
-                "length",
-                match params.strategy {
-                    tk::PaddingStrategy::BatchLongest => None,
-                    tk::PaddingStrategy::Fixed(size) => Some(size),
-                },
-            )?;
+            let (length, buckets, length_cap) = match &params.strategy {
+                tk::PaddingStrategy::BatchLongest => (None, None, None),
+                tk::PaddingStrategy::Fixed(size) => (Some(*size), None, None),
+                tk::PaddingStrategy::Buckets(buckets) => (None, Some(buckets.clone()), None),
+                tk::PaddingStrategy::BatchLongestCapped(cap) => (None, None, Some(*cap)),
+            };
+            dict.set_item("length", length)?;
+            dict.set_item("buckets", buckets)?;
+            dict.set_item("length_cap", length_cap)?;
             dict.set_item("pad_to_multiple_of", params.pad_to_multiple_of)?;
             dict.set_item("pad_id", params.pad_id)?;
             dict.set_item("pad_token", &params.pad_token)?;
@@ -602,6 +684,36 @@ impl PyTokenizer {
         })
     }
 
+    #[args(nbest_size = "None", seed = "None")]
+    fn enable_sampling(
+        &mut self,
+        alpha: f64,
+        nbest_size: Option<usize>,
+        seed: Option<u64>,
+    ) -> PyResult<()> {
+        self.update_sampling(Some(alpha), nbest_size, seed)
+    }
+
+    fn no_sampling(&mut self) -> PyResult<()> {
+        self.update_sampling(None, None, None)
+    }
+
+    #[getter]
+    fn get_sampling<'py>(&self, py: Python<'py>) -> PyResult<Option<&'py PyDict>> {
+        match self.tokenizer.get_model().model.as_ref() {
+            ModelWrapper::Unigram(unigram) => unigram.alpha().map_or(Ok(None), |alpha| {
+                let dict = PyDict::new(py);
+
+                dict.set_item("alpha", alpha)?;
+                dict.set_item("nbest_size", unigram.nbest_size())?;
+                dict.set_item("seed", unigram.seed())?;
+
+                Ok(Some(dict))
+            }),
+            _ => Ok(None),
+        }
+    }
+
     /// Input can be:
     /// encode("A single sequence")
     /// encode("A sequence", "And its pair")
@@ -610,14 +722,87 @@ impl PyTokenizer {
     ///     [ "A", "pre", "tokenized", "sequence" ], [ "And", "its", "pair" ],
     ///     is_pretokenized=True
     /// )
-    #[args(pair = "None", is_pretokenized = "false", add_special_tokens = "true")]
+    /// encode("A sequence", "Its pair", others=["And", "more", "sequences"])
+    #[args(
+        pair = "None",
+        is_pretokenized = "false",
+        add_special_tokens = "true",
+        seed = "None",
+        others = "None"
+    )]
     fn encode(
         &self,
         sequence: &PyAny,
         pair: Option<&PyAny>,
         is_pretokenized: bool,
         add_special_tokens: bool,
+        seed: Option<u64>,
+        others: Option<Vec<&PyAny>>,
     ) -> PyResult<PyEncoding> {
+        let extract = |sequence: &PyAny| -> PyResult<tk::InputSequence> {
+            Ok(if is_pretokenized {
+                sequence.extract::<PreTokenizedInputSequence>()?.into()
+            } else {
+                sequence.extract::<TextInputSequence>()?.into()
+            })
+        };
+        let mut sequences = vec![extract(sequence)?];
+        if let Some(pair) = pair {
+            sequences.push(extract(pair)?);
+        }
+        for other in others.unwrap_or_default() {
+            sequences.push(extract(other)?);
+        }
+        let input = match sequences.len() {
+            1 => tk::EncodeInput::Single(sequences.pop().unwrap()),
+            2 => {
+                let pair = sequences.pop().unwrap();
+                tk::EncodeInput::Dual(sequences.pop().unwrap(), pair)
+            }
+            _ => tk::EncodeInput::Multi(sequences),
+        };
+
+        let encoding = match seed {
+            Some(seed) => {
+                self.tokenizer
+                    .encode_char_offsets_with_seed(input, add_special_tokens, seed)
+            }
+            None => self
+                .tokenizer
+                .encode_char_offsets(input, add_special_tokens),
+        };
+        ToPyResult(encoding.map(|e| e.into())).into()
+    }
+
+    /// Encode a conversation, given as a list of `(role, content)` messages, using the chat
+    /// template of this tokenizer. Returns the encoding along with a mask marking the tokens
+    /// of the masked roles (usually the assistant) with 1.
+    #[args(add_generation_prompt = "false")]
+    fn encode_chat(
+        &self,
+        messages: Vec<(String, String)>,
+        add_generation_prompt: bool,
+    ) -> PyResult<(PyEncoding, Vec<u32>)> {
+        ToPyResult(
+            self.tokenizer
+                .encode_chat_char_offsets(&messages, add_generation_prompt)
+                .map(|(encoding, mask)| (encoding.into(), mask)),
+        )
+        .into()
+    }
+
+    /// Encode the given sequence (and its pair) in its `n` best alternative encodings,
+    /// returned along with their score. Only the models that support multiple tokenizations
+    /// (like `Unigram` or `BPE`) can return more than one encoding.
+    #[args(pair = "None", is_pretokenized = "false", add_special_tokens = "true")]
+    fn encode_nbest(
+        &self,
+        sequence: &PyAny,
+        n: usize,
+        pair: Option<&PyAny>,
+        is_pretokenized: bool,
+        add_special_tokens: bool,
+    ) -> PyResult<Vec<(PyEncoding, f64)>> {
         let sequence: tk::InputSequence = if is_pretokenized {
             sequence.extract::<PreTokenizedInputSequence>()?.into()
         } else {
@@ -637,8 +822,13 @@ impl PyTokenizer {
 
         ToPyResult(
             self.tokenizer
-                .encode_char_offsets(input, add_special_tokens)
-                .map(|e| e.into()),
+                .encode_nbest_char_offsets(input, n, add_special_tokens)
+                .map(|nbest| {
+                    nbest
+                        .into_iter()
+                        .map(|(encoding, score)| (encoding.into(), score))
+                        .collect()
+                }),
         )
         .into()
     }
@@ -650,13 +840,54 @@ impl PyTokenizer {
     ///   [ "A", "pre", "tokenized", "sequence" ],
     ///   ([ "A", "pre", "tokenized", "sequence" ], "And its pair")
     /// ])
-    #[args(is_pretokenized = "false", add_special_tokens = "true")]
+    #[args(is_pretokenized = "false", add_special_tokens = "true", seed = "None")]
     fn encode_batch(
         &self,
         input: Vec<&PyAny>,
         is_pretokenized: bool,
         add_special_tokens: bool,
+        seed: Option<u64>,
     ) -> PyResult<Vec<PyEncoding>> {
+        let input: Vec<tk::EncodeInput> = input
+            .into_iter()
+            .map(|o| {
+                let input: tk::EncodeInput = if is_pretokenized {
+                    o.extract::<PreTokenizedEncodeInput>()?.into()
+                } else {
+                    o.extract::<TextEncodeInput>()?.into()
+                };
+                Ok(input)
+            })
+            .collect::<PyResult<Vec<tk::EncodeInput>>>()?;
+        let gil = Python::acquire_gil();
+        gil.python().allow_threads(|| {
+            let encodings = match seed {
+                Some(seed) => self.tokenizer.encode_batch_char_offsets_with_seed(
+                    input,
+                    add_special_tokens,
+                    seed,
+                ),
+                None => self
+                    .tokenizer
+                    .encode_batch_char_offsets(input, add_special_tokens),
+            };
+            ToPyResult(encodings.map(|encodings| encodings.into_iter().map(|e| e.into()).collect()))
+                .into()
+        })
+    }
+
+    /// Input can be:
+    /// encode_batch_flattened([
+    ///   ("A question", "And a long context"),
+    ///   ("Another question", "And its context")
+    /// ])
+    #[args(is_pretokenized = "false", add_special_tokens = "true")]
+    fn encode_batch_flattened(
+        &self,
+        input: Vec<&PyAny>,
+        is_pretokenized: bool,
+        add_special_tokens: bool,
+    ) -> PyResult<(Vec<PyEncoding>, Vec<usize>)> {
         let input: Vec<tk::EncodeInput> = input
             .into_iter()
             .map(|o| {
@@ -672,13 +903,48 @@ impl PyTokenizer {
         gil.python().allow_threads(|| {
             ToPyResult(
                 self.tokenizer
-                    .encode_batch_char_offsets(input, add_special_tokens)
-                    .map(|encodings| encodings.into_iter().map(|e| e.into()).collect()),
+                    .encode_batch_flattened_char_offsets(input, add_special_tokens)
+                    .map(|batch| {
+                        (
+                            batch.encodings.into_iter().map(|e| e.into()).collect(),
+                            batch.overflow_to_sample_mapping,
+                        )
+                    }),
             )
             .into()
         })
     }
 
+    #[args(is_pretokenized = "false", add_special_tokens = "true")]
+    fn encode_batch_arrays(
+        &self,
+        input: Vec<&PyAny>,
+        is_pretokenized: bool,
+        add_special_tokens: bool,
+    ) -> PyResult<PyBatchEncoding> {
+        let input: Vec<tk::EncodeInput> = input
+            .into_iter()
+            .map(|o| {
+                let input: tk::EncodeInput = if is_pretokenized {
+                    o.extract::<PreTokenizedEncodeInput>()?.into()
+                } else {
+                    o.extract::<TextEncodeInput>()?.into()
+                };
+                Ok(input)
+            })
+            .collect::<PyResult<Vec<tk::EncodeInput>>>()?;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let batch: PyResult<_> = py.allow_threads(|| {
+            ToPyResult(
+                self.tokenizer
+                    .encode_batch_contiguous_char_offsets(input, add_special_tokens),
+            )
+            .into()
+        });
+        Ok(PyBatchEncoding::new(py, batch?))
+    }
+
     fn decode(&self, ids: Vec<u32>, skip_special_tokens: Option<bool>) -> PyResult<String> {
         ToPyResult(
             self.tokenizer
@@ -756,6 +1022,28 @@ impl PyTokenizer {
             .allow_threads(|| ToPyResult(self.tokenizer.train_and_replace(trainer, files)).into())
     }
 
+    /// Train the model using the given trainer on all the sequences yielded by the given
+    /// iterator, which can be any Python iterable of `str` (a list, a generator, ...).
+    /// The optional `length` is the number of sequences, used to show the progress.
+    #[args(length = "None")]
+    fn train_from_iterator(
+        &mut self,
+        trainer: &PyTrainer,
+        iterator: &PyAny,
+        length: Option<usize>,
+    ) -> PyResult<()> {
+        let py = iterator.py();
+        let mut sequences = PySequenceIterator::new(iterator)?;
+        let result = py.allow_threads(|| {
+            self.tokenizer
+                .train_from_iter_and_replace(trainer, &mut sequences, length)
+        });
+        if let Some(error) = sequences.take_error(py) {
+            return Err(error);
+        }
+        ToPyResult(result).into()
+    }
+
     #[args(pair = "None", add_special_tokens = true)]
     fn post_process(
         &self,
@@ -827,6 +1115,21 @@ impl PyTokenizer {
         self.tokenizer.with_post_processor(processor.clone());
     }
 
+    #[getter]
+    fn get_chat_template(&self) -> Option<PyChatTemplate> {
+        self.tokenizer
+            .get_chat_template()
+            .map(|template| PyChatTemplate {
+                template: template.clone(),
+            })
+    }
+
+    #[setter]
+    fn set_chat_template(&mut self, template: Option<PyRef<PyChatTemplate>>) {
+        self.tokenizer
+            .with_chat_template(template.map(|t| t.template.clone()));
+    }
+
     #[getter]
     fn get_decoder(&self) -> PyResult<PyObject> {
         if let Some(dec) = self.tokenizer.get_decoder() {
@@ -0,0 +1,55 @@
+use pyo3::exceptions;
+use pyo3::prelude::*;
+
+/// An `Iterator` over the strings yielded by a Python iterator, that can be sent to other
+/// threads. The GIL is acquired for each new item, so it must be released by the thread
+/// consuming it.
+///
+/// The iteration stops on the first error raised by the Python iterator (or if it yields
+/// something else than a `str`), and this error is kept so it can be raised later.
+pub struct PySequenceIterator {
+    iter: PyObject,
+    error: Option<PyObject>,
+}
+
+impl PySequenceIterator {
+    pub fn new(iterable: &PyAny) -> PyResult<Self> {
+        Ok(Self {
+            iter: iterable.iter()?.to_object(iterable.py()),
+            error: None,
+        })
+    }
+
+    /// Returns the error that stopped the iteration, if any
+    pub fn take_error(&mut self, py: Python) -> Option<PyErr> {
+        self.error
+            .take()
+            .map(|error| PyErr::from_instance(error.as_ref(py)))
+    }
+}
+
+impl Iterator for PySequenceIterator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        Python::with_gil(|py| {
+            let next = self
+                .iter
+                .as_ref(py)
+                .call_method0("__next__")
+                .and_then(|item| item.extract::<String>());
+            match next {
+                Ok(sequence) => Some(sequence),
+                Err(e) if e.is_instance::<exceptions::PyStopIteration>(py) => None,
+                Err(e) => {
+                    self.error = Some(e.to_object(py));
+                    None
+                }
+            }
+        })
+    }
+}
@@ -1,10 +1,12 @@
 use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 
+mod iterators;
 mod normalization;
 mod pretokenization;
 mod regex;
 
+pub use iterators::*;
 pub use normalization::*;
 pub use pretokenization::*;
 pub use regex::*;
@@ -1,7 +1,18 @@
 import pytest
 import pickle
 
-from tokenizers.decoders import Decoder, ByteLevel, WordPiece, Metaspace, BPEDecoder
+from tokenizers.decoders import (
+    Decoder,
+    ByteLevel,
+    WordPiece,
+    Metaspace,
+    BPEDecoder,
+    ByteFallback,
+    Replace,
+    Strip,
+    Fuse,
+    Sequence,
+)
 
 
 class TestByteLevel:
@@ -68,3 +79,68 @@ class TestBPEDecoder:
         )
         decoder = BPEDecoder(suffix="_")
         assert decoder.decode(["My_", "na", "me_", "is_", "Jo", "hn_"]) == "My name is John"
+
+
+class TestByteFallback:
+    def test_instantiate(self):
+        assert ByteFallback() is not None
+        assert isinstance(ByteFallback(), Decoder)
+        assert isinstance(ByteFallback(), ByteFallback)
+        assert isinstance(pickle.loads(pickle.dumps(ByteFallback())), ByteFallback)
+
+    def test_decoding(self):
+        decoder = ByteFallback()
+        assert decoder.decode(["My", "<0x20>", "na", "me"]) == "My name"
+        assert decoder.decode(["<0xE5>", "<0x8f>", "<0xab>"]) == "叫"
+        assert decoder.decode(["<0xE5>", "<0x8f>", "a"]) == "��a"
+
+
+class TestReplace:
+    def test_instantiate(self):
+        assert Replace("▁", " ") is not None
+        assert isinstance(Replace("▁", " "), Decoder)
+        assert isinstance(Replace("▁", " "), Replace)
+        assert isinstance(pickle.loads(pickle.dumps(Replace("▁", " "))), Replace)
+
+    def test_decoding(self):
+        decoder = Replace("▁", " ")
+        assert decoder.decode(["▁My", "▁na", "me"]) == " My name"
+
+
+class TestStrip:
+    def test_instantiate(self):
+        assert Strip() is not None
+        assert Strip(content="_", start=1, stop=1) is not None
+        with pytest.raises(Exception, match="content must be a character"):
+            Strip(content="")
+        assert isinstance(Strip(), Decoder)
+        assert isinstance(Strip(), Strip)
+        assert isinstance(pickle.loads(pickle.dumps(Strip(" ", 1, 0))), Strip)
+
+    def test_decoding(self):
+        decoder = Strip(" ", 1, 0)
+        assert decoder.decode([" My", " name"]) == "Myname"
+
+
+class TestFuse:
+    def test_instantiate(self):
+        assert Fuse() is not None
+        assert isinstance(Fuse(), Decoder)
+        assert isinstance(Fuse(), Fuse)
+        assert isinstance(pickle.loads(pickle.dumps(Fuse())), Fuse)
+
+    def test_decoding(self):
+        decoder = Fuse()
+        assert decoder.decode(["My", " name"]) == "My name"
+
+
+class TestSequence:
+    def test_instantiate(self):
+        assert Sequence([]) is not None
+        assert isinstance(Sequence([]), Decoder)
+        assert isinstance(Sequence([]), Sequence)
+        assert isinstance(pickle.loads(pickle.dumps(Sequence([Fuse()]))), Sequence)
+
+    def test_decoding(self):
+        decoder = Sequence([Replace("▁", " "), ByteFallback(), Fuse(), Strip(" ", 1, 0)])
+        assert decoder.decode(["▁My", "▁na", "<0xC3>", "<0xA9>", "me"]) == "My naéme"
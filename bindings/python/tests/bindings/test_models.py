@@ -3,7 +3,8 @@ import pickle
 
 from ..utils import data_dir, roberta_files, bert_files
 
-from tokenizers.models import Model, BPE, WordPiece, WordLevel
+from tokenizers import Tokenizer
+from tokenizers.models import Model, BPE, WordPiece, WordLevel, Unigram
 
 
 class TestBPE:
@@ -74,3 +75,26 @@ class TestWordLevel:
             assert isinstance(WordLevel(roberta_files["vocab"]), Model)
         with pytest.deprecated_call():
             assert isinstance(WordLevel(roberta_files["vocab"]), WordLevel)
+
+
+class TestUnigram:
+    def test_instantiate(self):
+        assert isinstance(Unigram(), Model)
+        assert isinstance(Unigram(), Unigram)
+
+        vocab = [("<unk>", 0.0), ("a", -1.0), ("b", -1.0), ("ab", -1.5)]
+        assert isinstance(Unigram(vocab, 0), Unigram)
+        assert isinstance(Unigram(vocab, 0, byte_fallback=True), Unigram)
+        with pytest.raises(ValueError, match="`vocab` and `unk_id` must be both specified"):
+            Unigram(vocab=vocab)
+
+        assert isinstance(pickle.loads(pickle.dumps(Unigram(vocab, 0, alpha=0.1))), Unigram)
+
+    def test_sampling(self):
+        vocab = [("<unk>", 0.0), ("a", -1.0), ("b", -1.0), ("ab", -1.5), ("ba", -1.5)]
+        tokenizer = Tokenizer(Unigram(vocab, 0, alpha=0.1, seed=42))
+        tokens = [tokenizer.encode("ababab").tokens for _ in range(10)]
+        assert all("".join(t) == "ababab" for t in tokens)
+        # The same seed replays the same segmentations
+        tokenizer = Tokenizer(Unigram(vocab, 0, alpha=0.1, seed=42))
+        assert [tokenizer.encode("ababab").tokens for _ in range(10)] == tokens
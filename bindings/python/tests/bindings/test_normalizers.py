@@ -1,9 +1,23 @@
 import pickle
 import pytest
 
-from tokenizers import Tokenizer, NormalizedString
+from tokenizers import Tokenizer, NormalizedString, Regex
 from tokenizers.models import BPE
-from tokenizers.normalizers import Normalizer, BertNormalizer, Sequence, Lowercase, Strip
+from tokenizers.normalizers import (
+    Normalizer,
+    BertNormalizer,
+    Sequence,
+    Lowercase,
+    Strip,
+    Replace,
+    CharMap,
+    Precompiled,
+    Scoped,
+    CaseFold,
+    NFKC,
+    StripAccents,
+    NFD,
+)
 
 
 class TestBertNormalizer:
@@ -96,6 +110,94 @@ class TestStrip:
         assert output == "hello"
 
 
+class TestReplace:
+    def test_instantiate(self):
+        assert isinstance(Replace("a", "b"), Normalizer)
+        assert isinstance(Replace(Regex(r"(\d)"), "$1", captures=True), Replace)
+        replace = pickle.loads(pickle.dumps(Replace(Regex(r"(\d)"), "<$1>", captures=True)))
+        assert isinstance(replace, Replace)
+        assert replace.normalize_str("a1") == "a<1>"
+
+    def test_captures(self):
+        normalizer = Replace(Regex(r"(\d+)-(\d+)"), "(${1}) $2", captures=True)
+        output = normalizer.normalize_str("call 555-1234")
+        assert output == "call (555) 1234"
+
+        # String patterns always use a literal content
+        assert Replace("-", "$1", captures=True).normalize_str("a-b") == "a$1b"
+        # Without captures, the content is literal
+        assert Replace(Regex(r"\d"), "US$").normalize_str("5") == "US$"
+
+        with pytest.raises(Exception, match="unknown group"):
+            Replace(Regex(r"(\d)"), "$2", captures=True)
+
+class TestCharMap:
+    def test_instantiate(self):
+        assert isinstance(CharMap({"ﬁ": "fi"}), Normalizer)
+        assert isinstance(CharMap({"ﬁ": "fi"}), CharMap)
+        assert isinstance(pickle.loads(pickle.dumps(CharMap({"ﬁ": "fi"}))), CharMap)
+        with pytest.raises(ValueError, match="single characters"):
+            CharMap({"ab": "c"})
+
+    def test_normalize(self):
+        normalizer = CharMap({"Ａ": "A", "ﬁ": "fi", "\u200b": ""})
+        assert normalizer.normalize_str("Ａ ﬁ\u200ble") == "A file"
+
+    def test_from_file(self, tmp_path):
+        path = tmp_path / "charmap.tsv"
+        path.write_text("# Ligatures\nU+FB01\tfi\nＡ\tA\n", encoding="utf-8")
+        normalizer = CharMap.from_file(str(path))
+        assert isinstance(normalizer, CharMap)
+        assert normalizer.normalize_str("Ａ ﬁle") == "A file"
+
+class TestPrecompiled:
+    def test_from_rules(self):
+        normalizer = Precompiled.from_rules([("Ａ", "A"), ("ﬁ", "fi"), ("\u200b", "")])
+        assert isinstance(normalizer, Precompiled)
+        assert isinstance(pickle.loads(pickle.dumps(normalizer)), Precompiled)
+        assert normalizer.normalize_str("Ａ ﬁle") == "A file"
+
+    def test_from_rules_file(self, tmp_path):
+        path = tmp_path / "rules.tsv"
+        path.write_text("# Ligatures\nFB01\t66 69\n", encoding="utf-8")
+        normalizer = Precompiled.from_rules_file(str(path))
+        assert normalizer.normalize_str("ﬁle") == "file"
+
+class TestScoped:
+    def test_instantiate(self):
+        assert isinstance(Scoped(Lowercase(), scripts=["Latin"]), Normalizer)
+        assert isinstance(Scoped(Lowercase(), pattern="A"), Scoped)
+        assert isinstance(pickle.loads(pickle.dumps(Scoped(Lowercase(), pattern="A"))), Scoped)
+        with pytest.raises(ValueError, match="exactly one"):
+            Scoped(Lowercase())
+        with pytest.raises(ValueError, match="Unknown script"):
+            Scoped(Lowercase(), scripts=["Klingon"])
+
+    def test_scripts(self):
+        normalizer = Scoped(
+            Sequence([NFD(), StripAccents(), Lowercase()]), scripts=["Latin", "Cyrillic"]
+        )
+        assert normalizer.normalize_str("Élan ПРИВЕТ 東京") == "elan привет 東京"
+
+    def test_pattern(self):
+        normalizer = Scoped(Lowercase(), pattern=Regex(r"`[^`]*`"))
+        assert normalizer.normalize_str("Keep `THIS` Not This") == "Keep `this` Not This"
+
+        normalizer = Scoped(NFKC(), pattern="ＡＢ")
+        assert normalizer.normalize_str("ＡＢ ＣＤ") == "AB ＣＤ"
+
+class TestCaseFold:
+    def test_instantiate(self):
+        assert isinstance(CaseFold(), Normalizer)
+        assert isinstance(CaseFold(locale="tr"), CaseFold)
+        assert isinstance(pickle.loads(pickle.dumps(CaseFold(locale="tr"))), CaseFold)
+        with pytest.raises(ValueError, match="Unknown locale"):
+            CaseFold(locale="fr")
+
+    def test_normalize(self):
+        assert CaseFold().normalize_str("Straße ΣΊΣΥΦΟΣ") == "strasse σίσυφοσ"
+        assert CaseFold(locale="tr").normalize_str("IĞDIR İzmir") == "ığdır izmir"
+
 class TestCustomNormalizer:
     class BadCustomNormalizer:
         def normalize(self, normalized, wrong):
@@ -1,6 +1,7 @@
 import pytest
 import pickle
 
+from tokenizers import Regex
 from tokenizers.pre_tokenizers import (
     PreTokenizer,
     ByteLevel,
@@ -9,6 +10,7 @@ from tokenizers.pre_tokenizers import (
     BertPreTokenizer,
     Metaspace,
     CharDelimiterSplit,
+    Split,
     Punctuation,
     Sequence,
     Digits,
@@ -76,6 +78,30 @@ class TestCharDelimiterSplit:
         assert isinstance(pickle.loads(pickle.dumps(CharDelimiterSplit("-"))), CharDelimiterSplit)
 
 
+class TestSplit:
+    def test_instantiate(self):
+        pre_tokenizer = Split(pattern=" ", behavior="removed")
+        assert pre_tokenizer is not None
+        assert isinstance(pre_tokenizer, PreTokenizer)
+        assert isinstance(pre_tokenizer, Split)
+        assert isinstance(pickle.loads(pickle.dumps(Split(" ", "removed"))), Split)
+
+        # test with invert=True
+        pre_tokenizer_with_invert = Split(pattern=" ", behavior="isolated", invert=True)
+        assert pre_tokenizer_with_invert is not None
+        assert isinstance(pre_tokenizer_with_invert, PreTokenizer)
+        assert isinstance(pre_tokenizer_with_invert, Split)
+        assert isinstance(pickle.loads(pickle.dumps(Split(" ", "removed", True))), Split)
+
+    def test_regex(self):
+        pre_tokenizer = Split(Regex(r"\w+|[^\w\s]+"), "removed", invert=True)
+        assert pre_tokenizer.pre_tokenize_str("Hey friend!") == [
+            ("Hey", (0, 3)),
+            ("friend", (4, 10)),
+            ("!", (10, 11)),
+        ]
+
+
 class TestPunctuation:
     def test_instantiate(self):
         assert Punctuation() is not None
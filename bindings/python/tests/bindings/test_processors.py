@@ -12,6 +12,8 @@ from tokenizers.processors import (
     RobertaProcessing,
     ByteLevel,
     TemplateProcessing,
+    Sequence,
+    ChatTemplate,
 )
 
 
@@ -172,3 +174,117 @@ class TestTemplateProcessing:
         tokenizer.post_processor = self.get_roberta()
         template = tokenizer.encode("my name is john", "pair")
         assert original.ids == template.ids
+
+    def test_multi(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_special_tokens(["[SEP]", "[CLS]"])
+        tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
+        tokenizer.post_processor = TemplateProcessing(
+            single="[CLS] $A [SEP]",
+            pair="[CLS] $A [SEP] $B:1 [SEP]:1",
+            multi=["[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2"],
+            special_tokens=[("[CLS]", 1), ("[SEP]", 0)],
+        )
+
+        output = tokenizer.encode_batch([["my name", "is john", "pair"]])[0]
+        assert output.tokens == [
+            "[CLS]",
+            "my",
+            "name",
+            "[SEP]",
+            "is",
+            "john",
+            "[SEP]",
+            "pair",
+            "[SEP]",
+        ]
+        assert output.type_ids == [0, 0, 0, 0, 1, 1, 1, 2, 2]
+
+
+    def test_flags(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_special_tokens(["[SEP]", "[CLS]"])
+        tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
+        tokenizer.post_processor = TemplateProcessing(
+            single="[CLS]:0:noattn $A [SEP]:nospecial",
+            special_tokens=[("[CLS]", 1), ("[SEP]", 0)],
+        )
+
+        output = tokenizer.encode("my name")
+        assert output.tokens == ["[CLS]", "my", "name", "[SEP]"]
+        assert output.attention_mask == [0, 1, 1, 1]
+        assert output.special_tokens_mask == [1, 0, 0, 0]
+
+        with pytest.raises(Exception, match="Cannot build Piece"):
+            TemplateProcessing(single="[CLS]:0:unknown $A")
+
+class TestSequence:
+    def test_instantiate(self):
+        processor = Sequence([ByteLevel(), TemplateProcessing(single="$A")])
+        assert processor is not None
+        assert isinstance(processor, PostProcessor)
+        assert isinstance(processor, Sequence)
+        assert isinstance(pickle.loads(pickle.dumps(processor)), Sequence)
+
+    def test_processing(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_special_tokens(["<s>", "</s>"])
+        tokenizer.add_tokens(["my", "name", "pair"])
+        tokenizer.post_processor = Sequence(
+            [
+                ByteLevel(trim_offsets=True),
+                TemplateProcessing(
+                    single="<s> $A </s>",
+                    pair="<s> $A </s> $B:1 </s>:1",
+                    special_tokens=[("<s>", 0), ("</s>", 1)],
+                ),
+            ]
+        )
+
+        output = tokenizer.encode("my name", "pair")
+        assert output.tokens == ["<s>", "my", "name", "</s>", "pair", "</s>"]
+        assert output.type_ids == [0, 0, 0, 0, 1, 1]
+
+
+class TestChatTemplate:
+    def get_template(self):
+        return ChatTemplate(
+            roles={
+                "user": "<|user|> $A <|end|>",
+                "assistant": ("<|assistant|> $A:1 <|end|>:1", True),
+            },
+            prefix="<s>",
+            generation_prompt="<|assistant|>:1",
+            special_tokens=[("<s>", 0), ("<|end|>", 1), ("<|user|>", 2), ("<|assistant|>", 3)],
+        )
+
+    def test_instantiate(self):
+        template = self.get_template()
+        assert isinstance(template, ChatTemplate)
+        assert isinstance(pickle.loads(pickle.dumps(template)), ChatTemplate)
+
+        with pytest.raises(ValueError, match="Missing SpecialToken"):
+            ChatTemplate(roles={"user": "<|user|> $A"})
+        with pytest.raises(ValueError, match="must use the content `\\$A` only"):
+            ChatTemplate(roles={"user": "$A $B"})
+
+    def test_encode_chat(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_special_tokens(["<s>", "<|end|>", "<|user|>", "<|assistant|>"])
+        tokenizer.add_tokens(["hello", "hi"])
+        tokenizer.chat_template = pickle.loads(pickle.dumps(self.get_template()))
+
+        messages = [("user", "hello"), ("assistant", "hi")]
+        encoding, mask = tokenizer.encode_chat(messages, add_generation_prompt=True)
+        assert encoding.tokens == [
+            "<s>",
+            "<|user|>",
+            "hello",
+            "<|end|>",
+            "<|assistant|>",
+            "hi",
+            "<|end|>",
+            "<|assistant|>",
+        ]
+        assert encoding.type_ids == [0, 0, 0, 0, 0, 1, 1, 1]
+        assert mask == [0, 0, 0, 0, 0, 1, 1, 0]
@@ -8,10 +8,15 @@ from ..utils import (
     multiprocessing_with_parallelism,
 )
 
-from tokenizers import AddedToken, Tokenizer, Encoding
-from tokenizers.models import Model, BPE, WordPiece
+from tokenizers import AddedToken, Tokenizer, Encoding, BatchEncoding
+from tokenizers.models import Model, BPE, WordPiece, Unigram
 from tokenizers.pre_tokenizers import ByteLevel
-from tokenizers.processors import RobertaProcessing, BertProcessing
+from tokenizers.processors import (
+    RobertaProcessing,
+    BertProcessing,
+    TemplateProcessing,
+    ChatTemplate,
+)
 from tokenizers.normalizers import Lowercase
 from tokenizers.implementations import BertWordPieceTokenizer
 
@@ -143,6 +148,131 @@ class TestTokenizer:
         output = tokenizer.encode_batch(["my name is john", ("my name is john", "pair")])
         assert len(output) == 2
 
+    def test_encode_nbest(self):
+        vocab = [("<unk>", 0.0), ("a", -1.0), ("b", -1.0), ("ab", -1.5)]
+        tokenizer = Tokenizer(Unigram(vocab, 0))
+
+        output = tokenizer.encode_nbest("ab", 3)
+        assert [encoding.tokens for encoding, _ in output] == [["ab"], ["a", "b"]]
+        assert [score for _, score in output] == [-1.5, -2.0]
+        assert output[1][0].offsets == [(0, 1), (1, 2)]
+
+        output = tokenizer.encode_nbest("ab", 1, "b")
+        assert len(output) == 1
+        assert output[0][0].tokens == ["ab", "b"]
+
+        # BPE gives the alternative orders of its merges
+        vocab = {"a": 0, "b": 1, "c": 2, "bc": 3, "ab": 4}
+        tokenizer = Tokenizer(BPE(vocab, [("b", "c"), ("a", "b")]))
+        output = tokenizer.encode_nbest("abc", 3)
+        assert [encoding.tokens for encoding, _ in output] == [["a", "bc"], ["ab", "c"]]
+        assert [score for _, score in output] == [0.0, -1.0]
+
+        # Other models only have one possible encoding
+        tokenizer = Tokenizer(WordPiece())
+        tokenizer.add_tokens(["my", "name"])
+        output = tokenizer.encode_nbest("my name", 2)
+        assert len(output) == 1
+        assert output[0][0].tokens == ["my", "name"]
+        assert output[0][1] == 0.0
+
+    def test_encode_multi(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
+        tokenizer.post_processor = TemplateProcessing(
+            single="[CLS] $A [SEP]",
+            pair="[CLS] $A [SEP] $B:1 [SEP]:1",
+            multi=["[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2"],
+            special_tokens=[("[CLS]", 1), ("[SEP]", 0)],
+        )
+
+        output = tokenizer.encode("my name", "is", others=["john"])
+        assert output.tokens == ["[CLS]", "my", "name", "[SEP]", "is", "[SEP]", "john", "[SEP]"]
+        assert output.type_ids == [0, 0, 0, 0, 1, 1, 2, 2]
+
+        output = tokenizer.encode(
+            ["my", "name"],
+            ["is"],
+            is_pretokenized=True,
+            add_special_tokens=False,
+            others=[["john"]],
+        )
+        assert output.tokens == ["my", "name", "is", "john"]
+        assert output.words == [0, 1, 0, 0]
+
+        # Too many special tokens for the max length
+        tokenizer.enable_truncation(3)
+        with pytest.raises(Exception, match="max length is too low"):
+            tokenizer.encode("my", "name", others=["john"])
+
+    def test_encode_with_seed(self):
+        vocab = {"a": 0, "b": 1, "ab": 2}
+        merges = [("a", "b")]
+        tokenizer = Tokenizer(BPE(vocab, merges, dropout=0.5))
+
+        output = tokenizer.encode("abababab", seed=42)
+        for _ in range(10):
+            assert tokenizer.encode("abababab", seed=42).tokens == output.tokens
+
+        # Each input of a batch gets its own random generator, built from the seed
+        batch = [e.tokens for e in tokenizer.encode_batch(["abababab"] * 16, seed=42)]
+        assert batch[0] == output.tokens
+        assert [e.tokens for e in tokenizer.encode_batch(["abababab"] * 16, seed=42)] == batch
+
+        # With the seed of the model, a fresh tokenizer replays the same encodings
+        def encode_batches(tokenizer):
+            return [
+                [e.tokens for e in tokenizer.encode_batch(["abababab"] * 16)] for _ in range(3)
+            ]
+
+        batches = encode_batches(Tokenizer(BPE(vocab, merges, dropout=0.5, seed=42)))
+        assert batches[0] != batches[1]
+        assert encode_batches(Tokenizer(BPE(vocab, merges, dropout=0.5, seed=42))) == batches
+
+    def test_sampling(self):
+        vocab = [("<unk>", 0.0), ("a", -1.0), ("b", -1.0), ("ab", -1.5), ("ba", -1.5)]
+        tokenizer = Tokenizer(Unigram(vocab, 0))
+        assert tokenizer.sampling is None
+        assert tokenizer.encode("abab").tokens == ["ab", "ab"]
+
+        tokenizer.enable_sampling(alpha=0.1, nbest_size=4, seed=42)
+        assert tokenizer.sampling == {"alpha": 0.1, "nbest_size": 4, "seed": 42}
+        segmentations = {tuple(tokenizer.encode("abababab").tokens) for _ in range(50)}
+        assert len(segmentations) > 1
+
+        tokenizer.no_sampling()
+        assert tokenizer.sampling is None
+        assert tokenizer.encode("abab").tokens == ["ab", "ab"]
+
+        with pytest.raises(Exception, match="only supported by the Unigram model"):
+            Tokenizer(BPE()).enable_sampling(alpha=0.1)
+
+    def test_encode_chat(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_special_tokens(["<user>", "<bot>"])
+        tokenizer.add_tokens(["hello", "hi"])
+        assert tokenizer.chat_template is None
+        with pytest.raises(Exception, match="no chat template"):
+            tokenizer.encode_chat([("user", "hello")])
+
+        tokenizer.chat_template = ChatTemplate(
+            roles={"user": "<user> $A", "assistant": ("<bot> $A", True)},
+            special_tokens=[("<user>", 0), ("<bot>", 1)],
+        )
+        assert isinstance(tokenizer.chat_template, ChatTemplate)
+
+        encoding, mask = tokenizer.encode_chat([("user", "hello"), ("assistant", "hi")])
+        assert encoding.tokens == ["<user>", "hello", "<bot>", "hi"]
+        assert mask == [0, 0, 0, 1]
+
+        # The chat template is saved with the tokenizer
+        tokenizer = Tokenizer.from_str(tokenizer.to_str())
+        encoding, mask = tokenizer.encode_chat([("user", "hello"), ("assistant", "hi")])
+        assert mask == [0, 0, 0, 1]
+
+        tokenizer.chat_template = None
+        assert tokenizer.chat_template is None
+
     def test_encode_formats(self, bert_files):
         with pytest.deprecated_call():
             tokenizer = BertWordPieceTokenizer(bert_files["vocab"])
@@ -311,6 +441,76 @@ class TestTokenizer:
         trunc = tokenizer.truncation
         tokenizer.enable_truncation(**trunc)
 
+        # Can truncate from the left
+        tokenizer.enable_truncation(2, direction="left")
+        output = tokenizer.encode("my name is john")
+        assert output.tokens == ["is", "john"]
+        assert tokenizer.truncation["direction"] == "left"
+
+        # Can keep the head and the tail of the sequence
+        tokenizer.enable_truncation(3, strategy="head_tail", head=1, stride=1)
+        output = tokenizer.encode("my name is john")
+        assert output.tokens == ["my", "is", "john"]
+        assert [o.tokens for o in output.overflowing] == [["my", "name"]]
+        assert tokenizer.truncation["head"] == 1
+        tokenizer.enable_truncation(**tokenizer.truncation)
+
+        with pytest.raises(ValueError, match="requires `head`"):
+            tokenizer.enable_truncation(3, strategy="head_tail")
+
+    def test_encode_batch_flattened(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
+        tokenizer.enable_truncation(2)
+        tokenizer.enable_padding()
+
+        encodings, mapping = tokenizer.encode_batch_flattened(["my name is john", "pair"])
+        assert mapping == [0, 0, 1]
+        assert [e.tokens for e in encodings] == [
+            ["my", "name"],
+            ["is", "john"],
+            ["pair", "[PAD]"],
+        ]
+        assert [e.window_start() for e in encodings] == [0, 2, 0]
+        assert all(not e.overflowing for e in encodings)
+
+        # With pairs, each sequence has its own window
+        tokenizer.post_processor = BertProcessing(("[SEP]", 0), ("[CLS]", 1))
+        tokenizer.add_special_tokens(["[SEP]", "[CLS]"])
+        tokenizer.enable_truncation(6, strategy="only_second", stride=1)
+        tokenizer.enable_padding(direction="left", length=7)
+        encodings, mapping = tokenizer.encode_batch_flattened([("my", "name is john")])
+        assert mapping == [0, 0]
+        assert encodings[1].tokens == ["[PAD]", "[CLS]", "my", "[SEP]", "is", "john", "[SEP]"]
+        assert [e.window_start(1) for e in encodings] == [0, 1]
+        assert encodings[1].token_to_sequence_index(1) is None
+        assert encodings[1].token_to_sequence_index(2) == (0, 0)
+        assert encodings[1].token_to_sequence_index(5) == (1, 2)
+
+    def test_encode_batch_arrays(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
+
+        batch = tokenizer.encode_batch_arrays(["my name is john", "pair"])
+        assert isinstance(batch, BatchEncoding)
+        assert len(batch) == 2
+        assert batch.shape == (2, 4)
+        assert batch.ids.dtype == np.uint32
+        assert batch.ids.tolist() == [[0, 1, 2, 3], [4, 0, 0, 0]]
+        assert batch.attention_mask.tolist() == [[1, 1, 1, 1], [1, 0, 0, 0]]
+        assert batch.special_tokens_mask.tolist() == [[0, 0, 0, 0], [0, 1, 1, 1]]
+        assert batch.offsets.dtype == np.int64
+        assert batch.offsets.shape == (2, 4, 2)
+        assert batch.offsets[0].tolist() == [[0, 2], [3, 7], [8, 10], [11, 15]]
+        # The arrays are built once, and shared by each access
+        assert batch.ids is batch.ids
+
+        tokenizer.enable_padding(direction="left", pad_to_multiple_of=3)
+        batch = tokenizer.encode_batch_arrays(["my name is john", "pair"])
+        assert batch.shape == (2, 6)
+        assert batch.ids.tolist() == [[0, 0, 0, 1, 2, 3], [0, 0, 0, 0, 0, 4]]
+        assert batch.attention_mask.tolist() == [[0, 0, 1, 1, 1, 1], [0, 0, 0, 0, 0, 1]]
+
     def test_padding(self):
         tokenizer = Tokenizer(BPE())
         tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
@@ -335,6 +535,24 @@ class TestTokenizer:
         padding = tokenizer.padding
         tokenizer.enable_padding(**padding)
 
+        # Can pad to the smallest bucket that fits
+        tokenizer.enable_padding(buckets=[3, 6])
+        output = tokenizer.encode_batch(["my name", "my name is john"])
+        assert all([len(encoding) == 6 for encoding in output])
+        assert tokenizer.padding["buckets"] == [3, 6]
+        tokenizer.enable_padding(**tokenizer.padding)
+        assert tokenizer.padding["buckets"] == [3, 6]
+
+        # Can pad to the longest without going beyond a cap
+        tokenizer.enable_padding(length_cap=3)
+        output = tokenizer.encode_batch(["my name", "my name is john"])
+        assert [encoding.tokens for encoding in output] == [
+            ["my", "name", "[PAD]"],
+            ["my", "name", "is"],
+        ]
+        assert output[1].overflowing[0].tokens == ["john", "[PAD]", "[PAD]"]
+        assert tokenizer.padding["length_cap"] == 3
+
     def test_decode(self):
         tokenizer = Tokenizer(BPE())
         tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
@@ -42,3 +42,48 @@ class TestUnigram:
 
         trainer = trainers.BpeTrainer(special_tokens=["<unk>"], show_progress=False)
         bpe_tokenizer.train(trainer, [train_files["small"]])
+
+
+class TestWordLevel:
+    def test_train(self, train_files):
+        tokenizer = Tokenizer(models.WordLevel())
+        tokenizer.pre_tokenizer = pre_tokenizers.Whitespace()
+
+        trainer = trainers.WordLevelTrainer(
+            vocab_size=100, special_tokens=["<unk>"], show_progress=False
+        )
+        tokenizer.train(trainer, [train_files["small"]])
+
+        assert tokenizer.get_vocab_size() == 100
+        assert tokenizer.token_to_id("<unk>") == 0
+
+    def test_train_unk_token(self):
+        tokenizer = Tokenizer(models.WordLevel())
+        tokenizer.pre_tokenizer = pre_tokenizers.Whitespace()
+        trainer = trainers.WordLevelTrainer(unk_token="[UNK]", show_progress=False)
+        tokenizer.train_from_iterator(trainer, ["Hello there"])
+
+        assert tokenizer.token_to_id("[UNK]") == 0
+        assert tokenizer.encode("Hello Kenobi").ids == [tokenizer.token_to_id("Hello"), 0]
+
+    def test_train_from_iterator(self):
+        tokenizer = Tokenizer(models.WordLevel())
+        tokenizer.pre_tokenizer = pre_tokenizers.Whitespace()
+        trainer = trainers.WordLevelTrainer(special_tokens=["<unk>"], show_progress=False)
+
+        def sequences():
+            for _ in range(10):
+                yield "Hello there"
+            yield "General Kenobi"
+
+        tokenizer.train_from_iterator(trainer, sequences(), length=11)
+        assert tokenizer.get_vocab_size() == 5
+        assert tokenizer.token_to_id("<unk>") == 0
+        assert tokenizer.token_to_id("Hello") == 1
+
+        def failing():
+            yield "Hello there"
+            raise ValueError("Cannot read this one")
+
+        with pytest.raises(ValueError, match="Cannot read this one"):
+            tokenizer.train_from_iterator(trainer, failing())
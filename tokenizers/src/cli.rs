@@ -4,9 +4,13 @@
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use std::io::{self, BufRead, Write};
-use tokenizers::models::bpe::BPE;
+use tokenizers::models::bpe::{BpeTrainer, BPE};
+use tokenizers::models::unigram::UnigramTrainer;
+use tokenizers::models::wordlevel::WordLevelTrainer;
+use tokenizers::models::wordpiece::WordPieceTrainer;
+use tokenizers::models::TrainerWrapper;
 use tokenizers::pre_tokenizers::byte_level::ByteLevel;
-use tokenizers::tokenizer::{AddedToken, Result};
+use tokenizers::tokenizer::{AddedToken, Result, WordCounts};
 use tokenizers::Tokenizer;
 
 fn shell(matches: &ArgMatches) -> Result<()> {
@@ -54,6 +58,107 @@ fn shell(matches: &ArgMatches) -> Result<()> {
     }
 }
 
+fn count_words(matches: &ArgMatches) -> Result<()> {
+    let tokenizer = matches
+        .value_of("tokenizer")
+        .expect("Must give a tokenizer.json file");
+    let output = matches
+        .value_of("output")
+        .expect("Must give an output file");
+    let files = matches
+        .values_of("files")
+        .expect("Must give some files")
+        .map(String::from)
+        .collect();
+
+    let tokenizer = Tokenizer::from_file(tokenizer)?;
+    // All the trainers count the words in the same way
+    let trainer: TrainerWrapper = BpeTrainer::default().into();
+    let counts = tokenizer.count_words(&trainer, files)?;
+    counts.save(output, false)?;
+
+    println!("Counted {} distinct words", counts.counts.len());
+    Ok(())
+}
+
+fn train_from_counts(matches: &ArgMatches) -> Result<()> {
+    let tokenizer = matches
+        .value_of("tokenizer")
+        .expect("Must give a tokenizer.json file");
+    let counts = matches
+        .value_of("counts")
+        .expect("Must give a word counts file");
+    let output = matches
+        .value_of("output")
+        .expect("Must give an output file");
+    let vocab_size = matches
+        .value_of("vocab_size")
+        .map(|v| v.parse::<usize>())
+        .transpose()?;
+    let min_frequency = matches
+        .value_of("min_frequency")
+        .map(|v| v.parse::<u32>())
+        .transpose()?;
+    let special_tokens = matches
+        .values_of("special_tokens")
+        .map(|tokens| {
+            tokens
+                .map(|t| AddedToken::from(String::from(t), true))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let trainer: TrainerWrapper = match matches.value_of("model") {
+        Some("bpe") => {
+            let mut builder = BpeTrainer::builder().special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder = builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder = builder.min_frequency(min_frequency);
+            }
+            builder.build().into()
+        }
+        Some("wordpiece") => {
+            let mut builder = WordPieceTrainer::builder().special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder = builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder = builder.min_frequency(min_frequency);
+            }
+            builder.build().into()
+        }
+        Some("wordlevel") => {
+            let mut builder = WordLevelTrainer::builder();
+            builder.special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder.min_frequency(min_frequency);
+            }
+            builder.build()?.into()
+        }
+        Some("unigram") => {
+            let mut builder = UnigramTrainer::builder();
+            builder.special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder.vocab_size(vocab_size as u32);
+            }
+            builder.build()?.into()
+        }
+        model => panic!("Unknown model {:?}", model),
+    };
+
+    let mut tokenizer = Tokenizer::from_file(tokenizer)?;
+    let counts = WordCounts::from_file(counts)?;
+    tokenizer.train_from_counts_and_replace(&trainer, counts)?;
+    tokenizer.save(output, false)?;
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let matches = App::new("tokenizers")
         .version("0.0.1")
@@ -78,10 +183,89 @@ fn main() -> Result<()> {
                         .required(true),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("count-words")
+                .about("Count the words of some files, to train tokenizers from these counts")
+                .arg(
+                    Arg::with_name("tokenizer")
+                        .long("tokenizer")
+                        .value_name("TOKENIZER_FILE")
+                        .help("Path to the tokenizer.json file whose normalizer and pre-tokenizer are used")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("COUNTS_FILE")
+                        .help("Path where the word counts will be saved")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("files")
+                        .value_name("FILES")
+                        .help("The files to read, possibly gzip or zstd compressed")
+                        .multiple(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("train-from-counts")
+                .about("Train a tokenizer from some word counts produced by count-words")
+                .arg(
+                    Arg::with_name("tokenizer")
+                        .long("tokenizer")
+                        .value_name("TOKENIZER_FILE")
+                        .help("Path to the tokenizer.json file that produced the counts")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("counts")
+                        .long("counts")
+                        .value_name("COUNTS_FILE")
+                        .help("Path to the word counts file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("model")
+                        .long("model")
+                        .value_name("MODEL")
+                        .help("The type of model to train")
+                        .possible_values(&["bpe", "wordpiece", "wordlevel", "unigram"])
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("vocab_size")
+                        .long("vocab-size")
+                        .value_name("SIZE")
+                        .help("The size of the final vocabulary"),
+                )
+                .arg(
+                    Arg::with_name("min_frequency")
+                        .long("min-frequency")
+                        .value_name("FREQUENCY")
+                        .help("The minimum frequency of a token to be part of the vocabulary"),
+                )
+                .arg(
+                    Arg::with_name("special_tokens")
+                        .long("special-tokens")
+                        .value_name("TOKEN")
+                        .help("The special tokens to add to the vocabulary")
+                        .multiple(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("TOKENIZER_FILE")
+                        .help("Path where the trained tokenizer will be saved")
+                        .required(true),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
         ("shell", matches) => shell(matches.unwrap()),
+        ("count-words", matches) => count_words(matches.unwrap()),
+        ("train-from-counts", matches) => train_from_counts(matches.unwrap()),
         (subcommand, _) => panic!("Unknown subcommand {}", subcommand),
     }
 }
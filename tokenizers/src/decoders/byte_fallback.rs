@@ -0,0 +1,136 @@
+use crate::tokenizer::{Decoder, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+/// ByteFallback is a simple trick which converts tokens looking like `<0x61>`
+/// to pure bytes, and attempts to make them into a string. If the tokens
+/// cannot be decoded you will get � instead for each inconvertable byte token
+pub struct ByteFallback;
+impl_serde_unit_struct!(ByteFallbackVisitor, ByteFallback);
+
+impl ByteFallback {
+    pub fn new() -> Self {
+        ByteFallback
+    }
+}
+
+impl Default for ByteFallback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a token of the form `<0xXX>` into the byte it represents
+fn token_to_byte(token: &str) -> Option<u8> {
+    if token.len() == 6 && token.starts_with("<0x") && token.ends_with('>') {
+        u8::from_str_radix(&token[3..5], 16).ok()
+    } else {
+        None
+    }
+}
+
+/// Converts the given bytes in a single token if they are valid UTF-8, or in one `�`
+/// per byte otherwise
+fn bytes_to_tokens(bytes: &[u8], output: &mut Vec<String>) {
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(token) => output.push(token),
+        Err(_) => output.extend(bytes.iter().map(|_| "\u{FFFD}".to_string())),
+    }
+}
+
+impl Decoder for ByteFallback {
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        Ok(self.decode_chain(tokens)?.join(""))
+    }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        let mut output = Vec::with_capacity(tokens.len());
+        let mut previous_bytes = vec![];
+
+        for token in tokens {
+            if let Some(byte) = token_to_byte(&token) {
+                previous_bytes.push(byte);
+            } else {
+                if !previous_bytes.is_empty() {
+                    bytes_to_tokens(&previous_bytes, &mut output);
+                    previous_bytes.clear();
+                }
+                output.push(token);
+            }
+        }
+        if !previous_bytes.is_empty() {
+            bytes_to_tokens(&previous_bytes, &mut output);
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = ByteFallback::new();
+        let res = decoder
+            .decode(vec!["Hey".into(), "friend!".into()])
+            .unwrap();
+        assert_eq!(res, "Heyfriend!");
+
+        let res = decoder.decode(vec!["<0x61>".into()]).unwrap();
+        assert_eq!(res, "a");
+
+        let res = decoder.decode(vec!["<0xE5>".into()]).unwrap();
+        assert_eq!(res, "�");
+
+        let res = decoder
+            .decode(vec!["<0xE5>".into(), "<0x8f>".into()])
+            .unwrap();
+        assert_eq!(res, "��");
+
+        // 叫
+        let res = decoder
+            .decode(vec!["<0xE5>".into(), "<0x8f>".into(), "<0xab>".into()])
+            .unwrap();
+        assert_eq!(res, "叫");
+
+        let res = decoder
+            .decode(vec![
+                "<0xE5>".into(),
+                "<0x8f>".into(),
+                "<0xab>".into(),
+                "a".into(),
+                "<0x62>".into(),
+            ])
+            .unwrap();
+        assert_eq!(res, "叫ab");
+    }
+
+    #[test]
+    fn decode_chain() {
+        let decoder = ByteFallback::new();
+        let res = decoder
+            .decode_chain(vec![
+                "a".into(),
+                "<0xE5>".into(),
+                "<0x8f>".into(),
+                "<0xab>".into(),
+                "b".into(),
+                "<0xE5>".into(),
+            ])
+            .unwrap();
+        assert_eq!(res, vec!["a", "叫", "b", "�"]);
+    }
+
+    #[test]
+    fn serialization() {
+        let decoder = ByteFallback::new();
+        let serialized = serde_json::to_string(&decoder).unwrap();
+        assert_eq!(serialized, r#"{"type":"ByteFallback"}"#);
+        let wrapped: crate::decoders::DecoderWrapper = serde_json::from_str(&serialized).unwrap();
+        match wrapped {
+            crate::decoders::DecoderWrapper::ByteFallback(_) => (),
+            _ => panic!("ByteFallback wrapped with incorrect variant"),
+        }
+    }
+}
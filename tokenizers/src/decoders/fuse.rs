@@ -0,0 +1,44 @@
+use crate::tokenizer::{Decoder, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+/// Fuse simply fuses all the tokens into one big string.
+/// It's usually the last decoding step anyway, but this decoder
+/// exists in case some decoders need to happen after that step
+pub struct Fuse;
+impl_serde_unit_struct!(FuseVisitor, Fuse);
+
+impl Fuse {
+    pub fn new() -> Self {
+        Fuse
+    }
+}
+
+impl Default for Fuse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for Fuse {
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        Ok(tokens.join(""))
+    }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(vec![self.decode(tokens)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = Fuse::new();
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), " friend!".into()])
+            .unwrap();
+        assert_eq!(res, vec!["Hey friend!"]);
+    }
+}
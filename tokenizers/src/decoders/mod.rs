@@ -1,14 +1,24 @@
 pub mod bpe;
+pub mod byte_fallback;
+pub mod fuse;
+pub mod sequence;
+pub mod strip;
 pub mod wordpiece;
 
 // Re-export these as decoders
+pub use super::normalizers::replace;
 pub use super::pre_tokenizers::byte_level;
 pub use super::pre_tokenizers::metaspace;
 
 use serde::{Deserialize, Serialize};
 
 use crate::decoders::bpe::BPEDecoder;
+use crate::decoders::byte_fallback::ByteFallback;
+use crate::decoders::fuse::Fuse;
+use crate::decoders::sequence::Sequence;
+use crate::decoders::strip::Strip;
 use crate::decoders::wordpiece::WordPiece;
+use crate::normalizers::replace::Replace;
 use crate::pre_tokenizers::byte_level::ByteLevel;
 use crate::pre_tokenizers::metaspace::Metaspace;
 use crate::{Decoder, Result};
@@ -20,6 +30,11 @@ pub enum DecoderWrapper {
     ByteLevel(ByteLevel),
     WordPiece(WordPiece),
     Metaspace(Metaspace),
+    ByteFallback(ByteFallback),
+    Sequence(Sequence),
+    Replace(Replace),
+    Strip(Strip),
+    Fuse(Fuse),
 }
 
 impl Decoder for DecoderWrapper {
@@ -29,6 +44,25 @@ impl Decoder for DecoderWrapper {
             DecoderWrapper::ByteLevel(bl) => bl.decode(tokens),
             DecoderWrapper::Metaspace(ms) => ms.decode(tokens),
             DecoderWrapper::WordPiece(wp) => wp.decode(tokens),
+            DecoderWrapper::ByteFallback(bf) => bf.decode(tokens),
+            DecoderWrapper::Sequence(seq) => seq.decode(tokens),
+            DecoderWrapper::Replace(rep) => rep.decode(tokens),
+            DecoderWrapper::Strip(st) => st.decode(tokens),
+            DecoderWrapper::Fuse(fuse) => fuse.decode(tokens),
+        }
+    }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        match self {
+            DecoderWrapper::BPE(bpe) => bpe.decode_chain(tokens),
+            DecoderWrapper::ByteLevel(bl) => bl.decode_chain(tokens),
+            DecoderWrapper::Metaspace(ms) => ms.decode_chain(tokens),
+            DecoderWrapper::WordPiece(wp) => wp.decode_chain(tokens),
+            DecoderWrapper::ByteFallback(bf) => bf.decode_chain(tokens),
+            DecoderWrapper::Sequence(seq) => seq.decode_chain(tokens),
+            DecoderWrapper::Replace(rep) => rep.decode_chain(tokens),
+            DecoderWrapper::Strip(st) => st.decode_chain(tokens),
+            DecoderWrapper::Fuse(fuse) => fuse.decode_chain(tokens),
         }
     }
 }
@@ -37,3 +71,8 @@ impl_enum_from!(BPEDecoder, DecoderWrapper, BPE);
 impl_enum_from!(ByteLevel, DecoderWrapper, ByteLevel);
 impl_enum_from!(Metaspace, DecoderWrapper, Metaspace);
 impl_enum_from!(WordPiece, DecoderWrapper, WordPiece);
+impl_enum_from!(ByteFallback, DecoderWrapper, ByteFallback);
+impl_enum_from!(Sequence, DecoderWrapper, Sequence);
+impl_enum_from!(Replace, DecoderWrapper, Replace);
+impl_enum_from!(Strip, DecoderWrapper, Strip);
+impl_enum_from!(Fuse, DecoderWrapper, Fuse);
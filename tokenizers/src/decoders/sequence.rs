@@ -0,0 +1,101 @@
+use crate::decoders::DecoderWrapper;
+use crate::tokenizer::{Decoder, Result};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+/// Allows concatenating multiple other Decoder as a Sequence.
+/// Each decoder runs its per-token stage (`decode_chain`) on the output of the previous one,
+/// and the resulting tokens are then joined together.
+pub struct Sequence {
+    decoders: Vec<DecoderWrapper>,
+}
+
+impl Sequence {
+    pub fn new(decoders: Vec<DecoderWrapper>) -> Self {
+        Self { decoders }
+    }
+
+    pub fn get_decoders(&self) -> &[DecoderWrapper] {
+        &self.decoders
+    }
+}
+
+impl Decoder for Sequence {
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        Ok(self.decode_chain(tokens)?.join(""))
+    }
+
+    fn decode_chain(&self, mut tokens: Vec<String>) -> Result<Vec<String>> {
+        for decoder in &self.decoders {
+            tokens = decoder.decode_chain(tokens)?;
+        }
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoders::byte_fallback::ByteFallback;
+    use crate::decoders::fuse::Fuse;
+    use crate::decoders::metaspace::Metaspace;
+    use crate::decoders::replace::Replace;
+    use crate::decoders::strip::Strip;
+
+    #[test]
+    fn sentencepiece_like() {
+        let decoder = Sequence::new(vec![
+            Replace::new("▁", " ").unwrap().into(),
+            ByteFallback::new().into(),
+            Fuse::new().into(),
+            Strip::new(' ', 1, 0).into(),
+        ]);
+        let tokens: Vec<String> = vec!["▁Hey", "▁fr", "<0xC3>", "<0xA9>", "nd", "!"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            decoder.decode_chain(tokens.clone()).unwrap(),
+            vec!["Hey fr\u{e9}nd!"]
+        );
+        assert_eq!(decoder.decode(tokens).unwrap(), "Hey fr\u{e9}nd!");
+    }
+
+    #[test]
+    fn fused_decoders() {
+        // Decoders without a per-token stage fuse everything in a single token
+        let decoder = Sequence::new(vec![
+            ByteFallback::new().into(),
+            Metaspace::default().into(),
+        ]);
+        let tokens: Vec<String> = vec!["▁Hey", "<0x21>", "▁friend"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(decoder.decode_chain(tokens).unwrap(), vec!["Hey! friend"]);
+    }
+
+    #[test]
+    fn serialization() {
+        let decoder = Sequence::new(vec![
+            Replace::new("▁", " ").unwrap().into(),
+            ByteFallback::new().into(),
+            Fuse::new().into(),
+            Strip::new(' ', 1, 0).into(),
+        ]);
+        let serialized = serde_json::to_string(&decoder).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"Sequence","decoders":[{"type":"Replace","pattern":{"String":"▁"},"content":" "},{"type":"ByteFallback"},{"type":"Fuse"},{"type":"Strip","content":" ","start":1,"stop":0}]}"#
+        );
+        let wrapped: DecoderWrapper = serde_json::from_str(&serialized).unwrap();
+        match wrapped {
+            DecoderWrapper::Sequence(seq) => {
+                assert_eq!(serde_json::to_string(&seq).unwrap(), serialized)
+            }
+            _ => panic!("Sequence wrapped with incorrect variant"),
+        }
+    }
+}
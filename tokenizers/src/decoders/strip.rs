@@ -0,0 +1,93 @@
+use crate::tokenizer::{Decoder, Result};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Debug, Serialize, PartialEq)]
+/// Strip is a simple decoder which removes up to `start` occurrences of `content`
+/// at the beginning of each token, and up to `stop` occurrences at the end.
+/// This is typically used to remove the leading space added by SentencePiece-like
+/// models once the tokens have been fused.
+#[serde(tag = "type")]
+pub struct Strip {
+    content: char,
+    start: usize,
+    stop: usize,
+}
+
+impl Strip {
+    pub fn new(content: char, start: usize, stop: usize) -> Self {
+        Self {
+            content,
+            start,
+            stop,
+        }
+    }
+
+    fn strip(&self, token: &str) -> String {
+        let mut chars = token.chars().collect::<Vec<_>>();
+
+        let start_cut = chars
+            .iter()
+            .take(self.start)
+            .take_while(|c| **c == self.content)
+            .count();
+        chars.drain(..start_cut);
+
+        let stop_cut = chars
+            .iter()
+            .rev()
+            .take(self.stop)
+            .take_while(|c| **c == self.content)
+            .count();
+        chars.truncate(chars.len() - stop_cut);
+
+        chars.into_iter().collect()
+    }
+}
+
+impl Decoder for Strip {
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        Ok(self.decode_chain(tokens)?.join(""))
+    }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(tokens.iter().map(|token| self.strip(token)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = Strip::new('H', 1, 0);
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), " HHfriend!".into(), "HHH".into()])
+            .unwrap();
+        assert_eq!(res, vec!["ey", " HHfriend!", "HH"]);
+
+        let decoder = Strip::new('y', 0, 1);
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), " friend!".into(), "yyy".into()])
+            .unwrap();
+        assert_eq!(res, vec!["He", " friend!", "yy"]);
+
+        let decoder = Strip::new(' ', 2, 2);
+        let res = decoder
+            .decode_chain(vec!["   ".into(), " a ".into()])
+            .unwrap();
+        assert_eq!(res, vec!["", "a"]);
+    }
+
+    #[test]
+    fn serialization() {
+        let decoder = Strip::new(' ', 1, 0);
+        let serialized = serde_json::to_string(&decoder).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"Strip","content":" ","start":1,"stop":0}"#
+        );
+        assert_eq!(serde_json::from_str::<Strip>(&serialized).unwrap(), decoder);
+    }
+}
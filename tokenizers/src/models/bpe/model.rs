@@ -1,7 +1,12 @@
-use super::{super::OrderedVocabIter, Error, Pair, Word};
+use super::{
+    super::{byte_to_token, OrderedVocabIter},
+    Error, Pair, Word,
+};
 use crate::tokenizer::{Model, Result, Token};
 use crate::utils::cache::{Cache, DEFAULT_CACHE_CAPACITY};
 use crate::utils::iter::ResultShunt;
+use crate::utils::{seeded_rng, SeedSequence};
+use rand::{thread_rng, RngCore};
 use serde_json::Value;
 use std::borrow::Cow;
 use std::{
@@ -23,10 +28,12 @@ struct Config {
     merges: Merges,
     cache_capacity: usize,
     dropout: Option<f32>,
+    seed: Option<u64>,
     unk_token: Option<String>,
     continuing_subword_prefix: Option<String>,
     end_of_word_suffix: Option<String>,
     fuse_unk: bool,
+    byte_fallback: bool,
 }
 
 /// A `BpeBuilder` can be used to create a `BPE` model with a custom configuration.
@@ -43,10 +50,12 @@ impl Default for BpeBuilder {
                 merges: vec![],
                 cache_capacity: DEFAULT_CACHE_CAPACITY,
                 dropout: None,
+                seed: None,
                 unk_token: None,
                 continuing_subword_prefix: None,
                 end_of_word_suffix: None,
                 fuse_unk: false,
+                byte_fallback: false,
             },
         }
     }
@@ -83,6 +92,13 @@ impl BpeBuilder {
         self
     }
 
+    /// Set the seed used by the dropout. Each encode call draws its own random number
+    /// generator from this seed, so the same seed always replays the same segmentations.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
     /// Set the `UNK` token for the vocab.
     pub fn unk_token(mut self, unk_token: String) -> Self {
         self.config.unk_token = Some(unk_token);
@@ -107,6 +123,12 @@ impl BpeBuilder {
         self
     }
 
+    /// Set the `byte_fallback` option.
+    pub fn byte_fallback(mut self, byte_fallback: bool) -> Self {
+        self.config.byte_fallback = byte_fallback;
+        self
+    }
+
     /// Returns a `BPE` model that uses the `BpeBuilder`'s configuration.
     pub fn build(mut self) -> Result<BPE> {
         // Validate dropout.
@@ -168,10 +190,12 @@ impl BpeBuilder {
             merges: merge_map,
             cache,
             dropout: self.config.dropout,
+            seed: self.config.seed.map(SeedSequence::new),
             unk_token: self.config.unk_token,
             continuing_subword_prefix: self.config.continuing_subword_prefix,
             end_of_word_suffix: self.config.end_of_word_suffix,
             fuse_unk: self.config.fuse_unk,
+            byte_fallback: self.config.byte_fallback,
         })
     }
 }
@@ -190,6 +214,10 @@ pub struct BPE {
     /// Dropout probability for merges. 0 = no dropout is the default. At 1.0, tokenization will
     /// perform no merges, so the result will just be characters.
     pub(super) dropout: Option<f32>,
+    /// An optional seed for the dropout, from which each encode call draws its own random
+    /// number generator. The successive calls thus get different segmentations, but a
+    /// fresh model always replays the same ones.
+    pub(super) seed: Option<SeedSequence>,
     /// The unknown token to be used when we encounter an unknown char
     pub(super) unk_token: Option<String>,
     /// An optional prefix to use on any subword that exist only behind another one
@@ -198,16 +226,21 @@ pub struct BPE {
     pub(super) end_of_word_suffix: Option<String>,
     /// Do multiple unk tokens get fused
     pub(super) fuse_unk: bool,
+    /// Whether unknown characters should be decomposed into their UTF-8 bytes, using the
+    /// `<0xXX>` byte tokens of the vocabulary, instead of being mapped to the `unk_token`
+    pub(super) byte_fallback: bool,
 }
 
 impl std::fmt::Debug for BPE {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_struct("BPE")
             .field("dropout", &self.dropout)
+            .field("seed", &self.seed)
             .field("unk_token", &self.unk_token)
             .field("continuing_subword_prefix", &self.continuing_subword_prefix)
             .field("end_of_word_suffix", &self.end_of_word_suffix)
             .field("fuse_unk", &self.fuse_unk)
+            .field("byte_fallback", &self.byte_fallback)
             .field("vocab", &self.vocab.len())
             .field("merges", &self.merges.len())
             .finish()
@@ -231,10 +264,12 @@ impl Clone for BPE {
             merges: self.merges.clone(),
             cache: fresh_cache,
             dropout: self.dropout,
+            seed: self.seed.clone(),
             unk_token: self.unk_token.clone(),
             continuing_subword_prefix: self.continuing_subword_prefix.clone(),
             end_of_word_suffix: self.end_of_word_suffix.clone(),
             fuse_unk: self.fuse_unk,
+            byte_fallback: self.byte_fallback,
         }
     }
 }
@@ -330,7 +365,14 @@ impl BPE {
         &self.continuing_subword_prefix
     }
 
-    fn merge_word(&self, w: &str) -> Result<Word> {
+    fn merge_word(&self, w: &str, rng: &mut dyn RngCore) -> Result<Word> {
+        let mut word = self.split_word(w)?;
+        word.merge_all(&self.merges, self.dropout, rng);
+        Ok(word)
+    }
+
+    /// Split the given word in the symbols of its characters, before any merge
+    fn split_word(&self, w: &str) -> Result<Word> {
         let mut indices = w.char_indices().map(|(idx, _)| idx).peekable();
         let mut word = Word::with_capacity(w.len());
         let mut unk: Option<(u32, usize)> = None;
@@ -359,6 +401,16 @@ impl BPE {
                     unk = None;
                 }
                 word.add(*id, byte_len);
+            } else if let Some(byte_ids) = self.byte_fallback_ids(&w[i..i + byte_len]) {
+                if let Some((unk_id, unk_len)) = unk {
+                    word.add(unk_id, unk_len);
+                    unk = None;
+                }
+                // Each byte token covers a single byte here, `word_to_tokens` takes care
+                // of making them all point to the full character
+                for id in byte_ids {
+                    word.add(id, 1);
+                }
             } else if let Some(unk_token) = &self.unk_token {
                 unk = match (unk, self.fuse_unk) {
                     (Some((unk_id, unk_len)), true) => {
@@ -389,23 +441,47 @@ impl BPE {
             word.add(unk_id, unk_len);
         }
 
-        word.merge_all(&self.merges, self.dropout);
-
         Ok(word)
     }
 
-    fn word_to_tokens<'a, 'b: 'a>(&'a self, word: &'b Word) -> impl Iterator<Item = Token> + 'a {
+    /// Returns the ids of the `<0xXX>` tokens representing each byte of the given
+    /// character, if `byte_fallback` is enabled and they are all part of the vocabulary.
+    fn byte_fallback_ids(&self, c: &str) -> Option<Vec<u32>> {
+        if !self.byte_fallback {
+            return None;
+        }
+        c.bytes()
+            .map(|b| self.vocab.get(&byte_to_token(b)).copied())
+            .collect()
+    }
+
+    fn word_to_tokens<'a, 'b: 'a>(
+        &'a self,
+        word: &'b Word,
+        sequence: &'b str,
+    ) -> impl Iterator<Item = Token> + 'a {
         word.get_chars_iter()
             .zip(word.get_offsets_iter())
-            .map(move |(id, offsets)| Token::new(id, self.vocab_r[&id].clone(), offsets))
+            .map(move |(id, (start, end))| {
+                // Only the byte-fallback tokens can end up in the middle of a character,
+                // in which case we extend their offsets to the full character.
+                let start = (0..=start)
+                    .rev()
+                    .find(|i| sequence.is_char_boundary(*i))
+                    .unwrap_or(0);
+                let end = (end..=sequence.len())
+                    .find(|i| sequence.is_char_boundary(*i))
+                    .unwrap_or(end);
+                Token::new(id, self.vocab_r[&id].clone(), (start, end))
+            })
     }
 
     fn tokenize_with_cache(&self, sequence: &str) -> Result<Vec<Token>> {
         if let Some(ref hit) = self.cache.as_ref().and_then(|c| c.get(sequence)) {
-            Ok(self.word_to_tokens(hit).collect())
+            Ok(self.word_to_tokens(hit, sequence).collect())
         } else {
-            let word = self.merge_word(sequence)?;
-            let ret = self.word_to_tokens(&word).collect();
+            let word = self.merge_word(sequence, &mut thread_rng())?;
+            let ret = self.word_to_tokens(&word, sequence).collect();
             if let Some(ref cache) = self.cache {
                 cache.set(sequence.to_owned(), word);
             }
@@ -431,9 +507,40 @@ impl Model for BPE {
         if self.dropout.is_none() {
             self.tokenize_with_cache(sequence)
         } else {
-            let word = self.merge_word(sequence)?;
-            Ok(self.word_to_tokens(&word).collect())
+            match self.next_seed() {
+                Some(seed) => self.tokenize_with_rng(sequence, &mut seeded_rng(seed, 0)),
+                None => self.tokenize_with_rng(sequence, &mut thread_rng()),
+            }
+        }
+    }
+
+    fn tokenize_with_rng(&self, sequence: &str, rng: &mut dyn RngCore) -> Result<Vec<Token>> {
+        if sequence.is_empty() || self.dropout.is_none() {
+            return self.tokenize(sequence);
+        }
+
+        let word = self.merge_word(sequence, rng)?;
+        Ok(self.word_to_tokens(&word, sequence).collect())
+    }
+
+    fn tokenize_nbest(&self, sequence: &str, n: usize) -> Result<Vec<(Vec<Token>, f64)>> {
+        if sequence.is_empty() {
+            return Ok(vec![(vec![], 0.0)].into_iter().take(n).collect());
         }
+
+        Ok(self
+            .split_word(sequence)?
+            .merge_nbest(&self.merges, n)
+            .into_iter()
+            .map(|(word, score)| (self.word_to_tokens(&word, sequence).collect(), score))
+            .collect())
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        // Only the dropout needs a seed
+        self.dropout
+            .and(self.seed.as_ref())
+            .map(SeedSequence::next_seed)
     }
 
     fn token_to_id(&self, token: &str) -> Option<u32> {
@@ -573,6 +680,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_byte_fallback() {
+        let vocab: Vocab = [
+            ("<unk>".into(), 0),
+            ("<0x61>".into(), 1),
+            ("<0xC3>".into(), 2),
+            ("<0xA9>".into(), 3),
+            ("b".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, vec![])
+            .unk_token("<unk>".to_string())
+            .byte_fallback(true)
+            .build()
+            .unwrap();
+
+        // Single byte char
+        let tokens = bpe.tokenize("ab").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1u32, "<0x61>".into(), (0, 1)),
+                Token::new(4u32, "b".into(), (1, 2)),
+            ]
+        );
+
+        // Each byte token points to the full character
+        let tokens = bpe.tokenize("béb").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(4u32, "b".into(), (0, 1)),
+                Token::new(2u32, "<0xC3>".into(), (1, 3)),
+                Token::new(3u32, "<0xA9>".into(), (1, 3)),
+                Token::new(4u32, "b".into(), (3, 4)),
+            ]
+        );
+
+        // Missing byte tokens fall back to unk
+        let tokens = bpe.tokenize("c").unwrap();
+        assert_eq!(tokens, vec![Token::new(0u32, "<unk>".into(), (0, 1))]);
+    }
+
     #[test]
     // Test tokenization. With dropout set to 0 tokenization is deterministic,
     // so we know exactly what the result should be.
@@ -638,6 +791,131 @@ mod tests {
         bpe.dropout = Some(0.5);
         let tokens = bpe.tokenize("unrelated").unwrap();
         assert!(!tokens.is_empty() && tokens.len() <= 9);
+
+        // A given random number generator always gives the same segmentation
+        let seeded = bpe.tokenize_with_rng("unrelated", &mut seeded_rng(42, 0));
+        assert_eq!(
+            bpe.tokenize_with_rng("unrelated", &mut seeded_rng(42, 0))
+                .unwrap(),
+            seeded.unwrap()
+        );
+
+        // With a seed, the successive calls get different segmentations, which a fresh
+        // model with the same seed replays
+        bpe.seed = Some(SeedSequence::new(42));
+        let fresh = bpe.clone();
+        let sampled: Vec<_> = (0..20)
+            .map(|_| bpe.tokenize("unrelated").unwrap())
+            .collect();
+        assert!(sampled.iter().any(|tokens| tokens != &sampled[0]));
+        let replayed: Vec<_> = (0..20)
+            .map(|_| fresh.tokenize("unrelated").unwrap())
+            .collect();
+        assert_eq!(replayed, sampled);
+    }
+
+    #[test]
+    fn test_seeded_dropout_in_batch() {
+        use crate::pre_tokenizers::whitespace::WhitespaceSplit;
+        use crate::Tokenizer;
+
+        let vocab: Vocab = [("a".into(), 0), ("b".into(), 1), ("ab".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let merges: Merges = vec![("a".to_string(), "b".to_string())];
+        let bpe = BPE::builder()
+            .vocab_and_merges(vocab, merges)
+            .dropout(0.5)
+            .seed(42)
+            .build()
+            .unwrap();
+        let tokenizer = |bpe: BPE| {
+            let mut tokenizer = Tokenizer::new(bpe);
+            tokenizer.with_pre_tokenizer(WhitespaceSplit);
+            tokenizer
+        };
+        let seeded = tokenizer(bpe.clone());
+        let input = vec!["ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab"; 32];
+
+        // All the words of an input draw from the same random number generator, so they
+        // don't all get the same segmentation
+        let encoding = seeded.encode_with_seed(input[0], false, 1).unwrap();
+        assert!(encoding.get_tokens().iter().any(|t| t == "ab"));
+        assert!(encoding.get_tokens().iter().any(|t| t == "a"));
+        assert_eq!(
+            seeded.encode_with_seed(input[0], false, 1).unwrap(),
+            encoding
+        );
+
+        // Each input of a batch gets its own generator, built from its index, so the result
+        // doesn't depend on the order in which the threads encode the inputs
+        let batch = seeded
+            .encode_batch_with_seed(input.clone(), false, 1)
+            .unwrap();
+        assert_eq!(batch[0], encoding);
+        assert!(batch.iter().any(|e| e != &encoding));
+        assert_eq!(
+            seeded
+                .encode_batch_with_seed(input.clone(), false, 1)
+                .unwrap(),
+            batch
+        );
+
+        // With the seed of the model, the successive calls differ, but a fresh model with
+        // the same seed replays them
+        let first = seeded.encode_batch(input.clone(), false).unwrap();
+        let second = seeded.encode_batch(input.clone(), false).unwrap();
+        assert_ne!(first, second);
+        let replay = tokenizer(bpe);
+        assert_eq!(replay.encode_batch(input.clone(), false).unwrap(), first);
+        assert_eq!(replay.encode_batch(input, false).unwrap(), second);
+    }
+
+    #[test]
+    fn test_tokenize_nbest() {
+        let vocab: Vocab = [
+            ("a".into(), 0),
+            ("b".into(), 1),
+            ("c".into(), 2),
+            ("bc".into(), 3),
+            ("ab".into(), 4),
+            ("abc".into(), 5),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let merges: Merges = vec![
+            ("b".to_string(), "c".to_string()),
+            ("a".to_string(), "b".to_string()),
+            ("ab".to_string(), "c".to_string()),
+        ];
+        let bpe = BPE::new(vocab, merges);
+
+        // Merging `a b` first is one rank behind `b c`, then `ab c` can be merged
+        let nbest = bpe.tokenize_nbest("abcabc", 4).unwrap();
+        let tokens = nbest
+            .iter()
+            .map(|(tokens, _)| tokens.iter().map(|t| t.value.as_str()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                vec!["a", "bc", "a", "bc"],
+                vec!["a", "bc", "abc"],
+                vec!["abc", "a", "bc"],
+                vec!["abc", "abc"],
+            ]
+        );
+        assert_eq!(
+            nbest.iter().map(|(_, score)| *score).collect::<Vec<_>>(),
+            vec![0.0, -1.0, -1.0, -2.0]
+        );
+        assert_eq!(nbest[0].0, bpe.tokenize("abcabc").unwrap());
+        assert_eq!(nbest[3].0[1], Token::new(5, "abc".into(), (3, 6)));
+
+        assert_eq!(bpe.tokenize_nbest("abc", 0).unwrap(), vec![]);
+        assert_eq!(bpe.tokenize_nbest("", 2).unwrap(), vec![(vec![], 0.0)]);
     }
 
     #[test]
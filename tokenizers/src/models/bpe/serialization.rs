@@ -1,4 +1,5 @@
 use super::{super::OrderedVocabIter, convert_merges_to_hashmap, BpeBuilder, Pair, BPE};
+use crate::utils::SeedSequence;
 use serde::{
     de::{Error, MapAccess, Visitor},
     ser::SerializeStruct,
@@ -11,14 +12,16 @@ impl Serialize for BPE {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("BPE", 6)?;
+        let mut model = serializer.serialize_struct("BPE", 9)?;
 
         // Start by small fields
         model.serialize_field("dropout", &self.dropout)?;
+        model.serialize_field("seed", &self.seed.as_ref().map(SeedSequence::seed))?;
         model.serialize_field("unk_token", &self.unk_token)?;
         model.serialize_field("continuing_subword_prefix", &self.continuing_subword_prefix)?;
         model.serialize_field("end_of_word_suffix", &self.end_of_word_suffix)?;
         model.serialize_field("fuse_unk", &self.fuse_unk)?;
+        model.serialize_field("byte_fallback", &self.byte_fallback)?;
 
         // Then the large ones
         let mut merges: Vec<(&Pair, &u32)> = self
@@ -49,10 +52,12 @@ impl<'de> Deserialize<'de> for BPE {
             "BPE",
             &[
                 "dropout",
+                "seed",
                 "unk_token",
                 "continuing_subword_prefix",
                 "end_of_word_suffix",
                 "fuse_unk",
+                "byte_fallback",
                 "vocab",
                 "merges",
             ],
@@ -83,6 +88,11 @@ impl<'de> Visitor<'de> for BPEVisitor {
                         builder = builder.dropout(dropout);
                     }
                 }
+                "seed" => {
+                    if let Some(seed) = map.next_value()? {
+                        builder = builder.seed(seed);
+                    }
+                }
                 "unk_token" => {
                     if let Some(unk) = map.next_value()? {
                         builder = builder.unk_token(unk);
@@ -103,6 +113,11 @@ impl<'de> Visitor<'de> for BPEVisitor {
                         builder = builder.fuse_unk(suffix);
                     }
                 }
+                "byte_fallback" => {
+                    if let Some(byte_fallback) = map.next_value()? {
+                        builder = builder.byte_fallback(byte_fallback);
+                    }
+                }
                 "vocab" => vocab = Some(map.next_value()?),
                 "merges" => merges = Some(map.next_value()?),
                 _ => {}
@@ -1,5 +1,5 @@
 use super::Pair;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 
@@ -147,7 +147,12 @@ impl Word {
         changes
     }
 
-    pub(super) fn merge_all(&mut self, merges: &HashMap<Pair, (u32, u32)>, dropout: Option<f32>) {
+    pub(super) fn merge_all<R: Rng + ?Sized>(
+        &mut self,
+        merges: &HashMap<Pair, (u32, u32)>,
+        dropout: Option<f32>,
+        rng: &mut R,
+    ) {
         let mut queue = BinaryHeap::with_capacity(self.symbols.len());
         let mut skip = Vec::with_capacity(queue.len());
 
@@ -166,10 +171,7 @@ impl Word {
         );
 
         while let Some(top) = queue.pop() {
-            if dropout
-                .map(|d| thread_rng().gen::<f32>() < d)
-                .unwrap_or(false)
-            {
+            if dropout.map(|d| rng.gen::<f32>() < d).unwrap_or(false) {
                 skip.push(top);
             } else {
                 // Re-insert the skipped elements
@@ -240,6 +242,74 @@ impl Word {
         self.symbols.retain(|s| s.len != 0);
     }
 
+    /// Merge the word in its `n` best alternative ways, with a beam search over the order of
+    /// the merges. At each step, the usual BPE applies the best ranked merge available, and
+    /// applying another one of the available merges instead costs the difference between
+    /// their ranks. The score of a segmentation is the opposite of its total cost, so the
+    /// usual segmentation always comes first, with a score of `0.0`.
+    pub(super) fn merge_nbest(
+        &self,
+        merges: &HashMap<Pair, (u32, u32)>,
+        n: usize,
+    ) -> Vec<(Word, f64)> {
+        type Symbols = Vec<(u32, usize)>;
+
+        let initial: Symbols = self.symbols.iter().map(|s| (s.c, s.len)).collect();
+        let mut beam: Vec<(Symbols, u64)> = vec![(initial, 0)];
+        let mut done: Vec<(Symbols, u64)> = vec![];
+
+        while !beam.is_empty() {
+            // Different orders can lead to the same symbols, we keep the cheapest one
+            let mut next: HashMap<Symbols, u64> = HashMap::new();
+            for (symbols, cost) in beam {
+                // The leftmost occurrence of each available merge
+                let mut available: Vec<(u32, usize, u32)> = vec![];
+                for (pos, window) in symbols.windows(2).enumerate() {
+                    if let Some(&(rank, new_id)) = merges.get(&(window[0].0, window[1].0)) {
+                        if available.iter().all(|(r, _, _)| *r != rank) {
+                            available.push((rank, pos, new_id));
+                        }
+                    }
+                }
+
+                let best = match available.iter().map(|(rank, _, _)| *rank).min() {
+                    Some(best) => best,
+                    None => {
+                        done.push((symbols, cost));
+                        continue;
+                    }
+                };
+                for (rank, pos, new_id) in available {
+                    let mut merged = symbols.clone();
+                    merged[pos] = (new_id, symbols[pos].1 + symbols[pos + 1].1);
+                    merged.remove(pos + 1);
+
+                    let cost = cost + u64::from(rank - best);
+                    let entry = next.entry(merged).or_insert(cost);
+                    *entry = (*entry).min(cost);
+                }
+            }
+
+            beam = next.into_iter().collect();
+            beam.sort_by(|(a, a_cost), (b, b_cost)| a_cost.cmp(b_cost).then_with(|| a.cmp(b)));
+            beam.truncate(n);
+        }
+
+        // Each merge removes a symbol, so the segmentations that end at different steps
+        // are all different
+        done.sort_by(|(a, a_cost), (b, b_cost)| a_cost.cmp(b_cost).then_with(|| a.cmp(b)));
+        done.into_iter()
+            .take(n)
+            .map(|(symbols, cost)| {
+                let mut word = Word::with_capacity(symbols.len());
+                for (c, len) in symbols {
+                    word.add(c, len);
+                }
+                (word, -(cost as f64))
+            })
+            .collect()
+    }
+
     pub(super) fn get_chars(&self) -> Vec<u32> {
         self.symbols.iter().map(|s| s.c).collect()
     }
@@ -306,4 +376,33 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_merge_nbest() {
+        // The word 'abc' with {'a': 0, 'b': 1, 'c': 2, 'ab': 3, 'bc': 4}, where 'bc' comes
+        // first in the merges.
+        let mut word = Word::new();
+        word.add(0, 1); // 'a'
+        word.add(1, 1); // 'b'
+        word.add(2, 1); // 'c'
+        let merges: HashMap<Pair, (u32, u32)> = vec![((1, 2), (0, 4)), ((0, 1), (1, 3))]
+            .into_iter()
+            .collect();
+
+        let nbest = word.merge_nbest(&merges, 3);
+        let nbest = nbest
+            .iter()
+            .map(|(word, score)| (word.get_chars(), *score))
+            .collect::<Vec<_>>();
+        assert_eq!(nbest, vec![(vec![0, 4], 0.0), (vec![3, 2], -1.0)]);
+
+        // The usual segmentation comes first
+        let mut merged = word.clone();
+        merged.merge_all(&merges, None, &mut rand::thread_rng());
+        assert_eq!(
+            word.merge_nbest(&merges, 1)[0].0.get_chars(),
+            merged.get_chars()
+        );
+        assert!(word.merge_nbest(&merges, 0).is_empty());
+    }
 }
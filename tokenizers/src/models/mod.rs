@@ -8,11 +8,12 @@ pub mod wordpiece;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use rand::RngCore;
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::models::bpe::{BpeTrainer, BPE};
 use crate::models::unigram::{Unigram, UnigramTrainer};
-use crate::models::wordlevel::WordLevel;
+use crate::models::wordlevel::{WordLevel, WordLevelTrainer};
 use crate::models::wordpiece::{WordPiece, WordPieceTrainer};
 use crate::{AddedToken, Model, Result, Token, Trainer};
 
@@ -38,6 +39,12 @@ impl<'a> Serialize for OrderedVocabIter<'a> {
     }
 }
 
+/// Returns the `<0xXX>` token used to represent the given byte by models that
+/// support `byte_fallback`.
+pub(crate) fn byte_to_token(byte: u8) -> String {
+    format!("<0x{:02X}>", byte)
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum ModelWrapper {
@@ -63,6 +70,36 @@ impl Model for ModelWrapper {
         }
     }
 
+    fn tokenize_nbest(&self, tokens: &str, n: usize) -> Result<Vec<(Vec<Token>, f64)>> {
+        use ModelWrapper::*;
+        match self {
+            WordLevel(t) => t.tokenize_nbest(tokens, n),
+            WordPiece(t) => t.tokenize_nbest(tokens, n),
+            BPE(t) => t.tokenize_nbest(tokens, n),
+            Unigram(t) => t.tokenize_nbest(tokens, n),
+        }
+    }
+
+    fn tokenize_with_rng(&self, tokens: &str, rng: &mut dyn RngCore) -> Result<Vec<Token>> {
+        use ModelWrapper::*;
+        match self {
+            WordLevel(t) => t.tokenize_with_rng(tokens, rng),
+            WordPiece(t) => t.tokenize_with_rng(tokens, rng),
+            BPE(t) => t.tokenize_with_rng(tokens, rng),
+            Unigram(t) => t.tokenize_with_rng(tokens, rng),
+        }
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        use ModelWrapper::*;
+        match self {
+            WordLevel(t) => t.next_seed(),
+            WordPiece(t) => t.next_seed(),
+            BPE(t) => t.next_seed(),
+            Unigram(t) => t.next_seed(),
+        }
+    }
+
     fn token_to_id(&self, token: &str) -> Option<u32> {
         use ModelWrapper::*;
         match self {
@@ -117,6 +154,7 @@ impl Model for ModelWrapper {
 pub enum TrainerWrapper {
     BpeTrainer(BpeTrainer),
     WordPieceTrainer(WordPieceTrainer),
+    WordLevelTrainer(WordLevelTrainer),
     UnigramTrainer(UnigramTrainer),
 }
 
@@ -127,6 +165,7 @@ impl Trainer for TrainerWrapper {
         match self {
             TrainerWrapper::BpeTrainer(bpe) => bpe.should_show_progress(),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.should_show_progress(),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.should_show_progress(),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.should_show_progress(),
         }
     }
@@ -135,6 +174,7 @@ impl Trainer for TrainerWrapper {
         match self {
             TrainerWrapper::BpeTrainer(bpe) => bpe.train(words).map(|(m, t)| (m.into(), t)),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
         }
     }
@@ -143,6 +183,7 @@ impl Trainer for TrainerWrapper {
         match self {
             TrainerWrapper::BpeTrainer(bpe) => bpe.process_tokens(words, tokens),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.process_tokens(words, tokens),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.process_tokens(words, tokens),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.process_tokens(words, tokens),
         }
     }
@@ -151,3 +192,4 @@ impl Trainer for TrainerWrapper {
 impl_enum_from!(BpeTrainer, TrainerWrapper, BpeTrainer);
 impl_enum_from!(WordPieceTrainer, TrainerWrapper, WordPieceTrainer);
 impl_enum_from!(UnigramTrainer, TrainerWrapper, UnigramTrainer);
+impl_enum_from!(WordLevelTrainer, TrainerWrapper, WordLevelTrainer);
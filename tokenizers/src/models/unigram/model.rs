@@ -1,8 +1,12 @@
-use crate::models::unigram::lattice::Lattice;
+use crate::models::byte_to_token;
+use crate::models::unigram::lattice::{Lattice, NodeRef};
 use crate::models::unigram::trie::{Trie, TrieBuilder};
 use crate::tokenizer::{Model, Result, Token};
 use crate::utils::cache::Cache;
+use crate::utils::{seeded_rng, SeedSequence};
 
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fs::read_to_string;
@@ -24,10 +28,19 @@ pub struct Unigram {
 
     fuse_unk: bool,
     is_optimized: bool,
+    byte_fallback: bool,
+    alpha: Option<f64>,
+    nbest_size: Option<usize>,
+    seed: Option<SeedSequence>,
 }
 impl PartialEq for Unigram {
     fn eq(&self, other: &Self) -> bool {
-        self.unk_id == other.unk_id && self.vocab == other.vocab
+        self.unk_id == other.unk_id
+            && self.vocab == other.vocab
+            && self.byte_fallback == other.byte_fallback
+            && self.alpha == other.alpha
+            && self.nbest_size == other.nbest_size
+            && self.seed == other.seed
     }
 }
 
@@ -47,6 +60,10 @@ impl Clone for Unigram {
             eos_id: self.eos_id,
             fuse_unk: self.fuse_unk,
             is_optimized: self.is_optimized,
+            byte_fallback: self.byte_fallback,
+            alpha: self.alpha,
+            nbest_size: self.nbest_size,
+            seed: self.seed.clone(),
         }
     }
 }
@@ -56,6 +73,9 @@ impl std::fmt::Debug for Unigram {
         fmt.debug_struct("BPE")
             .field("vocab", &self.vocab.len())
             .field("unk_id", &self.unk_id)
+            .field("byte_fallback", &self.byte_fallback)
+            .field("alpha", &self.alpha)
+            .field("nbest_size", &self.nbest_size)
             .finish()
     }
 }
@@ -136,9 +156,52 @@ impl Unigram {
             fuse_unk,
             cache: Cache::default(),
             is_optimized,
+            byte_fallback: false,
+            alpha: None,
+            nbest_size: None,
+            seed: None,
         })
     }
 
+    /// Set whether the characters that are not part of the vocabulary should be
+    /// decomposed into their UTF-8 bytes, using the `<0xXX>` byte tokens of the
+    /// vocabulary, instead of being mapped to `unk`.
+    pub fn set_byte_fallback(&mut self, byte_fallback: bool) {
+        self.byte_fallback = byte_fallback;
+    }
+
+    pub fn byte_fallback(&self) -> bool {
+        self.byte_fallback
+    }
+
+    /// Enable (or disable with `alpha = None`) the sampling of the segmentations, as used
+    /// for subword regularization. `alpha` is the smoothing parameter applied to the scores,
+    /// and `nbest_size` restricts the sampling to the `n` best segmentations. When
+    /// `nbest_size` is `None` (or `0`), the segmentations are sampled from the whole lattice.
+    pub fn set_sampling(&mut self, alpha: Option<f64>, nbest_size: Option<usize>) {
+        self.alpha = alpha;
+        self.nbest_size = nbest_size;
+    }
+
+    pub fn alpha(&self) -> Option<f64> {
+        self.alpha
+    }
+
+    pub fn nbest_size(&self) -> Option<usize> {
+        self.nbest_size
+    }
+
+    /// Set the seed used while sampling. Each encode call draws its own random number
+    /// generator from this seed, so the successive calls get different segmentations, but
+    /// the same seed always replays the same sequence of segmentations.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed.map(SeedSequence::new);
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed.as_ref().map(SeedSequence::seed)
+    }
+
     #[cfg(test)]
     pub(super) fn set_fuse_unk(&mut self, fuse_unk: bool) {
         self.fuse_unk = fuse_unk;
@@ -217,6 +280,15 @@ impl Unigram {
         if sentence.is_empty() {
             return vec![];
         }
+        if let Some(alpha) = self.alpha {
+            // Each call can give a different result, so we don't use the cache
+            return match &self.seed {
+                Some(seeds) => {
+                    self.encode_sampled(sentence, alpha, &mut seeded_rng(seeds.next_seed(), 0))
+                }
+                None => self.encode_sampled(sentence, alpha, &mut thread_rng()),
+            };
+        }
         if let Some(result) = self.cache.get(sentence) {
             result.to_vec()
         } else {
@@ -342,10 +414,45 @@ impl Unigram {
     fn encode_unoptimized(&self, sentence: &str) -> Vec<String> {
         let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
         self.populate_nodes(&mut lattice);
+        let path = lattice.viterbi();
+        self.path_to_pieces(&lattice, &path)
+    }
+
+    /// Sample a segmentation of the given sentence. The path is either sampled among the
+    /// `nbest_size` best ones, or from the whole lattice.
+    fn encode_sampled<R: Rng + ?Sized>(
+        &self,
+        sentence: &str,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Vec<String> {
+        let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
+        self.populate_nodes(&mut lattice);
+        let path = match self.nbest_size {
+            Some(n) if n > 0 => {
+                let mut paths = lattice.nbest(n);
+                let scores = paths
+                    .iter()
+                    .map(|path| alpha * path.iter().map(|node| node.borrow().score).sum::<f64>())
+                    .collect::<Vec<_>>();
+                // Shift the scores to avoid underflowing while computing the weights
+                let max_score = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let weights = scores.iter().map(|score| (score - max_score).exp());
+                let index = WeightedIndex::new(weights).unwrap().sample(rng);
+                paths.swap_remove(index)
+            }
+            _ => lattice.sample_with_rng(alpha, rng),
+        };
+        self.path_to_pieces(&lattice, &path)
+    }
+
+    /// Convert the given path of the lattice to its pieces, fusing the consecutive unknown
+    /// pieces if `fuse_unk` is set.
+    fn path_to_pieces(&self, lattice: &Lattice, path: &[NodeRef]) -> Vec<String> {
         if self.fuse_unk {
             let mut results = vec![];
             let mut token = String::new();
-            for node in lattice.viterbi().iter() {
+            for node in path {
                 let item = lattice.piece(&node.borrow());
                 if node.borrow().id == self.unk_id {
                     token.push_str(&item);
@@ -362,10 +469,53 @@ impl Unigram {
             }
             results
         } else {
-            lattice.tokens()
+            path.iter()
+                .map(|node| lattice.piece(&node.borrow()))
+                .collect()
         }
     }
 
+    /// Convert the given pieces to their `Token`, with the relevant offsets. The pieces that
+    /// are not part of the vocabulary use `byte_fallback` if possible, or `unk` otherwise.
+    fn pieces_to_tokens(&self, pieces: Vec<String>) -> Vec<Token> {
+        let mut offset = 0;
+        let mut tokens = Vec::with_capacity(pieces.len());
+        for string in pieces {
+            let len = string.len();
+            let offsets = (offset, offset + len);
+            match self.token_to_ids.get(&string) {
+                Some(id) => tokens.push(Token::new(*id, string, offsets)),
+                None => match self.byte_fallback_tokens(&string, offset) {
+                    Some(byte_tokens) => tokens.extend(byte_tokens),
+                    None => tokens.push(Token::new(self.unk_id as u32, string, offsets)),
+                },
+            }
+            offset += len;
+        }
+        tokens
+    }
+
+    /// Decompose the given unknown piece in its `<0xXX>` byte tokens, all pointing
+    /// to their originating character. Returns `None` if `byte_fallback` is disabled
+    /// or if some of these byte tokens are not part of the vocabulary.
+    fn byte_fallback_tokens(&self, piece: &str, offset: usize) -> Option<Vec<Token>> {
+        if !self.byte_fallback {
+            return None;
+        }
+
+        let mut tokens = Vec::with_capacity(piece.len());
+        for (i, c) in piece.char_indices() {
+            let offsets = (offset + i, offset + i + c.len_utf8());
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                let value = byte_to_token(byte);
+                let id = *self.token_to_ids.get(&value)?;
+                tokens.push(Token::new(id, value, offsets));
+            }
+        }
+        Some(tokens)
+    }
+
     /// Iterate of vocabulary of the model as a pair of `(token, score)`.
     pub fn iter(&self) -> UnigramIterator {
         UnigramIterator { model: self, i: 0 }
@@ -416,19 +566,39 @@ impl Model for Unigram {
     }
 
     fn tokenize(&self, sentence: &str) -> Result<Vec<Token>> {
-        let tokens = self.encode(sentence);
-        let mut offset = 0;
-        Ok(tokens
+        Ok(self.pieces_to_tokens(self.encode(sentence)))
+    }
+
+    fn tokenize_with_rng(&self, sentence: &str, rng: &mut dyn RngCore) -> Result<Vec<Token>> {
+        match self.alpha {
+            Some(alpha) if !sentence.is_empty() => {
+                Ok(self.pieces_to_tokens(self.encode_sampled(sentence, alpha, rng)))
+            }
+            _ => self.tokenize(sentence),
+        }
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        // Only the sampling needs a seed
+        self.alpha
+            .and(self.seed.as_ref())
+            .map(SeedSequence::next_seed)
+    }
+
+    fn tokenize_nbest(&self, sentence: &str, n: usize) -> Result<Vec<(Vec<Token>, f64)>> {
+        if sentence.is_empty() {
+            return Ok(vec![(vec![], 0.0)].into_iter().take(n).collect());
+        }
+
+        let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
+        self.populate_nodes(&mut lattice);
+        Ok(lattice
+            .nbest(n)
             .iter()
-            .map(|string| {
-                let id: u32 = match self.token_to_ids.get(string) {
-                    Some(id) => *id,
-                    None => self.unk_id as u32,
-                };
-                let len = string.len();
-                let offsets = (offset, offset + len);
-                offset += len;
-                Token::new(id, string.to_string(), offsets)
+            .map(|path| {
+                let score = path.iter().map(|node| node.borrow().score).sum();
+                let pieces = self.path_to_pieces(&lattice, path);
+                (self.pieces_to_tokens(pieces), score)
             })
             .collect())
     }
@@ -580,4 +750,135 @@ mod tests {
             assert_eq!(model.encode("abqrcd"), vec!["ab", "q", "r", "cd"]);
         }
     }
+
+    #[test]
+    fn test_byte_fallback() {
+        let sentencepieces = vec![
+            ("<unk>".to_string(), 0.0),
+            ("<0xC3>".to_string(), -0.01),
+            ("<0xA9>".to_string(), -0.03),
+            ("a".to_string(), -0.1),
+        ];
+        let mut model = Unigram::from(sentencepieces, 0).unwrap();
+
+        assert_eq!(
+            model.tokenize("aé").unwrap(),
+            vec![
+                Token::new(3, "a".into(), (0, 1)),
+                Token::new(0, "é".into(), (1, 3)),
+            ]
+        );
+
+        model.set_byte_fallback(true);
+        assert_eq!(
+            model.tokenize("aé").unwrap(),
+            vec![
+                Token::new(3, "a".into(), (0, 1)),
+                Token::new(1, "<0xC3>".into(), (1, 3)),
+                Token::new(2, "<0xA9>".into(), (1, 3)),
+            ]
+        );
+
+        // Missing byte tokens fall back to unk
+        assert_eq!(
+            model.tokenize("b").unwrap(),
+            vec![Token::new(0, "b".into(), (0, 1))]
+        );
+    }
+
+    #[test]
+    fn test_sampling() {
+        let sentencepieces = vec![
+            ("<unk>".to_string(), 0.0),
+            ("a".to_string(), -1.0),
+            ("b".to_string(), -1.0),
+            ("ab".to_string(), -1.5),
+            ("ba".to_string(), -1.5),
+            ("aba".to_string(), -2.0),
+            ("bab".to_string(), -2.0),
+        ];
+        let mut model = Unigram::from(sentencepieces, 0).unwrap();
+        let viterbi = model.encode("ababab");
+        assert_eq!(viterbi, vec!["aba", "bab"]);
+
+        // A single best segmentation always gives the Viterbi path
+        model.set_sampling(Some(0.1), Some(1));
+        for _ in 0..10 {
+            assert_eq!(model.encode("ababab"), viterbi);
+        }
+
+        for nbest_size in &[None, Some(4)] {
+            model.set_sampling(Some(0.1), *nbest_size);
+
+            // The successive calls get different segmentations, but the same seed always
+            // replays the same sequence of segmentations
+            model.set_seed(Some(42));
+            let sampled: Vec<_> = (0..20).map(|_| model.encode("ababab")).collect();
+            assert!(sampled.iter().any(|pieces| pieces != &sampled[0]));
+            model.set_seed(Some(42));
+            let replayed: Vec<_> = (0..20).map(|_| model.encode("ababab")).collect();
+            assert_eq!(replayed, sampled);
+            // And a given random number generator always gives the same segmentation
+            model.set_seed(None);
+            let seeded = model.tokenize_with_rng("ababab", &mut seeded_rng(42, 0));
+            assert_eq!(
+                model
+                    .tokenize_with_rng("ababab", &mut seeded_rng(42, 0))
+                    .unwrap(),
+                seeded.unwrap()
+            );
+
+            // Different seeds give different valid segmentations
+            let mut segmentations = std::collections::HashSet::new();
+            for seed in 0..50 {
+                model.set_seed(Some(seed));
+                let pieces = model.encode("ababab");
+                assert_eq!(pieces.concat(), "ababab");
+                segmentations.insert(pieces);
+            }
+            assert!(segmentations.len() > 1);
+            if let Some(n) = nbest_size {
+                assert!(segmentations.len() <= *n);
+            }
+        }
+
+        // Disabling the sampling goes back to the Viterbi path
+        model.set_sampling(None, None);
+        assert_eq!(model.encode("ababab"), viterbi);
+    }
+
+    #[test]
+    fn test_tokenize_nbest() {
+        let sentencepieces = vec![
+            ("<unk>".to_string(), 0.0),
+            ("a".to_string(), -1.0),
+            ("b".to_string(), -1.0),
+            ("ab".to_string(), -1.5),
+        ];
+        let model = Unigram::from(sentencepieces, 0).unwrap();
+
+        let nbest = model.tokenize_nbest("abc", 3).unwrap();
+        assert_eq!(nbest.len(), 2);
+        assert_eq!(
+            nbest[0].0,
+            vec![
+                Token::new(3, "ab".into(), (0, 2)),
+                Token::new(0, "c".into(), (2, 3)),
+            ]
+        );
+        assert_eq!(
+            nbest[1].0,
+            vec![
+                Token::new(1, "a".into(), (0, 1)),
+                Token::new(2, "b".into(), (1, 2)),
+                Token::new(0, "c".into(), (2, 3)),
+            ]
+        );
+        let unk_score = model.min_score - K_UNK_PENALTY;
+        assert!((nbest[0].1 - (-1.5 + unk_score)).abs() < 1e-9);
+        assert!((nbest[1].1 - (-2.0 + unk_score)).abs() < 1e-9);
+
+        assert_eq!(model.tokenize_nbest("abc", 0).unwrap(), vec![]);
+        assert_eq!(model.tokenize_nbest("", 2).unwrap(), vec![(vec![], 0.0)]);
+    }
 }
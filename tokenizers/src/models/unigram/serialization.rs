@@ -10,9 +10,13 @@ impl Serialize for Unigram {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("Unigram", 2)?;
+        let mut model = serializer.serialize_struct("Unigram", 6)?;
 
         model.serialize_field("unk_id", &self.unk_id)?;
+        model.serialize_field("byte_fallback", &self.byte_fallback())?;
+        model.serialize_field("alpha", &self.alpha())?;
+        model.serialize_field("nbest_size", &self.nbest_size())?;
+        model.serialize_field("seed", &self.seed())?;
         model.serialize_field("vocab", &self.vocab)?;
 
         model.end()
@@ -24,7 +28,18 @@ impl<'de> Deserialize<'de> for Unigram {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("Unigram", &["vocab", "unk_id"], UnigramVisitor)
+        deserializer.deserialize_struct(
+            "Unigram",
+            &[
+                "vocab",
+                "unk_id",
+                "byte_fallback",
+                "alpha",
+                "nbest_size",
+                "seed",
+            ],
+            UnigramVisitor,
+        )
     }
 }
 
@@ -42,18 +57,32 @@ impl<'de> Visitor<'de> for UnigramVisitor {
     {
         let mut vocab: Option<Vec<(String, f64)>> = None;
         let mut unk_id: Option<usize> = None;
+        let mut byte_fallback = false;
+        let mut alpha: Option<f64> = None;
+        let mut nbest_size: Option<usize> = None;
+        let mut seed: Option<u64> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
                 "unk_id" => {
                     unk_id = map.next_value()?;
                 }
+                "byte_fallback" => byte_fallback = map.next_value()?,
+                "alpha" => alpha = map.next_value()?,
+                "nbest_size" => nbest_size = map.next_value()?,
+                "seed" => seed = map.next_value()?,
                 "vocab" => vocab = Some(map.next_value()?),
                 _ => (),
             }
         }
         match (vocab, unk_id) {
-            (Some(vocab), Some(unk_id)) => Ok(Unigram::from(vocab, unk_id)
-                .map_err(|err| Error::custom(&format!("Unable to load vocab {:?}", err)))?),
+            (Some(vocab), Some(unk_id)) => {
+                let mut model = Unigram::from(vocab, unk_id)
+                    .map_err(|err| Error::custom(&format!("Unable to load vocab {:?}", err)))?;
+                model.set_byte_fallback(byte_fallback);
+                model.set_sampling(alpha, nbest_size);
+                model.set_seed(seed);
+                Ok(model)
+            }
             (None, Some(_)) => Err(Error::custom("Missing vocab")),
             (None, None) => Err(Error::custom("Missing vocab and unk_id")),
             (Some(_), None) => Err(Error::custom("Missing unk_id")),
@@ -76,6 +105,46 @@ mod test {
         assert_eq!(model, reconstructed);
     }
 
+    #[test]
+    fn test_serialization_byte_fallback() {
+        let vocab = vec![("<unk>".to_string(), 0.0), ("<0x41>".to_string(), -0.5)];
+        let mut model = Unigram::from(vocab, 0).unwrap();
+        model.set_byte_fallback(true);
+
+        let data = serde_json::to_string(&model).unwrap();
+        assert_eq!(
+            data,
+            r#"{"unk_id":0,"byte_fallback":true,"alpha":null,"nbest_size":null,"seed":null,"vocab":[["<unk>",0.0],["<0x41>",-0.5]]}"#
+        );
+        let reconstructed: Unigram = serde_json::from_str(&data).unwrap();
+        assert_eq!(model, reconstructed);
+        assert!(reconstructed.byte_fallback());
+
+        // Previous serialization format without `byte_fallback`
+        let data = r#"{"unk_id":0,"vocab":[["<unk>",0.0],["<0x41>",-0.5]]}"#;
+        let reconstructed: Unigram = serde_json::from_str(data).unwrap();
+        assert!(!reconstructed.byte_fallback());
+    }
+
+    #[test]
+    fn test_serialization_sampling() {
+        let vocab = vec![("<unk>".to_string(), 0.0), ("a".to_string(), -0.5)];
+        let mut model = Unigram::from(vocab, 0).unwrap();
+        model.set_sampling(Some(0.1), Some(64));
+        model.set_seed(Some(42));
+
+        let data = serde_json::to_string(&model).unwrap();
+        assert_eq!(
+            data,
+            r#"{"unk_id":0,"byte_fallback":false,"alpha":0.1,"nbest_size":64,"seed":42,"vocab":[["<unk>",0.0],["a",-0.5]]}"#
+        );
+        let reconstructed: Unigram = serde_json::from_str(&data).unwrap();
+        assert_eq!(model, reconstructed);
+        assert_eq!(reconstructed.alpha(), Some(0.1));
+        assert_eq!(reconstructed.nbest_size(), Some(64));
+        assert_eq!(reconstructed.seed(), Some(42));
+    }
+
     #[test]
     fn test_serialization_unk_id_not_zero() {
         let vocab = vec![("a".to_string(), -0.5), ("<unk>".to_string(), 0.0)];
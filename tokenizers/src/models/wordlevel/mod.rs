@@ -8,6 +8,8 @@ use std::io::{BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
 mod serialization;
+mod trainer;
+pub use trainer::*;
 
 type Vocab = HashMap<String, u32>;
 
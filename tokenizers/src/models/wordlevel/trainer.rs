@@ -0,0 +1,176 @@
+use super::WordLevel;
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// A `WordLevelTrainer` can train a `WordLevel` model from `word_counts`.
+#[derive(Builder, Debug, Clone)]
+pub struct WordLevelTrainer {
+    /// The minimum frequency a word must have to be part of the vocabulary
+    #[builder(default)]
+    min_frequency: u32,
+    /// The target vocabulary size
+    #[builder(default = "30_000")]
+    vocab_size: usize,
+    /// Whether to show progress while training
+    #[builder(default = "true")]
+    show_progress: bool,
+    /// A list of special tokens that the model should know of
+    #[builder(default)]
+    special_tokens: Vec<AddedToken>,
+    /// The token used for unknown words, added to the vocabulary after the special tokens
+    #[builder(default = "String::from(\"<unk>\")")]
+    unk_token: String,
+}
+
+impl Default for WordLevelTrainer {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl WordLevelTrainer {
+    pub fn builder() -> WordLevelTrainerBuilder {
+        WordLevelTrainerBuilder::default()
+    }
+
+    fn do_train(&self, word_counts: HashMap<String, u32>) -> Result<(WordLevel, Vec<AddedToken>)> {
+        // Most frequent words first, and alphabetically for equal counts to stay deterministic
+        let mut ordered_counts = word_counts
+            .into_iter()
+            .filter(|(_, n)| *n >= self.min_frequency)
+            .collect::<Vec<_>>();
+        ordered_counts.sort_by(|(wa, na), (wb, nb)| (Reverse(na), wa).cmp(&(Reverse(nb), wb)));
+
+        let mut vocab: HashMap<String, u32> = HashMap::with_capacity(self.vocab_size);
+        fn add_word(vocab: &mut HashMap<String, u32>, word: String) {
+            let id = vocab.len() as u32;
+            vocab.entry(word).or_insert(id);
+        }
+        // The special tokens and the unk token are always part of the vocabulary, even when
+        // `vocab_size` is too small to hold them
+        for token in &self.special_tokens {
+            add_word(&mut vocab, token.content.clone());
+        }
+        add_word(&mut vocab, self.unk_token.clone());
+        for (word, _) in ordered_counts {
+            if vocab.len() >= self.vocab_size {
+                break;
+            }
+            add_word(&mut vocab, word);
+        }
+
+        Ok((
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token(self.unk_token.clone())
+                .build(),
+            self.special_tokens.clone(),
+        ))
+    }
+}
+
+impl Trainer for WordLevelTrainer {
+    type Model = WordLevel;
+
+    /// Train a WordLevel model
+    fn train(&self, word_counts: HashMap<String, u32>) -> Result<(WordLevel, Vec<AddedToken>)> {
+        self.do_train(word_counts)
+    }
+
+    /// Process a bunch of tokens, counting them
+    fn process_tokens(&self, words: &mut HashMap<String, u32>, tokens: Vec<String>) {
+        for token in tokens {
+            words
+                .entry(token.clone())
+                .and_modify(|c| *c += 1)
+                .or_insert(1);
+        }
+    }
+
+    /// Whether we should show progress
+    fn should_show_progress(&self) -> bool {
+        self.show_progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Model;
+
+    #[test]
+    fn test_train() {
+        let word_counts: HashMap<String, u32> = [
+            ("the".into(), 25),
+            ("roses".into(), 22),
+            ("are".into(), 24),
+            ("red".into(), 12),
+            ("voilets".into(), 10),
+            ("blue".into(), 16),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let trainer = WordLevelTrainer::builder()
+            .vocab_size(5)
+            .show_progress(false)
+            .build()
+            .unwrap();
+        let (model, _) = trainer.train(word_counts.clone()).unwrap();
+        let expected_vocab: HashMap<String, u32> = [
+            ("<unk>".into(), 0),
+            ("the".into(), 1),
+            ("are".into(), 2),
+            ("roses".into(), 3),
+            ("blue".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+        // Unknown words use the trained unk token
+        assert_eq!(model.tokenize("violets").unwrap()[0].id, 0);
+
+        // If we specify a min_frequency and special tokens
+        let trainer = WordLevelTrainer::builder()
+            .vocab_size(5)
+            .min_frequency(15)
+            .special_tokens(vec![AddedToken::from("[PAD]", true)])
+            .unk_token("[UNK]".into())
+            .show_progress(false)
+            .build()
+            .unwrap();
+        let (model, special_tokens) = trainer.train(word_counts.clone()).unwrap();
+        let expected_vocab: HashMap<String, u32> = [
+            ("[PAD]".into(), 0),
+            ("[UNK]".into(), 1),
+            ("the".into(), 2),
+            ("are".into(), 3),
+            ("roses".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+        assert_eq!(special_tokens, vec![AddedToken::from("[PAD]", true)]);
+        assert_eq!(model.tokenize("red").unwrap()[0].id, 1);
+
+        // The unk token is kept even when the vocab_size only fits the special tokens
+        let trainer = WordLevelTrainer::builder()
+            .vocab_size(1)
+            .special_tokens(vec![AddedToken::from("[PAD]", true)])
+            .unk_token("[UNK]".into())
+            .show_progress(false)
+            .build()
+            .unwrap();
+        let (model, _) = trainer.train(word_counts).unwrap();
+        let expected_vocab: HashMap<String, u32> = [("[PAD]".into(), 0), ("[UNK]".into(), 1)]
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+        assert_eq!(model.tokenize("the").unwrap()[0].id, 1);
+    }
+}
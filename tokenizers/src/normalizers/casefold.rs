@@ -0,0 +1,201 @@
+use crate::tokenizer::{NormalizedString, Normalizer, Result};
+use serde::{Deserialize, Serialize};
+use unicode_normalization_alignments::char::canonical_combining_class;
+
+/// The languages with some specific case mappings
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// Turkish, where `I` lowercases to the dotless `ı`, and `İ` to `i`
+    Tr,
+    /// Azerbaijani, with the same mappings as Turkish
+    Az,
+    /// Lithuanian, where `i` and `j` keep their dot when followed by an accent
+    Lt,
+}
+
+/// Applies the full Unicode case folding, which is like lowercasing, except that some
+/// characters get expanded (`ß` to `ss`, `ﬁ` to `fi`, ...), and that the characters which
+/// only differ by their case in some contexts are folded to the same one (`ς` and `σ`, ...).
+///
+/// With a `Locale`, the language-specific lowercasing of the dotted and dotless `I` is
+/// applied first.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct CaseFold {
+    locale: Option<Locale>,
+}
+
+impl CaseFold {
+    pub fn new(locale: Option<Locale>) -> Self {
+        Self { locale }
+    }
+
+    pub fn get_locale(&self) -> Option<Locale> {
+        self.locale
+    }
+}
+
+/// The position of the next character with a combining class of 230 (above), as long as
+/// there is no other character with a combining class of 0 or 230 before it
+fn next_above(chars: &[char]) -> Option<usize> {
+    chars
+        .iter()
+        .position(|c| matches!(canonical_combining_class(*c), 0 | 230))
+        .filter(|i| canonical_combining_class(chars[*i]) == 230)
+}
+
+/// Push the full case folding of the given char
+fn fold(c: char, dest: &mut Vec<char>) {
+    match c {
+        // The dotless i is kept as is, to be folded only with a Turkic locale
+        'ı' => dest.push(c),
+        // Cherokee folds to its uppercase letters, which came first in Unicode
+        '\u{13A0}'..='\u{13F5}' => dest.push(c),
+        '\u{13F8}'..='\u{13FD}' | '\u{AB70}'..='\u{ABBF}' => dest.extend(c.to_uppercase()),
+        // Going through the uppercase gives the expanded foldings, like `ß` to `ss`, and
+        // merges the characters having the same uppercase, like `ς` and `σ`
+        _ => dest.extend(
+            c.to_lowercase()
+                .flat_map(|c| c.to_uppercase())
+                .flat_map(|c| c.to_lowercase()),
+        ),
+    }
+}
+
+impl Normalizer for CaseFold {
+    fn normalize(&self, normalized: &mut NormalizedString) -> Result<()> {
+        let chars = normalized.get().chars().collect::<Vec<_>>();
+        let mut transformations: Vec<(char, isize)> = Vec::with_capacity(chars.len());
+        let mut folded = Vec::with_capacity(3);
+        let mut removed = vec![false; chars.len()];
+
+        for (i, c) in chars.iter().copied().enumerate() {
+            if removed[i] {
+                // Attach the removal to the last char we kept
+                if let Some((_, change)) = transformations.last_mut() {
+                    *change -= 1;
+                }
+                continue;
+            }
+
+            folded.clear();
+            let following = &chars[i + 1..];
+            match (self.locale, c) {
+                (Some(Locale::Tr), 'I') | (Some(Locale::Az), 'I') => {
+                    match next_above(following) {
+                        // `I` followed by a combining dot above is the same as `İ`
+                        Some(dot) if following[dot] == '\u{0307}' => {
+                            removed[i + 1 + dot] = true;
+                            folded.push('i');
+                        }
+                        _ => folded.push('ı'),
+                    }
+                }
+                (Some(Locale::Tr), 'İ') | (Some(Locale::Az), 'İ') => folded.push('i'),
+                (Some(Locale::Lt), 'I') | (Some(Locale::Lt), 'J') | (Some(Locale::Lt), 'Į')
+                    if next_above(following).is_some() =>
+                {
+                    fold(c, &mut folded);
+                    folded.push('\u{0307}');
+                }
+                (Some(Locale::Lt), 'Ì') => folded.extend(&['i', '\u{0307}', '\u{0300}']),
+                (Some(Locale::Lt), 'Í') => folded.extend(&['i', '\u{0307}', '\u{0301}']),
+                (Some(Locale::Lt), 'Ĩ') => folded.extend(&['i', '\u{0307}', '\u{0303}']),
+                _ => fold(c, &mut folded),
+            }
+
+            transformations.extend(
+                folded
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| (*c, if i == 0 { 0 } else { 1 })),
+            );
+        }
+
+        normalized.transform(transformations, 0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalizers::NormalizerWrapper;
+    use crate::tokenizer::normalizer::Range;
+
+    fn fold_str(locale: Option<Locale>, s: &str) -> String {
+        let mut n = NormalizedString::from(s);
+        CaseFold::new(locale).normalize(&mut n).unwrap();
+        n.get().to_owned()
+    }
+
+    #[test]
+    fn full_folding() {
+        assert_eq!(fold_str(None, "Straße ẞ"), "strasse ss");
+        assert_eq!(fold_str(None, "ΣΊΣΥΦΟΣ σίσυφος"), "σίσυφοσ σίσυφοσ");
+        assert_eq!(fold_str(None, "ﬁ µ ſ ϐ ᾳ"), "fi μ s β αι");
+        assert_eq!(fold_str(None, "İı"), "i\u{0307}ı");
+        assert_eq!(fold_str(None, "Ꭰꭰᏸ"), "ᎠᎠᏰ");
+
+        let mut n = NormalizedString::from("Maße ﬁn");
+        CaseFold::default().normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "masse fin");
+        assert_eq!(n.get_range_original(Range::Normalized(2..4)), Some("ß"));
+        assert_eq!(n.get_range_original(Range::Normalized(4..5)), Some("e"));
+        assert_eq!(n.get_range_original(Range::Normalized(6..8)), Some("ﬁ"));
+    }
+
+    #[test]
+    fn turkic() {
+        for locale in &[Locale::Tr, Locale::Az] {
+            assert_eq!(fold_str(Some(*locale), "IĞDIR İzmir"), "ığdır izmir");
+            assert_eq!(fold_str(Some(*locale), "ı"), "ı");
+        }
+
+        let mut n = NormalizedString::from("I\u{0307}I\u{0323}\u{0307}x");
+        CaseFold::new(Some(Locale::Tr)).normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "ii\u{0323}x");
+        assert_eq!(n.get_range_original(Range::Normalized(0..1)), Some("I"));
+        assert_eq!(n.get_range_original(Range::Normalized(1..2)), Some("I"));
+        assert_eq!(
+            n.get_range_original(Range::Normalized(2..4)),
+            Some("\u{0323}")
+        );
+        assert_eq!(n.get_range_original(Range::Normalized(4..5)), Some("x"));
+    }
+
+    #[test]
+    fn lithuanian() {
+        let lt = Some(Locale::Lt);
+        assert_eq!(
+            fold_str(lt, "I\u{0300} J\u{0323}\u{0301} I"),
+            "i\u{0307}\u{0300} j\u{0307}\u{0323}\u{0301} i"
+        );
+        assert_eq!(
+            fold_str(lt, "Ì Í Ĩ"),
+            "i\u{0307}\u{0300} i\u{0307}\u{0301} i\u{0307}\u{0303}"
+        );
+        assert_eq!(fold_str(lt, "I\u{0323}x"), "i\u{0323}x");
+    }
+
+    #[test]
+    fn serialization() {
+        let casefold = CaseFold::new(Some(Locale::Tr));
+        let casefold_s = r#"{"type":"CaseFold","locale":"tr"}"#;
+        assert_eq!(serde_json::to_string(&casefold).unwrap(), casefold_s);
+        assert_eq!(
+            serde_json::from_str::<CaseFold>(casefold_s).unwrap(),
+            casefold
+        );
+
+        let casefold_s = r#"{"type":"CaseFold","locale":null}"#;
+        assert_eq!(
+            serde_json::to_string(&CaseFold::default()).unwrap(),
+            casefold_s
+        );
+        let deserialized: NormalizerWrapper =
+            serde_json::from_str(r#"{"type":"CaseFold"}"#).unwrap();
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), casefold_s);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::tokenizer::{NormalizedString, Normalizer, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Maps some characters to a string each, like to fold full-width characters to ASCII,
+/// or to expand ligatures. A character mapped to an empty string gets removed.
+///
+/// The mapping can be loaded from a TSV file, with one `<source>\t<target>` per line. Each
+/// field is either the literal text, or a list of codepoints like `U+0066 U+0069`. Empty
+/// lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct CharMap {
+    #[serde(serialize_with = "crate::utils::ordered_map")]
+    mapping: HashMap<char, String>,
+}
+
+impl CharMap {
+    pub fn new(mapping: HashMap<char, String>) -> Self {
+        Self { mapping }
+    }
+
+    /// Load the mapping from the given TSV file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+
+        let mut mapping = HashMap::new();
+        for (index, line) in file.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let err = || format!("CharMap: invalid mapping on line {}: {}", index + 1, line);
+            let mut fields = line.split('\t');
+            let (source, target) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(source), Some(target), None) => (source, target),
+                _ => return Err(err().into()),
+            };
+            let source = parse_field(source).ok_or_else(err)?;
+            let target = parse_field(target).ok_or_else(err)?;
+            let mut source = source.chars();
+            match (source.next(), source.next()) {
+                (Some(c), None) => {
+                    mapping.insert(c, target);
+                }
+                _ => return Err(err().into()),
+            }
+        }
+
+        Ok(Self { mapping })
+    }
+
+    pub fn get_mapping(&self) -> &HashMap<char, String> {
+        &self.mapping
+    }
+}
+
+/// Parse a field of the TSV file, either literal text or a list of codepoints
+fn parse_field(field: &str) -> Option<String> {
+    if field.starts_with("U+") {
+        field
+            .split_whitespace()
+            .map(|codepoint| {
+                let codepoint = codepoint.strip_prefix("U+")?;
+                std::char::from_u32(u32::from_str_radix(codepoint, 16).ok()?)
+            })
+            .collect()
+    } else {
+        Some(field.to_owned())
+    }
+}
+
+impl Normalizer for CharMap {
+    fn normalize(&self, normalized: &mut NormalizedString) -> Result<()> {
+        let removed = |c: char| self.mapping.get(&c).map_or(false, |s| s.is_empty());
+        // The removed characters are filtered out first, so that each mapped character can
+        // then be transformed without any removal
+        if normalized.get().chars().any(removed) {
+            normalized.filter(|c| !removed(c));
+        }
+
+        let mut modified = false;
+        let mut transformations = Vec::with_capacity(normalized.get().len());
+        for c in normalized.get().chars() {
+            match self.mapping.get(&c) {
+                Some(target) => {
+                    modified = true;
+                    transformations.extend(
+                        target
+                            .chars()
+                            .enumerate()
+                            .map(|(i, c)| (c, if i == 0 { 0 } else { 1 })),
+                    );
+                }
+                None => transformations.push((c, 0)),
+            }
+        }
+        if modified {
+            normalized.transform(transformations, 0);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::normalizer::Range;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn get_charmap() -> CharMap {
+        CharMap::new(
+            vec![('Ａ', "A"), ('ﬁ', "fi"), ('\u{200B}', "")]
+                .into_iter()
+                .map(|(c, s)| (c, s.to_owned()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn normalize() {
+        let mut n = NormalizedString::from("\u{200B}Ａ ﬁ\u{200B}le");
+        get_charmap().normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "A file");
+        assert_eq!(n.get_range_original(Range::Normalized(0..1)), Some("Ａ"));
+        assert_eq!(n.get_range_original(Range::Normalized(2..3)), Some("ﬁ"));
+        assert_eq!(n.get_range_original(Range::Normalized(3..4)), Some("ﬁ"));
+        assert_eq!(n.get_range_original(Range::Normalized(4..6)), Some("le"));
+    }
+
+    #[test]
+    fn from_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all("# Some folding\nＡ\tA\nU+FB01\tU+0066 U+0069\n\nU+200B\t\n".as_bytes())
+            .unwrap();
+
+        let charmap = CharMap::from_file(file.path()).unwrap();
+        assert_eq!(charmap, get_charmap());
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all("AB\tA\n".as_bytes()).unwrap();
+        assert!(CharMap::from_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn serialization() {
+        let charmap = get_charmap();
+        let charmap_s =
+            "{\"type\":\"CharMap\",\"mapping\":{\"\u{200B}\":\"\",\"ﬁ\":\"fi\",\"Ａ\":\"A\"}}";
+        assert_eq!(serde_json::to_string(&charmap).unwrap(), charmap_s);
+        assert_eq!(serde_json::from_str::<CharMap>(charmap_s).unwrap(), charmap);
+    }
+}
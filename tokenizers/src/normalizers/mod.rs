@@ -1,13 +1,19 @@
 pub mod bert;
+pub mod casefold;
+pub mod charmap;
 pub mod precompiled;
 pub mod replace;
+pub mod scoped;
 pub mod strip;
 pub mod unicode;
 pub mod utils;
 
 pub use crate::normalizers::bert::BertNormalizer;
+pub use crate::normalizers::casefold::{CaseFold, Locale};
+pub use crate::normalizers::charmap::CharMap;
 pub use crate::normalizers::precompiled::Precompiled;
 pub use crate::normalizers::replace::Replace;
+pub use crate::normalizers::scoped::{Scope, Scoped};
 pub use crate::normalizers::strip::{Strip, StripAccents};
 pub use crate::normalizers::unicode::{Nmt, NFC, NFD, NFKC, NFKD};
 pub use crate::normalizers::utils::{Lowercase, Sequence};
@@ -32,6 +38,9 @@ pub enum NormalizerWrapper {
     Nmt(Nmt),
     Precompiled(Precompiled),
     Replace(Replace),
+    CharMap(CharMap),
+    Scoped(Scoped),
+    CaseFold(CaseFold),
 }
 
 impl Normalizer for NormalizerWrapper {
@@ -49,6 +58,9 @@ impl Normalizer for NormalizerWrapper {
             NormalizerWrapper::Nmt(lc) => lc.normalize(normalized),
             NormalizerWrapper::Precompiled(lc) => lc.normalize(normalized),
             NormalizerWrapper::Replace(lc) => lc.normalize(normalized),
+            NormalizerWrapper::CharMap(lc) => lc.normalize(normalized),
+            NormalizerWrapper::Scoped(lc) => lc.normalize(normalized),
+            NormalizerWrapper::CaseFold(lc) => lc.normalize(normalized),
         }
     }
 }
@@ -65,3 +77,6 @@ impl_enum_from!(Lowercase, NormalizerWrapper, Lowercase);
 impl_enum_from!(Nmt, NormalizerWrapper, Nmt);
 impl_enum_from!(Precompiled, NormalizerWrapper, Precompiled);
 impl_enum_from!(Replace, NormalizerWrapper, Replace);
+impl_enum_from!(CharMap, NormalizerWrapper, CharMap);
+impl_enum_from!(Scoped, NormalizerWrapper, Scoped);
+impl_enum_from!(CaseFold, NormalizerWrapper, CaseFold);
@@ -1,5 +1,9 @@
 use crate::tokenizer::{NormalizedString, Normalizer, Result};
 pub use spm_precompiled::Precompiled;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 use unicode_segmentation::UnicodeSegmentation;
 
 impl Normalizer for Precompiled {
@@ -57,3 +61,307 @@ impl Normalizer for Precompiled {
         Ok(())
     }
 }
+
+/// Compile the given normalization rules into a `precompiled_charsmap`, the format used by
+/// SentencePiece: the size in bytes of a double-array trie, the trie itself, and then the
+/// targets of the rules, each followed by a `\0`.
+///
+/// Each rule maps a source sequence to its target, which can be empty to remove the source.
+pub fn compile_charsmap<I, S, T>(rules: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = (S, T)>,
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    let mut keys = BTreeMap::new();
+    let mut offsets = HashMap::new();
+    let mut normalized = String::new();
+    for (source, target) in rules {
+        let (source, target) = (source.as_ref(), target.as_ref());
+        if source.is_empty() || source.contains('\0') || target.contains('\0') {
+            return Err(format!("Invalid normalization rule {:?} -> {:?}", source, target).into());
+        }
+        let offset = *offsets.entry(target.to_owned()).or_insert_with(|| {
+            let offset = normalized.len();
+            normalized.push_str(target);
+            normalized.push('\0');
+            offset
+        });
+        if keys
+            .insert(source.as_bytes().to_vec(), offset as u32)
+            .is_some()
+        {
+            return Err(format!("Duplicated normalization rule for {:?}", source).into());
+        }
+    }
+
+    let trie = DoubleArrayBuilder::build(&keys)?;
+    let mut charsmap = Vec::with_capacity(4 + trie.len() * 4 + normalized.len());
+    charsmap.extend_from_slice(&((trie.len() * 4) as u32).to_le_bytes());
+    for unit in trie {
+        charsmap.extend_from_slice(&unit.to_le_bytes());
+    }
+    charsmap.extend_from_slice(normalized.as_bytes());
+    Ok(charsmap)
+}
+
+/// Build a `Precompiled` normalizer from the given normalization rules
+pub fn from_rules<I, S, T>(rules: I) -> Result<Precompiled>
+where
+    I: IntoIterator<Item = (S, T)>,
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    Ok(Precompiled::from(&compile_charsmap(rules)?)?)
+}
+
+/// Read the normalization rules from a TSV file, in the format used by the
+/// `--normalization_rule_tsv` option of `spm_train`: each line has a source and a target,
+/// both given as space separated hexadecimal codepoints, like `FB01\t66 69`. Any other
+/// field is ignored, as well as the empty lines and those starting with `#`.
+pub fn read_rules_file<P: AsRef<Path>>(path: P) -> Result<Vec<(String, String)>> {
+    let file = BufReader::new(File::open(path)?);
+
+    let mut rules = vec![];
+    for (index, line) in file.lines().enumerate() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let err = || format!("Invalid normalization rule on line {}: {}", index + 1, line);
+        let codepoints = |field: &str| {
+            field
+                .split_whitespace()
+                .map(|c| std::char::from_u32(u32::from_str_radix(c, 16).ok()?))
+                .collect::<Option<String>>()
+        };
+        let mut fields = line.split('\t');
+        match (fields.next(), fields.next()) {
+            (Some(source), Some(target)) => rules.push((
+                codepoints(source).ok_or_else(err)?,
+                codepoints(target).ok_or_else(err)?,
+            )),
+            _ => return Err(err().into()),
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Build a `Precompiled` normalizer from a TSV file of normalization rules, as described
+/// in `read_rules_file`
+pub fn from_rules_file<P: AsRef<Path>>(path: P) -> Result<Precompiled> {
+    from_rules(read_rules_file(path)?)
+}
+
+/// Builds the double-array trie read by `Precompiled`, in the darts-clone format.
+///
+/// Each unit holds a `label` (the byte leading to it), whether it has a leaf, and an
+/// `offset` such that its children are at `position ^ offset ^ label`. The leaf of a node is
+/// its child with the label `0`, and holds the value of the key.
+struct DoubleArrayBuilder {
+    units: Vec<u32>,
+    used: Vec<bool>,
+    used_bases: HashSet<usize>,
+    first_free: usize,
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children: BTreeMap<u8, usize>,
+    value: Option<u32>,
+}
+
+impl DoubleArrayBuilder {
+    const BLOCK_SIZE: usize = 256;
+    const HAS_LEAF: u32 = 1 << 8;
+    const IS_LEAF: u32 = 1 << 31;
+
+    fn build(keys: &BTreeMap<Vec<u8>, u32>) -> Result<Vec<u32>> {
+        let mut nodes = vec![TrieNode::default()];
+        for (key, value) in keys {
+            let mut node = 0;
+            for byte in key {
+                node = match nodes[node].children.get(byte) {
+                    Some(child) => *child,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(*byte, child);
+                        child
+                    }
+                };
+            }
+            nodes[node].value = Some(*value);
+        }
+
+        let mut builder = DoubleArrayBuilder {
+            units: vec![],
+            used: vec![],
+            used_bases: HashSet::new(),
+            first_free: 1,
+        };
+        builder.reserve(0);
+        builder.used[0] = true;
+
+        let mut queue = VecDeque::new();
+        queue.push_back((0, 0));
+        while let Some((node, position)) = queue.pop_front() {
+            let node = &nodes[node];
+            let labels = node
+                .value
+                .map(|_| 0)
+                .into_iter()
+                .chain(node.children.keys().copied())
+                .collect::<Vec<_>>();
+            if labels.is_empty() {
+                continue;
+            }
+
+            let base = builder.find_base(position, &labels);
+            builder.used_bases.insert(base);
+            builder.set_offset(position, position ^ base)?;
+            if let Some(value) = node.value {
+                if value >= Self::IS_LEAF {
+                    return Err("The normalized rules are too large to be precompiled".into());
+                }
+                builder.units[position] |= Self::HAS_LEAF;
+                builder.units[base] = value | Self::IS_LEAF;
+                builder.used[base] = true;
+            }
+            for (label, child) in &node.children {
+                let child_position = base ^ *label as usize;
+                builder.units[child_position] |= *label as u32;
+                builder.used[child_position] = true;
+                queue.push_back((*child, child_position));
+            }
+        }
+
+        Ok(builder.units)
+    }
+
+    /// Make sure the arrays cover the block of the given position
+    fn reserve(&mut self, position: usize) {
+        let len = (position / Self::BLOCK_SIZE + 1) * Self::BLOCK_SIZE;
+        if self.units.len() < len {
+            self.units.resize(len, 0);
+            self.used.resize(len, false);
+        }
+    }
+
+    /// Find a base, not used by any other node, where all the given labels are free
+    fn find_base(&mut self, position: usize, labels: &[u8]) -> usize {
+        while self.first_free < self.used.len() && self.used[self.first_free] {
+            self.first_free += 1;
+        }
+
+        let mut free = self.first_free;
+        loop {
+            self.reserve(free);
+            if !self.used[free] {
+                let base = free ^ labels[0] as usize;
+                let offset = position ^ base;
+                let valid_offset = offset < 1 << 21 || (offset & 0xFF == 0 && offset < 1 << 29);
+                if valid_offset
+                    && !self.used_bases.contains(&base)
+                    && labels.iter().all(|l| !self.used[base ^ *l as usize])
+                {
+                    return base;
+                }
+            }
+            free += 1;
+        }
+    }
+
+    fn set_offset(&mut self, position: usize, offset: usize) -> Result<()> {
+        let offset = offset as u32;
+        if offset < 1 << 21 {
+            self.units[position] |= offset << 10;
+        } else if offset < 1 << 29 && offset & 0xFF == 0 {
+            self.units[position] |= (offset << 2) | (1 << 9);
+        } else {
+            return Err("The normalized rules are too large to be precompiled".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn compile_rules() {
+        let precompiled = from_rules(vec![
+            ("Ａ", "A"),
+            ("ﬁ", "fi"),
+            ("e\u{301}", "é"),
+            ("AB", "x"),
+            ("\u{200B}", ""),
+        ])
+        .unwrap();
+
+        assert_eq!(precompiled.transform("Ａ"), Some("A"));
+        assert_eq!(precompiled.transform("ﬁ"), Some("fi"));
+        assert_eq!(precompiled.transform("e\u{301}"), Some("é"));
+        assert_eq!(precompiled.transform("AB"), Some("x"));
+        assert_eq!(precompiled.transform("\u{200B}"), Some(""));
+        assert_eq!(precompiled.transform("A"), None);
+        assert_eq!(precompiled.transform("e"), None);
+        assert_eq!(precompiled.transform("z"), None);
+
+        let mut n = NormalizedString::from("Ａ ﬁle");
+        precompiled.normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "A file");
+
+        // It survives a round trip through serialization
+        let serialized = serde_json::to_string(&precompiled).unwrap();
+        let deserialized: Precompiled = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.transform("ﬁ"), Some("fi"));
+
+        assert!(from_rules(vec![("", "a")]).is_err());
+        assert!(from_rules(vec![("a", "b"), ("a", "c")]).is_err());
+    }
+
+    #[test]
+    fn compile_many_rules() {
+        let rules = (0x100..0x2000)
+            .filter_map(std::char::from_u32)
+            .map(|c| (c.to_string(), format!("<{:x}>", c as u32)))
+            .chain((b'a'..=b'z').map(|c| (format!("{}{}", c as char, c as char), "x".into())))
+            .collect::<Vec<_>>();
+        let precompiled = from_rules(rules.clone()).unwrap();
+        for (source, target) in &rules {
+            assert_eq!(precompiled.transform(source), Some(target.as_str()));
+        }
+        assert_eq!(precompiled.transform("a"), None);
+        assert_eq!(precompiled.transform("\u{2000}"), None);
+    }
+
+    #[test]
+    fn rules_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"# Some rules\nFF21\t41\t# Full-width A\nFB01\t66 69\n\n200B\t\n")
+            .unwrap();
+
+        let rules = read_rules_file(file.path()).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ("Ａ".into(), "A".into()),
+                ("ﬁ".into(), "fi".into()),
+                ("\u{200B}".into(), "".into())
+            ]
+        );
+        let precompiled = from_rules_file(file.path()).unwrap();
+        assert_eq!(precompiled.transform("ﬁ"), Some("fi"));
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"FB01\n").unwrap();
+        assert!(read_rules_file(file.path()).is_err());
+    }
+}
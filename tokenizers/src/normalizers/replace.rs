@@ -1,5 +1,5 @@
-use crate::tokenizer::{NormalizedString, Normalizer, Result};
-use onig::Regex;
+use crate::tokenizer::{Decoder, NormalizedString, Normalizer, ReplacementPiece, Result};
+use onig::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 
 /// Represents the different patterns that `Replace` can use
@@ -28,64 +28,203 @@ impl From<&str> for ReplacePattern {
 struct ReplaceDeserializer {
     pattern: ReplacePattern,
     content: String,
+    #[serde(default)]
+    captures: bool,
 }
 
 impl std::convert::TryFrom<ReplaceDeserializer> for Replace {
     type Error = Box<dyn std::error::Error + Send + Sync>;
 
     fn try_from(v: ReplaceDeserializer) -> Result<Self> {
-        Replace::new(v.pattern, v.content)
+        Replace::build(v.pattern, v.content, v.captures)
     }
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 /// This normalizer will take a `pattern` (for now only a String)
 /// and replace every occurrence with `content`.
+/// It can also be used as a decoder, in which case the replacement happens in each token.
+///
+/// When built `with_captures` and a regex `pattern`, the `content` can reference the capture
+/// groups with `$1` or `${1}`, and `$$` stands for a literal `$`. The characters of the capture
+/// groups then keep their alignments with the original string. Otherwise, the `content` is
+/// always used literally.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", try_from = "ReplaceDeserializer")]
 pub struct Replace {
     pattern: ReplacePattern,
     content: String,
+    #[serde(skip_serializing_if = "is_false")]
+    captures: bool,
     #[serde(skip)]
     regex: Regex,
+    #[serde(skip)]
+    pieces: Option<Vec<ReplacementPiece>>,
 }
 
 impl Clone for Replace {
     fn clone(&self) -> Self {
-        Replace::new(self.pattern.clone(), &self.content).unwrap()
+        Replace::build(self.pattern.clone(), &self.content, self.captures).unwrap()
     }
 }
 
 impl PartialEq for Replace {
     fn eq(&self, other: &Replace) -> bool {
-        self.pattern == other.pattern && self.content == other.content
+        self.pattern == other.pattern
+            && self.content == other.content
+            && self.captures == other.captures
     }
 }
 
 impl Replace {
+    /// Replace each occurrence of `pattern` with the literal `content`
     pub fn new<I: Into<ReplacePattern>, C: Into<String>>(pattern: I, content: C) -> Result<Self> {
+        Self::build(pattern, content, false)
+    }
+
+    /// Replace each occurrence of the regex `pattern` with `content`, in which `$1` or `${1}`
+    /// reference the capture groups, and `$$` stands for a literal `$`
+    pub fn with_captures<I: Into<ReplacePattern>, C: Into<String>>(
+        pattern: I,
+        content: C,
+    ) -> Result<Self> {
+        Self::build(pattern, content, true)
+    }
+
+    fn build<I: Into<ReplacePattern>, C: Into<String>>(
+        pattern: I,
+        content: C,
+        captures: bool,
+    ) -> Result<Self> {
         let pattern: ReplacePattern = pattern.into();
         let regex = match &pattern {
             ReplacePattern::String(s) => Regex::new(&regex::escape(s))?,
             ReplacePattern::Regex(r) => Regex::new(r)?,
         };
 
+        let content = content.into();
+        let pieces = match &pattern {
+            ReplacePattern::Regex(_) if captures && content.contains('$') => {
+                Some(parse_captures(&content, regex.captures_len())?)
+            }
+            _ => None,
+        };
+
         Ok(Self {
             pattern,
-            content: content.into(),
+            content,
+            captures,
             regex,
+            pieces,
         })
     }
+
+    /// Build the replacement of the given match, substituting the capture groups
+    fn expand(pieces: &[ReplacementPiece], captures: &Captures) -> String {
+        pieces
+            .iter()
+            .map(|piece| match piece {
+                ReplacementPiece::Text(text) => text.as_str(),
+                ReplacementPiece::Group(group) => captures.at(*group).unwrap_or(""),
+            })
+            .collect()
+    }
+}
+
+/// Parse the content of a `Replace` into literal text and references to the capture groups
+fn parse_captures(content: &str, n_groups: usize) -> Result<Vec<ReplacementPiece>> {
+    let mut pieces = vec![];
+    let mut text = String::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            text.push(c);
+            continue;
+        }
+
+        let group = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                text.push('$');
+                continue;
+            }
+            Some('{') => {
+                chars.next();
+                let group = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                group.parse::<usize>().ok()
+            }
+            _ => {
+                let mut group = String::new();
+                while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    group.push(*d);
+                    chars.next();
+                }
+                group.parse::<usize>().ok()
+            }
+        };
+        match group {
+            Some(group) if group <= n_groups => {
+                if !text.is_empty() {
+                    pieces.push(ReplacementPiece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(ReplacementPiece::Group(group));
+            }
+            Some(group) => {
+                return Err(
+                    format!("Replace content references an unknown group ${}", group).into(),
+                )
+            }
+            None => {
+                return Err(
+                    format!("Invalid group reference in Replace content {}", content).into(),
+                )
+            }
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(ReplacementPiece::Text(text));
+    }
+
+    Ok(pieces)
 }
 
 impl Normalizer for Replace {
     fn normalize(&self, normalized: &mut NormalizedString) -> Result<()> {
-        normalized.replace(&self.regex, &self.content)
+        match &self.pieces {
+            Some(pieces) => {
+                normalized.replace_captures(&self.regex, pieces);
+                Ok(())
+            }
+            None => normalized.replace(&self.regex, &self.content),
+        }
+    }
+}
+
+impl Decoder for Replace {
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        Ok(self.decode_chain(tokens)?.join(""))
+    }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(tokens
+            .iter()
+            .map(|token| match &self.pieces {
+                Some(pieces) => self
+                    .regex
+                    .replace_all(token, |captures: &Captures| Self::expand(pieces, captures)),
+                None => self.regex.replace_all(token, self.content.as_str()),
+            })
+            .collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tokenizer::normalizer::Range;
 
     #[test]
     fn test_replace() {
@@ -112,6 +251,67 @@ mod tests {
         assert_eq!(&n.get(), &normalized);
     }
 
+    #[test]
+    fn test_replace_captures() {
+        let original = "Hello   friend, call 555-1234";
+        let normalized = "Hello friend, call (555) 1234";
+
+        let mut n = NormalizedString::from(original);
+        Replace::with_captures(ReplacePattern::Regex(r"(\s)\s+".into()), "$1")
+            .unwrap()
+            .normalize(&mut n)
+            .unwrap();
+        Replace::with_captures(ReplacePattern::Regex(r"(\d+)-(\d+)".into()), "(${1}) $2")
+            .unwrap()
+            .normalize(&mut n)
+            .unwrap();
+
+        assert_eq!(&n.get(), &normalized);
+        assert_eq!(
+            n.get_range_original(Range::Normalized(6..12)),
+            Some("friend")
+        );
+        assert_eq!(n.get_range_original(Range::Normalized(20..23)), Some("555"));
+        assert_eq!(
+            n.get_range_original(Range::Normalized(25..29)),
+            Some("1234")
+        );
+
+        // With a string pattern, the content is always literal
+        let mut n = NormalizedString::from("a-b");
+        Replace::with_captures("-", "$1")
+            .unwrap()
+            .normalize(&mut n)
+            .unwrap();
+        assert_eq!(&n.get(), &"a$1b");
+
+        let mut n = NormalizedString::from("cost: 5");
+        Replace::with_captures(ReplacePattern::Regex(r"(\d)".into()), "$$$1")
+            .unwrap()
+            .normalize(&mut n)
+            .unwrap();
+        assert_eq!(&n.get(), &"cost: $5");
+
+        assert!(Replace::with_captures(ReplacePattern::Regex(r"(\d)".into()), "$2").is_err());
+        assert!(Replace::with_captures(ReplacePattern::Regex(r"(\d)".into()), "${a}").is_err());
+
+        let decoder =
+            Replace::with_captures(ReplacePattern::Regex(r"▁(\w)".into()), " $1").unwrap();
+        let res = decoder
+            .decode_chain(vec!["▁Hey".into(), "▁▁".into()])
+            .unwrap();
+        assert_eq!(res, vec![" Hey", "▁▁"]);
+    }
+
+    #[test]
+    fn test_replace_decode() {
+        let decoder = Replace::new("▁", " ").unwrap();
+        let res = decoder
+            .decode_chain(vec!["▁Hey".into(), "▁friend!".into(), "▁▁".into()])
+            .unwrap();
+        assert_eq!(res, vec![" Hey", " friend!", "  "]);
+    }
+
     #[test]
     fn serialization() {
         let replace = Replace::new("Hello", "Hey").unwrap();
@@ -123,5 +323,35 @@ mod tests {
         let replace_s = r#"{"type":"Replace","pattern":{"Regex":"\\s+"},"content":" "}"#;
         assert_eq!(serde_json::to_string(&replace).unwrap(), replace_s);
         assert_eq!(serde_json::from_str::<Replace>(replace_s).unwrap(), replace);
+
+        let replace = Replace::with_captures(ReplacePattern::Regex(r"(\d)".into()), "$1").unwrap();
+        let replace_s =
+            r#"{"type":"Replace","pattern":{"Regex":"(\\d)"},"content":"$1","captures":true}"#;
+        assert_eq!(serde_json::to_string(&replace).unwrap(), replace_s);
+        assert_eq!(serde_json::from_str::<Replace>(replace_s).unwrap(), replace);
+    }
+
+    #[test]
+    fn literal_dollar_from_older_configs() {
+        // Configs serialized before the captures existed use `$` literally
+        let replace_s = r#"{"type":"Replace","pattern":{"Regex":"USD ?(\\d)"},"content":"US$"}"#;
+        let replace = serde_json::from_str::<Replace>(replace_s).unwrap();
+        assert_eq!(
+            replace,
+            Replace::new(ReplacePattern::Regex(r"USD ?(\d)".into()), "US$").unwrap()
+        );
+        assert_eq!(serde_json::to_string(&replace).unwrap(), replace_s);
+
+        let mut n = NormalizedString::from("USD 5, $2");
+        replace.normalize(&mut n).unwrap();
+        assert_eq!(&n.get(), &"US$, $2");
+
+        let replace_s = r#"{"type":"Replace","pattern":{"Regex":"\\d"},"content":"$"}"#;
+        let mut n = NormalizedString::from("a1b");
+        serde_json::from_str::<Replace>(replace_s)
+            .unwrap()
+            .normalize(&mut n)
+            .unwrap();
+        assert_eq!(&n.get(), &"a$b");
     }
 }
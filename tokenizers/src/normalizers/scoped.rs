@@ -0,0 +1,191 @@
+use crate::normalizers::replace::ReplacePattern;
+use crate::normalizers::NormalizerWrapper;
+use crate::pre_tokenizers::unicode_scripts::{get_script, Script};
+use crate::tokenizer::normalizer::Range;
+use crate::tokenizer::pattern::Pattern;
+use crate::tokenizer::{NormalizedString, Normalizer, Result};
+use onig::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Represents the parts of the string a `Scoped` normalizer applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Scope {
+    /// The spans of characters that belong to one of these scripts. The combining marks, of
+    /// the `Inherited` script, belong to the script of the character they follow.
+    Scripts(Vec<Script>),
+    /// The spans matching this pattern
+    Pattern(ReplacePattern),
+}
+
+impl Scope {
+    /// Find the ranges of the given string that are in this scope
+    fn find_ranges(
+        &self,
+        regex: Option<&Regex>,
+        normalized: &str,
+    ) -> Result<Vec<std::ops::Range<usize>>> {
+        match (self, regex) {
+            (Scope::Pattern(_), Some(regex)) => Ok(regex
+                .find_matches(normalized)?
+                .into_iter()
+                .filter(|((start, end), is_match)| *is_match && start < end)
+                .map(|((start, end), _)| start..end)
+                .collect()),
+            (Scope::Pattern(_), None) => Err("Scoped: missing regex".into()),
+            (Scope::Scripts(scripts), _) => {
+                let mut ranges: Vec<std::ops::Range<usize>> = vec![];
+                let mut last_script = None;
+                for (i, c) in normalized.char_indices() {
+                    let script = match get_script(c) {
+                        Script::Inherited => last_script.unwrap_or(Script::Inherited),
+                        script => script,
+                    };
+                    last_script = Some(script);
+
+                    if scripts.contains(&script) {
+                        match ranges.last_mut() {
+                            Some(range) if range.end == i => range.end = i + c.len_utf8(),
+                            _ => ranges.push(i..i + c.len_utf8()),
+                        }
+                    }
+                }
+                Ok(ranges)
+            }
+        }
+    }
+}
+
+/// We use this custom deserializer to provide the value for `regex` for `Scoped`
+#[doc(hidden)]
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+struct ScopedDeserializer {
+    scope: Scope,
+    normalizer: Box<NormalizerWrapper>,
+}
+
+impl std::convert::TryFrom<ScopedDeserializer> for Scoped {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(v: ScopedDeserializer) -> Result<Self> {
+        Scoped::new(v.scope, *v.normalizer)
+    }
+}
+
+/// Applies the inner normalizer only to the parts of the string selected by its `Scope`,
+/// like some scripts or the spans matching a pattern. The rest of the string is left as is.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", try_from = "ScopedDeserializer")]
+pub struct Scoped {
+    scope: Scope,
+    normalizer: Box<NormalizerWrapper>,
+    #[serde(skip)]
+    regex: Option<Regex>,
+}
+
+impl Clone for Scoped {
+    fn clone(&self) -> Self {
+        Scoped::new(self.scope.clone(), (*self.normalizer).clone()).unwrap()
+    }
+}
+
+impl Scoped {
+    pub fn new<N: Into<NormalizerWrapper>>(scope: Scope, normalizer: N) -> Result<Self> {
+        let regex = match &scope {
+            Scope::Pattern(ReplacePattern::String(s)) => Some(Regex::new(&regex::escape(s))?),
+            Scope::Pattern(ReplacePattern::Regex(r)) => Some(Regex::new(r)?),
+            Scope::Scripts(_) => None,
+        };
+
+        Ok(Self {
+            scope,
+            normalizer: Box::new(normalizer.into()),
+            regex,
+        })
+    }
+
+    pub fn get_scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    pub fn get_normalizer(&self) -> &NormalizerWrapper {
+        &self.normalizer
+    }
+}
+
+impl Normalizer for Scoped {
+    fn normalize(&self, normalized: &mut NormalizedString) -> Result<()> {
+        let ranges = self
+            .scope
+            .find_ranges(self.regex.as_ref(), normalized.get())?;
+        // We start from the end, to keep the ranges valid while normalizing them
+        for range in ranges.into_iter().rev() {
+            normalized.map_range(Range::Normalized(range), |n| self.normalizer.normalize(n))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalizers::{Lowercase, Sequence, StripAccents, NFD, NFKC};
+
+    #[test]
+    fn scripts() {
+        let normalizer = Scoped::new(
+            Scope::Scripts(vec![Script::Latin, Script::Cyrillic]),
+            Sequence::new(vec![NFD.into(), StripAccents.into(), Lowercase.into()]),
+        )
+        .unwrap();
+
+        let mut n = NormalizedString::from("Élan ПРИВЕТ ＡＢ 東京 Ｘ");
+        normalizer.normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "elan привет ａｂ 東京 ｘ");
+        assert_eq!(n.get_range_original(Range::Normalized(0..1)), Some("É"));
+        assert_eq!(n.get_range_original(Range::Normalized(1..4)), Some("lan"));
+    }
+
+    #[test]
+    fn pattern() {
+        let normalizer = Scoped::new(
+            Scope::Pattern(ReplacePattern::Regex(r"`[^`]*`".into())),
+            Lowercase,
+        )
+        .unwrap();
+
+        let mut n = NormalizedString::from("Keep `THIS` and `THAT` But Not This");
+        normalizer.normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "Keep `this` and `that` But Not This");
+
+        let normalizer =
+            Scoped::new(Scope::Pattern(ReplacePattern::String("ＡＢ".into())), NFKC).unwrap();
+        let mut n = NormalizedString::from("ＡＢ ＣＤ ＡＢ");
+        normalizer.normalize(&mut n).unwrap();
+        assert_eq!(n.get(), "AB ＣＤ AB");
+        assert_eq!(
+            n.get_range_original(Range::Normalized(10..12)),
+            Some("ＡＢ")
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        let normalizer = Scoped::new(Scope::Scripts(vec![Script::Latin]), Lowercase).unwrap();
+        let normalizer_s =
+            r#"{"type":"Scoped","scope":{"Scripts":["Latin"]},"normalizer":{"type":"Lowercase"}}"#;
+        assert_eq!(serde_json::to_string(&normalizer).unwrap(), normalizer_s);
+        let deserialized: NormalizerWrapper = serde_json::from_str(normalizer_s).unwrap();
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), normalizer_s);
+
+        let normalizer = Scoped::new(
+            Scope::Pattern(ReplacePattern::Regex(r"\d+".into())),
+            Lowercase,
+        )
+        .unwrap();
+        let normalizer_s = r#"{"type":"Scoped","scope":{"Pattern":{"Regex":"\\d+"}},"normalizer":{"type":"Lowercase"}}"#;
+        assert_eq!(serde_json::to_string(&normalizer).unwrap(), normalizer_s);
+        let deserialized: Scoped = serde_json::from_str(normalizer_s).unwrap();
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), normalizer_s);
+    }
+}
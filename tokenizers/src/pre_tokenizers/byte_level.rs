@@ -82,6 +82,20 @@ impl ByteLevel {
         self.trim_offsets = v;
         self
     }
+
+    /// Trim the offsets of each given encoding and of their overflowings if necessary,
+    /// without merging them
+    pub fn process_encodings(&self, encodings: &mut [Encoding]) {
+        if self.trim_offsets {
+            for encoding in encodings.iter_mut() {
+                process_offsets(encoding, self.add_prefix_space);
+                encoding
+                    .get_overflowing_mut()
+                    .iter_mut()
+                    .for_each(|mut encoding| process_offsets(&mut encoding, self.add_prefix_space));
+            }
+        }
+    }
 }
 
 /// As a `PreTokenizer`, `ByteLevel` is in charge of transforming all the unicode characters into
@@ -168,6 +182,15 @@ impl PostProcessor for ByteLevel {
 
         PostProcessor::default_process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn process_multi(
+        &self,
+        mut encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        self.process_encodings(&mut encodings);
+        PostProcessor::default_process_multi(encodings, add_special_tokens)
+    }
 }
 
 pub fn process_offsets(encoding: &mut Encoding, add_prefix_space: bool) {
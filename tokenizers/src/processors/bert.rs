@@ -81,7 +81,7 @@ impl PostProcessor for BertProcessing {
                         [&[1u32], &vec![0; encoding.get_ids().len()][..], &[1]].concat();
                     let attention_mask = vec![1; ids.len()];
 
-                    Encoding::new(
+                    let mut overflowing = Encoding::new(
                         ids,
                         type_ids,
                         tokens,
@@ -90,10 +90,13 @@ impl PostProcessor for BertProcessing {
                         special_tokens,
                         attention_mask,
                         vec![],
-                    )
+                    );
+                    overflowing.add_windows_from(&encoding, 1);
+                    overflowing
                 })
                 .collect(),
         );
+        new_encoding.add_windows_from(&encoding, 1);
 
         if let Some(mut encoding) = pair_encoding {
             let pair_ids = [&encoding.get_ids()[..], &[self.sep.1]].concat();
@@ -105,7 +108,7 @@ impl PostProcessor for BertProcessing {
                 [&vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
             let pair_attention_mask = vec![1; pair_ids.len()];
 
-            let new_pair_encoding = Encoding::new(
+            let mut new_pair_encoding = Encoding::new(
                 pair_ids,
                 pair_type_ids,
                 pair_tokens,
@@ -127,7 +130,7 @@ impl PostProcessor for BertProcessing {
                             [&vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
                         let pair_attention_mask = vec![1; pair_ids.len()];
 
-                        Encoding::new(
+                        let mut overflowing = Encoding::new(
                             pair_ids,
                             pair_type_ids,
                             pair_tokens,
@@ -136,10 +139,13 @@ impl PostProcessor for BertProcessing {
                             pair_special_tokens,
                             pair_attention_mask,
                             vec![],
-                        )
+                        );
+                        overflowing.add_windows_from(&encoding, 0);
+                        overflowing
                     })
                     .collect(),
             );
+            new_pair_encoding.add_windows_from(&encoding, 0);
 
             new_encoding.merge_with(new_pair_encoding, false);
         }
@@ -0,0 +1,342 @@
+//! # Chat Templating
+//!
+//! Provides a way to format a conversation, made of a list of `(role, content)` messages,
+//! before encoding it. Each role has its own [`Template`], using `$A` to represent the
+//! content of the message, and the special tokens delimiting the turn:
+//! ```
+//! # use tokenizers::processors::chat::ChatTemplate;
+//! let template = ChatTemplate::builder()
+//!     .try_prefix("<s>").unwrap()
+//!     .try_role("system", "<|system|> $A <|end|>", false).unwrap()
+//!     .try_role("user", "<|user|> $A <|end|>", false).unwrap()
+//!     // The tokens of the assistant turns are marked in the mask
+//!     .try_role("assistant", "<|assistant|> $A:1 <|end|>:1", true).unwrap()
+//!     .try_generation_prompt("<|assistant|>:1").unwrap()
+//!     .special_tokens(vec![
+//!         ("<s>", 0),
+//!         ("<|end|>", 1),
+//!         ("<|system|>", 2),
+//!         ("<|user|>", 3),
+//!         ("<|assistant|>", 4),
+//!     ])
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! The conversation is then encoded with `Tokenizer::encode_chat`, which returns the
+//! `Encoding` along with a mask marking the tokens that belong to the turns of the masked
+//! roles with `1`. For each masked turn, these are the tokens of the content, and the special
+//! tokens following it in the template, so that a model learns when to end its turn.
+//!
+//! [`Template`]: ../template/struct.Template.html
+//!
+use crate::processors::template::{apply_template, Piece, Template, Tokens};
+use crate::{Encoding, Result};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::result::Result as StdResult;
+
+/// The template used for the messages of one role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatRole {
+    /// The template of each message, where `$A` represents its content
+    template: Template,
+    /// Whether the tokens of the messages of this role are marked in the mask
+    #[serde(default)]
+    mask: bool,
+}
+
+impl ChatRole {
+    pub fn new(template: Template, mask: bool) -> Self {
+        Self { template, mask }
+    }
+
+    /// Build the mask of a message of this role, with the given content length
+    fn message_mask(&self, content_len: usize, special_tokens: &Tokens) -> Vec<u32> {
+        let mut mask = vec![];
+        let mut after_content = false;
+        for piece in &self.template.0 {
+            let len = match piece {
+                Piece::Sequence { .. } => {
+                    after_content = true;
+                    content_len
+                }
+                Piece::SpecialToken { id, .. } => special_tokens.0.get(id).map_or(0, |t| t.len()),
+            };
+            let value = if self.mask && after_content { 1 } else { 0 };
+            mask.extend(std::iter::repeat(value).take(len));
+        }
+        mask
+    }
+}
+
+/// Formats conversations, applying the template of the role of each message, and
+/// keeps track of the tokens belonging to the masked roles.
+#[derive(Debug, Clone, PartialEq, Builder, Serialize, Deserialize)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct ChatTemplate {
+    /// Added once at the beginning of the conversation
+    #[builder(try_setter, default)]
+    #[serde(default)]
+    prefix: Template,
+    /// The template of each role
+    #[builder(setter(custom), default)]
+    #[serde(serialize_with = "crate::utils::ordered_map")]
+    roles: HashMap<String, ChatRole>,
+    /// Added at the end of the conversation, when asked to prompt for a new message
+    #[builder(setter(custom), default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    generation_prompt: Option<Template>,
+    #[builder(setter(into), default)]
+    special_tokens: Tokens,
+}
+
+impl ChatTemplateBuilder {
+    /// Set the template used for the messages of the given role
+    pub fn role(&mut self, role: impl Into<String>, template: Template, mask: bool) -> &mut Self {
+        self.roles
+            .get_or_insert_with(HashMap::new)
+            .insert(role.into(), ChatRole::new(template, mask));
+        self
+    }
+
+    /// Same as `role`, but building the template from anything convertible
+    pub fn try_role<T>(
+        &mut self,
+        role: impl Into<String>,
+        template: T,
+        mask: bool,
+    ) -> StdResult<&mut Self, String>
+    where
+        T: TryInto<Template, Error = String>,
+    {
+        Ok(self.role(role, template.try_into()?, mask))
+    }
+
+    /// Set the template added when prompting for a new message
+    pub fn generation_prompt(&mut self, template: Template) -> &mut Self {
+        self.generation_prompt = Some(Some(template));
+        self
+    }
+
+    /// Same as `generation_prompt`, but building the template from anything convertible
+    pub fn try_generation_prompt<T>(&mut self, template: T) -> StdResult<&mut Self, String>
+    where
+        T: TryInto<Template, Error = String>,
+    {
+        Ok(self.generation_prompt(template.try_into()?))
+    }
+
+    fn validate(&self) -> StdResult<(), String> {
+        let roles = self.roles.iter().flatten();
+        for (role, chat_role) in roles.clone() {
+            let template = &chat_role.template;
+            if template.n_sequences() != 1 {
+                return Err(format!(
+                    "Template for role `{}` must use the content `$A` only",
+                    role
+                ));
+            }
+        }
+
+        let empty = Template::default();
+        let prefix = self.prefix.as_ref().unwrap_or(&empty);
+        let generation_prompt = self.generation_prompt.iter().flatten();
+        if prefix
+            .0
+            .iter()
+            .chain(generation_prompt.clone().flat_map(|t| t.0.iter()))
+            .any(|piece| matches!(piece, Piece::Sequence { .. }))
+        {
+            return Err("Templates for `prefix` and `generation_prompt` can't use `$A`".into());
+        }
+
+        let missing: HashSet<&str> = prefix
+            .0
+            .iter()
+            .chain(generation_prompt.flat_map(|t| t.0.iter()))
+            .chain(roles.flat_map(|(_, r)| r.template.0.iter()))
+            .filter_map(|piece| match piece {
+                Piece::Sequence { .. } => None,
+                Piece::SpecialToken { id, .. } => {
+                    let exist = self
+                        .special_tokens
+                        .as_ref()
+                        .map_or(false, |map| map.0.contains_key(id));
+                    if exist {
+                        None
+                    } else {
+                        Some(id.as_ref())
+                    }
+                }
+            })
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Missing SpecialToken(s) with id(s) `{}`",
+                missing.iter().sorted().join(", ")
+            ))
+        }
+    }
+}
+
+impl ChatTemplate {
+    pub fn builder() -> ChatTemplateBuilder {
+        ChatTemplateBuilder::default()
+    }
+
+    /// Format the conversation made of the given messages, already encoded, with their role.
+    /// Returns the final `Encoding`, with the mask marking the tokens of the masked roles.
+    pub fn apply<R: AsRef<str>>(
+        &self,
+        messages: Vec<(R, Encoding)>,
+        add_generation_prompt: bool,
+    ) -> Result<(Encoding, Vec<u32>)> {
+        let mut encoding = apply_template(&self.prefix.0, &self.special_tokens, vec![], true)?;
+        let mut mask = vec![0; encoding.len()];
+
+        for (role, content) in messages {
+            let role = role.as_ref();
+            let chat_role = self
+                .roles
+                .get(role)
+                .ok_or_else(|| format!("Chat template has no role `{}`", role))?;
+
+            mask.extend(chat_role.message_mask(content.len(), &self.special_tokens));
+            encoding.merge_with(
+                apply_template(
+                    &chat_role.template.0,
+                    &self.special_tokens,
+                    vec![content],
+                    true,
+                )?,
+                false,
+            );
+        }
+
+        if add_generation_prompt {
+            let prompt = self
+                .generation_prompt
+                .as_ref()
+                .ok_or("Chat template has no generation prompt")?;
+            let prompt = apply_template(&prompt.0, &self.special_tokens, vec![], true)?;
+            mask.extend(std::iter::repeat(0).take(prompt.len()));
+            encoding.merge_with(prompt, false);
+        }
+
+        Ok((encoding, mask))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Token;
+
+    fn get_template() -> ChatTemplate {
+        ChatTemplate::builder()
+            .try_prefix("<s>")
+            .unwrap()
+            .try_role("user", "<|user|> $A <|end|>", false)
+            .unwrap()
+            .try_role("assistant", "<|assistant|> $A:1 <|end|>:1", true)
+            .unwrap()
+            .try_generation_prompt("<|assistant|>:1")
+            .unwrap()
+            .special_tokens(vec![
+                ("<s>", 0),
+                ("<|end|>", 1),
+                ("<|user|>", 3),
+                ("<|assistant|>", 4),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn apply() {
+        let template = get_template();
+        let messages = vec![
+            (
+                "user",
+                Encoding::from_tokens(
+                    vec![
+                        Token::new(12, "Hello".into(), (0, 5)),
+                        Token::new(13, "there".into(), (6, 11)),
+                    ],
+                    0,
+                ),
+            ),
+            (
+                "assistant",
+                Encoding::from_tokens(vec![Token::new(14, "Hi".into(), (0, 2))], 0),
+            ),
+            (
+                "user",
+                Encoding::from_tokens(vec![Token::new(15, "Bye".into(), (0, 3))], 0),
+            ),
+        ];
+
+        let (encoding, mask) = template.apply(messages.clone(), false).unwrap();
+        assert_eq!(encoding.get_ids(), &[0, 3, 12, 13, 1, 4, 14, 1, 3, 15, 1]);
+        assert_eq!(encoding.get_type_ids(), &[0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0]);
+        assert_eq!(mask, vec![0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0]);
+        assert_eq!(
+            encoding.get_special_tokens_mask(),
+            &[1, 1, 0, 0, 1, 1, 0, 1, 1, 0, 1]
+        );
+
+        let (encoding, mask) = template.apply(messages, true).unwrap();
+        assert_eq!(encoding.get_ids().last(), Some(&4));
+        assert_eq!(mask.len(), encoding.len());
+        assert_eq!(mask.last(), Some(&0));
+
+        let unknown = vec![("system", Encoding::default())];
+        assert!(template.apply(unknown, false).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let missing = ChatTemplate::builder()
+            .try_role("user", "<|user|> $A <|end|>", false)
+            .unwrap()
+            .build();
+        assert_eq!(
+            missing,
+            Err("Missing SpecialToken(s) with id(s) `<|end|>, <|user|>`".into())
+        );
+
+        let pair = ChatTemplate::builder()
+            .try_role("user", "$A $B", false)
+            .unwrap()
+            .build();
+        assert_eq!(
+            pair,
+            Err("Template for role `user` must use the content `$A` only".into())
+        );
+
+        let prefix = ChatTemplate::builder().try_prefix("$A").unwrap().build();
+        assert_eq!(
+            prefix,
+            Err("Templates for `prefix` and `generation_prompt` can't use `$A`".into())
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let template = get_template();
+        let serialized = serde_json::to_string(&template).unwrap();
+        assert!(serialized.starts_with(
+            r#"{"prefix":[{"SpecialToken":{"id":"<s>","type_id":0}}],"roles":{"assistant":{"template":"#
+        ));
+        assert_eq!(
+            serde_json::from_str::<ChatTemplate>(&serialized).unwrap(),
+            template
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod bert;
+pub mod chat;
 pub mod roberta;
+pub mod sequence;
 pub mod template;
 
 // Re-export these as processors
@@ -10,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::pre_tokenizers::byte_level::ByteLevel;
 use crate::processors::bert::BertProcessing;
 use crate::processors::roberta::RobertaProcessing;
+use crate::processors::sequence::Sequence;
 use crate::processors::template::TemplateProcessing;
 use crate::{Encoding, PostProcessor, Result};
 
@@ -21,6 +24,7 @@ pub enum PostProcessorWrapper {
     Bert(BertProcessing),
     ByteLevel(ByteLevel),
     Template(TemplateProcessing),
+    Sequence(Sequence),
 }
 
 impl PostProcessor for PostProcessorWrapper {
@@ -30,6 +34,7 @@ impl PostProcessor for PostProcessorWrapper {
             PostProcessorWrapper::ByteLevel(bl) => bl.added_tokens(is_pair),
             PostProcessorWrapper::Roberta(roberta) => roberta.added_tokens(is_pair),
             PostProcessorWrapper::Template(template) => template.added_tokens(is_pair),
+            PostProcessorWrapper::Sequence(seq) => seq.added_tokens(is_pair),
         }
     }
 
@@ -52,6 +57,37 @@ impl PostProcessor for PostProcessorWrapper {
             PostProcessorWrapper::Template(template) => {
                 template.process(encoding, pair_encoding, add_special_tokens)
             }
+            PostProcessorWrapper::Sequence(seq) => {
+                seq.process(encoding, pair_encoding, add_special_tokens)
+            }
+        }
+    }
+
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        match self {
+            PostProcessorWrapper::Bert(bert) => bert.added_tokens_multi(n_sequences),
+            PostProcessorWrapper::ByteLevel(bl) => bl.added_tokens_multi(n_sequences),
+            PostProcessorWrapper::Roberta(roberta) => roberta.added_tokens_multi(n_sequences),
+            PostProcessorWrapper::Template(template) => template.added_tokens_multi(n_sequences),
+            PostProcessorWrapper::Sequence(seq) => seq.added_tokens_multi(n_sequences),
+        }
+    }
+
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        match self {
+            PostProcessorWrapper::Bert(bert) => bert.process_multi(encodings, add_special_tokens),
+            PostProcessorWrapper::ByteLevel(bl) => bl.process_multi(encodings, add_special_tokens),
+            PostProcessorWrapper::Roberta(roberta) => {
+                roberta.process_multi(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::Template(template) => {
+                template.process_multi(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::Sequence(seq) => seq.process_multi(encodings, add_special_tokens),
         }
     }
 }
@@ -60,6 +96,7 @@ impl_enum_from!(BertProcessing, PostProcessorWrapper, Bert);
 impl_enum_from!(ByteLevel, PostProcessorWrapper, ByteLevel);
 impl_enum_from!(RobertaProcessing, PostProcessorWrapper, Roberta);
 impl_enum_from!(TemplateProcessing, PostProcessorWrapper, Template);
+impl_enum_from!(Sequence, PostProcessorWrapper, Sequence);
 
 #[cfg(test)]
 mod tests {
@@ -114,7 +114,7 @@ impl PostProcessor for RobertaProcessing {
                         [&[1u32], &vec![0; encoding.get_ids().len()][..], &[1]].concat();
                     let attention_mask = vec![1; ids.len()];
 
-                    Encoding::new(
+                    let mut overflowing = Encoding::new(
                         ids,
                         type_ids,
                         tokens,
@@ -123,10 +123,13 @@ impl PostProcessor for RobertaProcessing {
                         special_tokens,
                         attention_mask,
                         vec![],
-                    )
+                    );
+                    overflowing.add_windows_from(&encoding, 1);
+                    overflowing
                 })
                 .collect(),
         );
+        new_encoding.add_windows_from(&encoding, 1);
 
         if let Some(mut encoding) = pair_encoding {
             let pair_ids = [&[self.sep.1], &encoding.get_ids()[..], &[self.sep.1]].concat();
@@ -143,7 +146,7 @@ impl PostProcessor for RobertaProcessing {
                 [&[1], &vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
             let pair_attention_mask = vec![1; pair_ids.len()];
 
-            let new_pair_encoding = Encoding::new(
+            let mut new_pair_encoding = Encoding::new(
                 pair_ids,
                 pair_type_ids,
                 pair_tokens,
@@ -171,7 +174,7 @@ impl PostProcessor for RobertaProcessing {
                             [&[1], &vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
                         let pair_attention_mask = vec![1; pair_ids.len()];
 
-                        Encoding::new(
+                        let mut overflowing = Encoding::new(
                             pair_ids,
                             pair_type_ids,
                             pair_tokens,
@@ -180,10 +183,13 @@ impl PostProcessor for RobertaProcessing {
                             pair_special_tokens,
                             pair_attention_mask,
                             vec![],
-                        )
+                        );
+                        overflowing.add_windows_from(&encoding, 1);
+                        overflowing
                     })
                     .collect(),
             );
+            new_pair_encoding.add_windows_from(&encoding, 1);
 
             new_encoding.merge_with(new_pair_encoding, false);
         }
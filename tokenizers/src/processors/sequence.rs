@@ -0,0 +1,168 @@
+use crate::processors::PostProcessorWrapper;
+use crate::tokenizer::{Encoding, PostProcessor, Result};
+use serde::{Deserialize, Serialize};
+
+/// Allows concatenating multiple other PostProcessor as a Sequence.
+/// The processors are applied in the given order, each of them receiving the encodings
+/// returned by the previous one. `ByteLevel` (and any nested `Sequence` of such processors)
+/// processes each encoding separately, while all the other processors merge the encodings
+/// they receive into a single one. The remaining encodings are merged at the end.
+///
+/// For example, `ByteLevel` followed by a `TemplateProcessing` trims the offsets of each
+/// sequence, before adding the special tokens around them. The overflowing encodings are
+/// processed by each processor along with the encoding that contains them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct Sequence {
+    processors: Vec<PostProcessorWrapper>,
+}
+
+impl Sequence {
+    pub fn new(processors: Vec<PostProcessorWrapper>) -> Self {
+        Self { processors }
+    }
+
+    pub fn get_processors(&self) -> &[PostProcessorWrapper] {
+        &self.processors
+    }
+
+    /// Apply all the processors on the given encodings, without merging the encodings that
+    /// are still separated at the end
+    pub fn process_encodings(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Vec<Encoding>> {
+        self.processors
+            .iter()
+            .try_fold(encodings, |mut encodings, processor| match processor {
+                PostProcessorWrapper::ByteLevel(bl) => {
+                    bl.process_encodings(&mut encodings);
+                    Ok(encodings)
+                }
+                PostProcessorWrapper::Sequence(seq) => {
+                    seq.process_encodings(encodings, add_special_tokens)
+                }
+                processor => Ok(vec![processor.process_multi(encodings, add_special_tokens)?]),
+            })
+    }
+
+    /// The number of encodings returned by `process_encodings` when given `n_sequences`
+    fn processed_sequences(&self, n_sequences: usize) -> usize {
+        self.processors
+            .iter()
+            .fold(n_sequences, |n, processor| match processor {
+                PostProcessorWrapper::ByteLevel(_) => n,
+                PostProcessorWrapper::Sequence(seq) => seq.processed_sequences(n),
+                _ => 1,
+            })
+    }
+}
+
+impl PostProcessor for Sequence {
+    fn added_tokens(&self, is_pair: bool) -> usize {
+        self.added_tokens_multi(if is_pair { 2 } else { 1 })
+    }
+
+    fn process(
+        &self,
+        encoding: Encoding,
+        pair_encoding: Option<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let encodings = std::iter::once(encoding).chain(pair_encoding).collect();
+        self.process_multi(encodings, add_special_tokens)
+    }
+
+    /// Each processor adds its tokens, given the number of encodings it receives
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        self.processors
+            .iter()
+            .fold((n_sequences, 0), |(n, added), processor| {
+                let next = match processor {
+                    PostProcessorWrapper::ByteLevel(_) => n,
+                    PostProcessorWrapper::Sequence(seq) => seq.processed_sequences(n),
+                    _ => 1,
+                };
+                (next, added + processor.added_tokens_multi(n))
+            })
+            .1
+    }
+
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let encodings = self.process_encodings(encodings, add_special_tokens)?;
+        PostProcessor::default_process_multi(encodings, add_special_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::byte_level::ByteLevel;
+    use crate::processors::template::TemplateProcessing;
+    use crate::Token;
+
+    fn get_sequence() -> Sequence {
+        let template = TemplateProcessing::builder()
+            .try_single("<s> $A </s>")
+            .unwrap()
+            .try_pair("<s> $A </s> $B:1 </s>:1")
+            .unwrap()
+            .special_tokens(vec![("<s>", 0), ("</s>", 2)])
+            .build()
+            .unwrap();
+        Sequence::new(vec![
+            ByteLevel::default().trim_offsets(true).into(),
+            template.into(),
+        ])
+    }
+
+    #[test]
+    fn added_tokens() {
+        let sequence = get_sequence();
+        assert_eq!(sequence.added_tokens(false), 2);
+        assert_eq!(sequence.added_tokens(true), 3);
+
+        // A processor after the template sees a single sequence
+        let twice = Sequence::new(vec![sequence.clone().into(), sequence.into()]);
+        assert_eq!(twice.added_tokens(true), 5);
+    }
+
+    #[test]
+    fn trims_offsets_and_adds_special_tokens() {
+        let sequence = get_sequence();
+        let encoding = Encoding::from_tokens(
+            vec![
+                Token::new(12, "Hello".into(), (0, 5)),
+                Token::new(14, "Ġthere".into(), (5, 11)),
+            ],
+            0,
+        );
+        let pair = Encoding::from_tokens(vec![Token::new(15, "Ġpair".into(), (0, 5))], 0);
+
+        let processed = sequence.process(encoding, Some(pair), true).unwrap();
+        assert_eq!(processed.get_ids(), &[0, 12, 14, 2, 15, 2]);
+        assert_eq!(processed.get_type_ids(), &[0, 0, 0, 0, 1, 1]);
+        assert_eq!(
+            processed.get_offsets(),
+            &[(0, 0), (0, 5), (6, 11), (0, 0), (0, 5), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let sequence = PostProcessorWrapper::Sequence(get_sequence());
+        let serialized = serde_json::to_string(&sequence).unwrap();
+        assert!(serialized.starts_with(
+            r#"{"type":"Sequence","processors":[{"type":"ByteLevel","add_prefix_space":true,"trim_offsets":true},{"type":"TemplateProcessing""#
+        ));
+        assert_eq!(
+            serde_json::from_str::<PostProcessorWrapper>(&serialized).unwrap(),
+            sequence
+        );
+    }
+}
@@ -48,6 +48,29 @@
 //!
 //! The same construct is used for special tokens: `<identifier>(:<type_id>)?`.
 //!
+//! Special tokens get an attention mask and a special tokens mask of `1` by default. Both can
+//! be changed with some flags, following the `type_id` if any:
+//! - `noattn` to exclude the special token from the attention: `[CLS]:0:noattn`
+//! - `nospecial` to not mark the token as special, like for task prefixes: `[2FR]:nospecial`
+//! - `attn` and `special` to restore the defaults
+//!
+//! ## More than two sequences
+//!
+//! Templates for inputs with more than two sequences can be added with `try_multi`, using
+//! `$C`, `$D`, ... up to `$Z` for the following sequences. The number of sequences handled
+//! by such a template is given by the last sequence it uses. Since each sequence is identified
+//! by a letter, a `TemplateProcessing` handles at most 26 sequences:
+//! ```
+//! # use tokenizers::processors::template::TemplateProcessing;
+//! let template = TemplateProcessing::builder()
+//!     .try_single("[CLS] $A [SEP]").unwrap()
+//!     .try_pair("[CLS] $A [SEP] $B:1 [SEP]:1").unwrap()
+//!     .try_multi("[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2").unwrap()
+//!     .special_tokens(vec![("[CLS]", 1), ("[SEP]", 0)])
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
 //! **Warning**: You must ensure that you are giving the correct tokens/ids as these will
 //! be added to the `Encoding` without any further check. If the given ids correspond to
 //! something totally different in a `Tokenizer` using this `PostProcessor`, it might lead
@@ -57,18 +80,73 @@
 //!
 use crate::{Encoding, PostProcessor, Result};
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::result::Result as StdResult;
 
-/// Represents both sequences received as input of the PostProcessor
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum Sequence {
+/// Represents one of the sequences received as input of the PostProcessor, identified
+/// by a letter: `A` for the first one, `B` for the pair, then `C`, `D`, ... up to `Z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sequence(usize);
+
+impl Sequence {
     /// This is the first sequence, the one that is always specified
-    A,
+    pub const A: Sequence = Sequence(0);
     /// This is the pair sequence, that is optional
-    B,
+    pub const B: Sequence = Sequence(1);
+    /// The maximum number of sequences we can identify with a letter
+    pub const MAX_SEQUENCES: usize = 26;
+
+    /// Returns the sequence at the given index, if it can be identified by a letter
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index < Self::MAX_SEQUENCES {
+            Some(Sequence(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the sequence identified by the given letter
+    pub fn from_letter(letter: &str) -> Option<Self> {
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => {
+                Some(Sequence((c.to_ascii_uppercase() as u8 - b'A') as usize))
+            }
+            _ => None,
+        }
+    }
+
+    /// The index of this sequence in the input of the PostProcessor
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// The letter identifying this sequence
+    pub fn letter(&self) -> char {
+        (b'A' + self.0 as u8) as char
+    }
+}
+
+impl Serialize for Sequence {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.letter().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Sequence {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let letter = String::deserialize(deserializer)?;
+        Sequence::from_letter(&letter)
+            .ok_or_else(|| de::Error::custom(format!("Unknown sequence {}", letter)))
+    }
 }
 
 /// Represents the different kind of pieces that constitute a template.
@@ -79,6 +157,8 @@ pub enum Sequence {
 /// of the input sequence given as input of the `PostProcessor`.
 ///
 /// - The `SpecialToken` has an associated `id`. It corresponds to a [`SpecialToken`].
+/// It also has an `attention` and a `special` flag, giving the values to use in the
+/// attention mask and the special tokens mask for its tokens.
 ///
 /// The easiest way to build a `Piece` is actually by converting it from a string:
 /// ```
@@ -87,14 +167,33 @@ pub enum Sequence {
 /// let sequence_with_type_id_0 = Piece::try_from("$0").unwrap();
 /// let sequence_with_type_id_1 = Piece::try_from("$1").unwrap();
 /// let special_token_cls = Piece::try_from("[CLS]").unwrap();
+/// let special_token_cls_without_attention = Piece::try_from("[CLS]:0:noattn").unwrap();
 /// ```
 ///
 /// [`SpecialToken`]: struct.SpecialToken.html
 ///
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Piece {
-    Sequence { id: Sequence, type_id: u32 },
-    SpecialToken { id: String, type_id: u32 },
+    Sequence {
+        id: Sequence,
+        type_id: u32,
+    },
+    SpecialToken {
+        id: String,
+        type_id: u32,
+        #[serde(default = "default_true", skip_serializing_if = "is_true")]
+        attention: bool,
+        #[serde(default = "default_true", skip_serializing_if = "is_true")]
+        special: bool,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
 }
 
 impl Piece {
@@ -108,14 +207,6 @@ impl Piece {
                     id: Sequence::A,
                     type_id: 0,
                 }),
-                "A" | "a" => Some(Self::Sequence {
-                    id: Sequence::A,
-                    type_id: 0,
-                }),
-                "B" | "b" => Some(Self::Sequence {
-                    id: Sequence::B,
-                    type_id: 0,
-                }),
                 n => {
                     if let Ok(type_id) = n.parse::<u32>() {
                         Some(Self::Sequence {
@@ -123,7 +214,7 @@ impl Piece {
                             type_id,
                         })
                     } else {
-                        None
+                        Sequence::from_letter(n).map(|id| Self::Sequence { id, type_id: 0 })
                     }
                 }
             }
@@ -131,6 +222,8 @@ impl Piece {
             Some(Self::SpecialToken {
                 id: s.to_owned(),
                 type_id: 0,
+                attention: true,
+                special: true,
             })
         }
     }
@@ -138,7 +231,44 @@ impl Piece {
     fn with_type_id(self, type_id: u32) -> Piece {
         match self {
             Piece::Sequence { id, .. } => Piece::Sequence { id, type_id },
-            Piece::SpecialToken { id, .. } => Piece::SpecialToken { id, type_id },
+            Piece::SpecialToken {
+                id,
+                attention,
+                special,
+                ..
+            } => Piece::SpecialToken {
+                id,
+                type_id,
+                attention,
+                special,
+            },
+        }
+    }
+
+    /// Apply the given flag, which is only supported by special tokens
+    fn with_flag(self, flag: &str) -> Option<Piece> {
+        match self {
+            Piece::Sequence { .. } => None,
+            Piece::SpecialToken {
+                id,
+                type_id,
+                mut attention,
+                mut special,
+            } => {
+                match flag {
+                    "attn" => attention = true,
+                    "noattn" => attention = false,
+                    "special" => special = true,
+                    "nospecial" => special = false,
+                    _ => return None,
+                }
+                Some(Piece::SpecialToken {
+                    id,
+                    type_id,
+                    attention,
+                    special,
+                })
+            }
         }
     }
 }
@@ -147,18 +277,20 @@ impl TryFrom<String> for Piece {
     type Error = String;
 
     fn try_from(s: String) -> StdResult<Self, Self::Error> {
-        let parts = s.split(':').collect::<Vec<_>>();
+        let mut parts = s.split(':');
 
         let err = || format!("Cannot build Piece from string \"{}\"", s);
-        match parts.as_slice() {
-            [id, type_id] => {
-                let type_id: u32 = type_id.parse().map_err(|_| err())?;
-                let piece = Piece::extract_id(id).ok_or_else(err)?;
-                Ok(piece.with_type_id(type_id))
-            }
-            [id] => Piece::extract_id(id).ok_or_else(err),
-            _ => Err(err()),
+        let id = parts.next().ok_or_else(err)?;
+        let mut piece = Piece::extract_id(id).ok_or_else(err)?;
+        // The type_id comes right after the id, followed by the flags
+        for (i, part) in parts.enumerate() {
+            piece = match part.parse::<u32>() {
+                Ok(type_id) if i == 0 => piece.with_type_id(type_id),
+                Ok(_) => return Err(err()),
+                Err(_) => piece.with_flag(part).ok_or_else(err)?,
+            };
         }
+        Ok(piece)
     }
 }
 
@@ -231,6 +363,11 @@ impl SpecialToken {
             Ok(Self { id, ids, tokens })
         }
     }
+
+    /// The number of tokens added by this SpecialToken
+    pub(crate) fn len(&self) -> usize {
+        self.ids.len()
+    }
 }
 
 /// A Template represents a Vec<[`Piece`]>.
@@ -249,9 +386,36 @@ impl SpecialToken {
 ///
 /// [`Piece`]: enum.Piece.html
 ///
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(transparent)]
-pub struct Template(Vec<Piece>);
+pub struct Template(pub(crate) Vec<Piece>);
+
+impl Template {
+    /// The number of sequences used by this template, given by the last sequence it uses
+    pub(crate) fn n_sequences(&self) -> usize {
+        self.0
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Sequence { id, .. } => Some(id.index() + 1),
+                Piece::SpecialToken { .. } => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether this template uses each of its sequences
+    pub(crate) fn uses_all_sequences(&self) -> bool {
+        let used = self
+            .0
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Sequence { id, .. } => Some(id.index()),
+                Piece::SpecialToken { .. } => None,
+            })
+            .collect::<HashSet<_>>();
+        used.len() == self.n_sequences()
+    }
+}
 
 impl<T> TryFrom<Vec<T>> for Template
 where
@@ -340,6 +504,10 @@ pub struct TemplateProcessing {
     single: Template,
     #[builder(try_setter, default = "\"$A:0 $B:1\".try_into().unwrap()")]
     pair: Template,
+    /// The templates used with more than two sequences
+    #[builder(setter(custom), default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    multi: Vec<Template>,
     #[builder(setter(skip), default = "self.default_added(true)")]
     #[serde(skip)]
     added_single: usize,
@@ -358,6 +526,8 @@ pub struct TemplateProcessing {
 struct TemplateProcessingDeserializer {
     single: Template,
     pair: Template,
+    #[serde(default)]
+    multi: Vec<Template>,
     special_tokens: Tokens,
 }
 impl From<TemplateProcessingDeserializer> for TemplateProcessing {
@@ -367,6 +537,7 @@ impl From<TemplateProcessingDeserializer> for TemplateProcessing {
         Self {
             single: t.single,
             pair: t.pair,
+            multi: t.multi,
             added_single,
             added_pair,
             special_tokens: t.special_tokens,
@@ -389,6 +560,20 @@ fn count_added(container: &Template, special_tokens: Option<&Tokens>) -> usize {
 }
 
 impl TemplateProcessingBuilder {
+    /// Add a template used when processing as many sequences as this template uses
+    pub fn multi(&mut self, template: Template) -> &mut Self {
+        self.multi.get_or_insert_with(Vec::new).push(template);
+        self
+    }
+
+    /// Same as `multi`, but building the template from anything convertible
+    pub fn try_multi<T>(&mut self, template: T) -> StdResult<&mut Self, String>
+    where
+        T: TryInto<Template, Error = String>,
+    {
+        Ok(self.multi(template.try_into()?))
+    }
+
     fn default_added(&self, is_single: bool) -> usize {
         let container = if is_single {
             self.single.as_ref()
@@ -424,6 +609,23 @@ impl TemplateProcessingBuilder {
             return Err("Template for `pair` must use both sequences".into());
         }
 
+        let mut multi_sizes = HashSet::new();
+        for template in self.multi.iter().flatten() {
+            let n = template.n_sequences();
+            if n <= 2 {
+                return Err("Templates for `multi` must use more than two sequences".into());
+            }
+            if !template.uses_all_sequences() {
+                return Err(format!(
+                    "Template for `multi` must use all of its {} sequences",
+                    n
+                ));
+            }
+            if !multi_sizes.insert(n) {
+                return Err(format!("Multiple `multi` templates use {} sequences", n));
+            }
+        }
+
         let check = |sp| {
             let exist = self
                 .special_tokens
@@ -442,6 +644,7 @@ impl TemplateProcessingBuilder {
             .as_ref()
             .map_or(empty.iter(), |s| s.0.iter())
             .chain(self.pair.as_ref().map_or(empty.iter(), |s| s.0.iter()))
+            .chain(self.multi.iter().flatten().flat_map(|s| s.0.iter()))
             .filter_map(|piece| match piece {
                 Piece::Sequence { .. } => None,
                 Piece::SpecialToken { id, .. } => check(id.as_ref()),
@@ -464,6 +667,7 @@ impl Default for TemplateProcessing {
         Self {
             single: "$0".try_into().unwrap(),
             pair: "$1".try_into().unwrap(),
+            multi: vec![],
             added_single: 0,
             added_pair: 0,
             special_tokens: Tokens::default(),
@@ -476,143 +680,141 @@ impl TemplateProcessing {
         TemplateProcessingBuilder::default()
     }
 
-    fn apply_template(
-        &self,
-        template: &[Piece],
-        mut encoding: Encoding,
-        mut pair: Option<Encoding>,
-        add_special_tokens: bool,
-    ) -> Result<Encoding> {
-        // Compute the new size
-        let mut new_len = 0;
-        for piece in template {
-            new_len += match piece {
-                Piece::Sequence {
-                    id: Sequence::A, ..
-                } => encoding.len(),
-                Piece::Sequence {
-                    id: Sequence::B, ..
-                } => pair
-                    .as_ref()
-                    .ok_or("Template expected a pair sequence, but none provided")?
-                    .len(),
-                Piece::SpecialToken { id, .. } => {
-                    if add_special_tokens {
-                        self.special_tokens
-                            .0
-                            .get(id)
-                            .ok_or_else(|| format!("Missing SpecialToken with id {}", id))?
-                            .ids
-                            .len()
-                    } else {
-                        0
-                    }
-                }
-            };
-        }
-
-        // Then build the new Encoding
-        let mut ids = Vec::with_capacity(new_len);
-        let mut type_ids = Vec::with_capacity(new_len);
-        let mut tokens = Vec::with_capacity(new_len);
-        let mut words = Vec::with_capacity(new_len);
-        let mut offsets = Vec::with_capacity(new_len);
-        let mut special_tokens_mask = Vec::with_capacity(new_len);
-        let mut attention_mask = Vec::with_capacity(new_len);
-
-        let pair_overflowing = pair.as_mut().map_or(vec![], |e| e.take_overflowing());
-        let mut overflowing = encoding
-            .take_overflowing()
-            .into_iter()
-            .flat_map(|encoding| {
-                let mut overflowings = vec![];
-
-                // 1. The pair itself
-                overflowings.push(self.apply_template(
-                    template,
-                    encoding.clone(),
-                    pair.clone(),
-                    add_special_tokens,
-                ));
+    /// Find the template to use with the given number of sequences
+    fn multi_template(&self, n_sequences: usize) -> Option<&Template> {
+        self.multi.iter().find(|t| t.n_sequences() == n_sequences)
+    }
+}
 
-                // 2. Its overflowings
-                for other_o in &pair_overflowing {
-                    overflowings.push(self.apply_template(
-                        template,
-                        encoding.clone(),
-                        Some(other_o.clone()),
-                        add_special_tokens,
-                    ));
+/// Build the `Encoding` described by the given template, from the given sequences and
+/// special tokens
+pub(crate) fn apply_template(
+    template: &[Piece],
+    special_tokens: &Tokens,
+    mut encodings: Vec<Encoding>,
+    add_special_tokens: bool,
+) -> Result<Encoding> {
+    // Compute the new size
+    let mut new_len = 0;
+    for piece in template {
+        new_len += match piece {
+            Piece::Sequence { id, .. } => encodings
+                .get(id.index())
+                .ok_or_else(|| {
+                    format!(
+                        "Template expected a sequence {}, but none provided",
+                        id.letter()
+                    )
+                })?
+                .len(),
+            Piece::SpecialToken { id, .. } => {
+                if add_special_tokens {
+                    special_tokens
+                        .0
+                        .get(id)
+                        .ok_or_else(|| format!("Missing SpecialToken with id {}", id))?
+                        .ids
+                        .len()
+                } else {
+                    0
                 }
+            }
+        };
+    }
 
-                overflowings
-            })
-            .collect::<Result<Vec<_>>>()?;
-        // We also need to combine the first sequence with all other overflowings
-        overflowing.extend(
-            pair_overflowing
+    // Then build the new Encoding
+    let mut ids = Vec::with_capacity(new_len);
+    let mut type_ids = Vec::with_capacity(new_len);
+    let mut tokens = Vec::with_capacity(new_len);
+    let mut words = Vec::with_capacity(new_len);
+    let mut offsets = Vec::with_capacity(new_len);
+    let mut special_tokens_mask = Vec::with_capacity(new_len);
+    let mut attention_mask = Vec::with_capacity(new_len);
+    let mut positions = Vec::with_capacity(encodings.len());
+
+    // Each combination of the sequences and their overflowings gives an overflowing, except
+    // the one with all the sequences themselves. The overflowings of the first sequence
+    // come first, the other sequences come before their overflowings.
+    let sequences_overflowing = encodings
+        .iter_mut()
+        .map(|e| e.take_overflowing())
+        .collect::<Vec<_>>();
+    let overflowing = sequences_overflowing
+        .iter()
+        .enumerate()
+        .map(|(i, overflowing)| {
+            let overflowing = (0..overflowing.len()).map(Some);
+            if i == 0 {
+                overflowing.chain(std::iter::once(None)).collect::<Vec<_>>()
+            } else {
+                std::iter::once(None).chain(overflowing).collect::<Vec<_>>()
+            }
+        })
+        .multi_cartesian_product()
+        .filter(|combination| combination.iter().any(|o| o.is_some()))
+        .map(|combination| {
+            let sequences = combination
                 .into_iter()
-                .map(|pair| {
-                    self.apply_template(template, encoding.clone(), Some(pair), add_special_tokens)
+                .enumerate()
+                .map(|(i, o)| match o {
+                    Some(o) => sequences_overflowing[i][o].clone(),
+                    None => encodings[i].clone(),
                 })
-                .collect::<Result<Vec<_>>>()?,
-        );
+                .collect();
+            apply_template(template, special_tokens, sequences, add_special_tokens)
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-        for piece in template {
-            match piece {
-                Piece::Sequence {
-                    id: Sequence::A,
-                    type_id,
-                } => {
-                    ids.extend(encoding.get_ids());
-                    type_ids.extend(std::iter::repeat(type_id).take(encoding.len()));
-                    tokens.extend(encoding.get_tokens().iter().map(|s| s.to_owned()));
-                    words.extend(encoding.get_words());
-                    offsets.extend(encoding.get_offsets());
-                    special_tokens_mask.extend(encoding.get_special_tokens_mask());
-                    attention_mask.extend(encoding.get_attention_mask());
-                }
-                Piece::Sequence {
-                    id: Sequence::B,
-                    type_id,
-                } => {
-                    let pair = pair.as_ref().expect("Missing pair sequence, checked above");
-                    ids.extend(pair.get_ids());
-                    type_ids.extend(std::iter::repeat(type_id).take(pair.len()));
-                    tokens.extend(pair.get_tokens().iter().map(|s| s.to_owned()));
-                    words.extend(pair.get_words());
-                    offsets.extend(pair.get_offsets());
-                    special_tokens_mask.extend(pair.get_special_tokens_mask());
-                    attention_mask.extend(pair.get_attention_mask());
-                }
-                Piece::SpecialToken { id, type_id } => {
-                    if add_special_tokens {
-                        let tok = &self.special_tokens.0[id]; // We already checked existance above
-                        let len = tok.ids.len();
-
-                        ids.extend(&tok.ids);
-                        type_ids.extend(std::iter::repeat(type_id).take(len));
-                        tokens.extend(tok.tokens.clone());
-                        words.extend(std::iter::repeat(None).take(len));
-                        offsets.extend(std::iter::repeat((0, 0)).take(len));
-                        special_tokens_mask.extend(std::iter::repeat(1).take(len));
-                        attention_mask.extend(std::iter::repeat(1).take(len));
-                    }
+    for piece in template {
+        match piece {
+            Piece::Sequence { id, type_id } => {
+                // We already checked existance above
+                let encoding = &encodings[id.index()];
+                positions.push((id.index(), ids.len()));
+                ids.extend(encoding.get_ids());
+                type_ids.extend(std::iter::repeat(type_id).take(encoding.len()));
+                tokens.extend(encoding.get_tokens().iter().map(|s| s.to_owned()));
+                words.extend(encoding.get_words());
+                offsets.extend(encoding.get_offsets());
+                special_tokens_mask.extend(encoding.get_special_tokens_mask());
+                attention_mask.extend(encoding.get_attention_mask());
+            }
+            Piece::SpecialToken {
+                id,
+                type_id,
+                attention,
+                special,
+            } => {
+                if add_special_tokens {
+                    let tok = &special_tokens.0[id]; // We already checked existance above
+                    let len = tok.ids.len();
+
+                    ids.extend(&tok.ids);
+                    type_ids.extend(std::iter::repeat(type_id).take(len));
+                    tokens.extend(tok.tokens.clone());
+                    words.extend(std::iter::repeat(None).take(len));
+                    offsets.extend(std::iter::repeat((0, 0)).take(len));
+                    special_tokens_mask.extend(std::iter::repeat(*special as u32).take(len));
+                    attention_mask.extend(std::iter::repeat(*attention as u32).take(len));
                 }
             }
         }
+    }
 
-        Ok(Encoding::new(
-            ids,
-            type_ids,
-            tokens,
-            words,
-            offsets,
-            special_tokens_mask,
-            attention_mask,
-            overflowing,
-        ))
+    let mut encoding = Encoding::new(
+        ids,
+        type_ids,
+        tokens,
+        words,
+        offsets,
+        special_tokens_mask,
+        attention_mask,
+        overflowing,
+    );
+    for (index, position) in positions {
+        encoding.add_windows_from(&encodings[index], position);
     }
+    Ok(encoding)
 }
 
 impl PostProcessor for TemplateProcessing {
@@ -630,17 +832,68 @@ impl PostProcessor for TemplateProcessing {
         pair: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding> {
-        self.apply_template(
-            if pair.is_some() {
-                &self.pair.0
-            } else {
-                &self.single.0
-            },
-            encoding,
-            pair,
+        let template = if pair.is_some() {
+            &self.pair.0
+        } else {
+            &self.single.0
+        };
+        let encodings = std::iter::once(encoding).chain(pair).collect();
+        apply_template(
+            template,
+            &self.special_tokens,
+            encodings,
             add_special_tokens,
         )
     }
+
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        match n_sequences {
+            0 | 1 => self.added_single,
+            2 => self.added_pair,
+            n => self.multi_template(n).map_or(0, |template| {
+                count_added(template, Some(&self.special_tokens))
+            }),
+        }
+    }
+
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        match encodings.len() {
+            0 => Err("PostProcessor expected at least one sequence".into()),
+            1 => apply_template(
+                &self.single.0,
+                &self.special_tokens,
+                encodings,
+                add_special_tokens,
+            ),
+            2 => apply_template(
+                &self.pair.0,
+                &self.special_tokens,
+                encodings,
+                add_special_tokens,
+            ),
+            n if n > Sequence::MAX_SEQUENCES => Err(format!(
+                "TemplateProcessing handles at most {} sequences ($A to $Z), got {}",
+                Sequence::MAX_SEQUENCES,
+                n
+            )
+            .into()),
+            n => {
+                let template = self
+                    .multi_template(n)
+                    .ok_or_else(|| format!("No template for {} sequences", n))?;
+                apply_template(
+                    &template.0,
+                    &self.special_tokens,
+                    encodings,
+                    add_special_tokens,
+                )
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -670,10 +923,26 @@ mod tests {
         let spe = Piece::SpecialToken {
             id: "[CLS]".into(),
             type_id: 0,
+            attention: true,
+            special: true,
         };
         let spe_s = r#"{"SpecialToken":{"id":"[CLS]","type_id":0}}"#;
         assert_eq!(serde_json::to_string(&spe).unwrap(), spe_s);
         assert_eq!(serde_json::from_str::<Piece>(spe_s).unwrap(), spe);
+
+        let spe_flags = Piece::SpecialToken {
+            id: "[CLS]".into(),
+            type_id: 0,
+            attention: false,
+            special: false,
+        };
+        let spe_flags_s =
+            r#"{"SpecialToken":{"id":"[CLS]","type_id":0,"attention":false,"special":false}}"#;
+        assert_eq!(serde_json::to_string(&spe_flags).unwrap(), spe_flags_s);
+        assert_eq!(
+            serde_json::from_str::<Piece>(spe_flags_s).unwrap(),
+            spe_flags
+        );
     }
 
     #[test]
@@ -713,8 +982,37 @@ mod tests {
             }),
             "$:1".try_into()
         );
-        assert!(Piece::try_from("$C:1").is_err());
+        assert_eq!(
+            Ok(Piece::Sequence {
+                id: Sequence::from_index(2).unwrap(),
+                type_id: 1
+            }),
+            "$C:1".try_into()
+        );
+        assert!(Piece::try_from("$AB:1").is_err());
         assert!(Piece::try_from("$A:").is_err());
+        assert_eq!(
+            Ok(Piece::SpecialToken {
+                id: "[CLS]".into(),
+                type_id: 1,
+                attention: false,
+                special: true
+            }),
+            "[CLS]:1:noattn".try_into()
+        );
+        assert_eq!(
+            Ok(Piece::SpecialToken {
+                id: "[2FR]".into(),
+                type_id: 0,
+                attention: true,
+                special: false
+            }),
+            "[2FR]:nospecial".try_into()
+        );
+        assert!(Piece::try_from("[CLS]:noattn:1").is_err());
+        assert!(Piece::try_from("[CLS]:0:1").is_err());
+        assert!(Piece::try_from("[CLS]:0:unknown").is_err());
+        assert!(Piece::try_from("$A:0:noattn").is_err());
     }
 
     #[test]
@@ -764,6 +1062,8 @@ mod tests {
             Piece::SpecialToken {
                 id: "[CLS]".into(),
                 type_id: 0,
+                attention: true,
+                special: true,
             },
         ]);
         let template_s =
@@ -897,6 +1197,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn template_processing_windows() {
+        let processor = tests::get_bert_template();
+
+        use crate::tokenizer::SequenceWindow;
+        use crate::Token;
+        let mut encoding = Encoding::from_tokens(vec![Token::new(12, "Hello".into(), (0, 5))], 0);
+        encoding.set_sequence_id(0);
+        let mut pair = Encoding::from_tokens(
+            vec![
+                Token::new(14, "there".into(), (0, 5)),
+                Token::new(15, "pair".into(), (6, 10)),
+            ],
+            0,
+        );
+        pair.set_sequence_id(1);
+        pair.truncate(1, 0);
+
+        // The pair is cut after `there`, so `pair` starts the window of its overflowing
+        let encoding = processor.process(encoding, Some(pair), true).unwrap();
+        let window = &encoding.get_overflowing()[0];
+        assert_eq!(
+            window.get_tokens(),
+            &["[CLS]", "Hello", "[SEP]", "pair", "[SEP]"]
+        );
+        assert_eq!(window.get_window_start(1), 1);
+        assert_eq!(
+            window.get_windows(1),
+            &[SequenceWindow {
+                range: 3..4,
+                start: 1
+            }]
+        );
+        assert_eq!(window.token_to_sequence_index(1), Some((0, 0)));
+        assert_eq!(window.token_to_sequence_index(3), Some((1, 1)));
+        assert_eq!(window.token_to_sequence_index(4), None);
+    }
+
+    #[test]
+    fn template_processing_flags() {
+        let processor = TemplateProcessing::builder()
+            .try_single("[CLS]:0:noattn $A [2FR]:0:nospecial")
+            .unwrap()
+            .special_tokens(vec![
+                SpecialToken::from(("[CLS]", 1)),
+                SpecialToken::new("[2FR]".into(), vec![2, 3], vec!["to".into(), "FR".into()])
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        use crate::Token;
+        let encoding = Encoding::from_tokens(vec![Token::new(12, "Hello".into(), (0, 5))], 0);
+        let encoding = processor.process(encoding, None, true).unwrap();
+        assert_eq!(encoding.get_ids(), &[1, 12, 2, 3]);
+        assert_eq!(encoding.get_attention_mask(), &[0, 1, 1, 1]);
+        assert_eq!(encoding.get_special_tokens_mask(), &[1, 0, 0, 0]);
+    }
+
     #[test]
     fn pair_must_use_both_sequences() {
         let processor = TemplateProcessing::builder()
@@ -910,4 +1269,73 @@ mod tests {
             Err("Template for `pair` must use both sequences".into())
         );
     }
+
+    #[test]
+    fn multi_template_processing() {
+        let processor = TemplateProcessing::builder()
+            .try_single("[CLS] $A [SEP]")
+            .unwrap()
+            .try_pair("[CLS] $A [SEP] $B:1 [SEP]:1")
+            .unwrap()
+            .try_multi("[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2")
+            .unwrap()
+            .special_tokens(vec![("[CLS]", 1), ("[SEP]", 0)])
+            .build()
+            .unwrap();
+        assert_eq!(processor.added_tokens_multi(2), 3);
+        assert_eq!(processor.added_tokens_multi(3), 4);
+
+        use crate::Token;
+        let encodings = vec![
+            Encoding::from_tokens(vec![Token::new(12, "Hello".into(), (0, 5))], 0),
+            Encoding::from_tokens(vec![Token::new(14, "there".into(), (0, 5))], 1),
+            Encoding::from_tokens(vec![Token::new(15, "pair".into(), (0, 4))], 2),
+        ];
+        let processed = processor.process_multi(encodings.clone(), true).unwrap();
+        assert_eq!(processed.get_ids(), &[1, 12, 0, 14, 0, 15, 0]);
+        assert_eq!(processed.get_type_ids(), &[0, 0, 0, 1, 1, 2, 2]);
+        assert_eq!(processed.get_special_tokens_mask(), &[1, 0, 1, 0, 1, 0, 1]);
+
+        assert!(processor
+            .process_multi([&encodings[..], &encodings[..]].concat(), true)
+            .is_err());
+        let too_many = (0..27)
+            .map(|i| Encoding::from_tokens(vec![Token::new(12, "Hello".into(), (0, 5))], i))
+            .collect();
+        assert_eq!(
+            processor
+                .process_multi(too_many, true)
+                .unwrap_err()
+                .to_string(),
+            "TemplateProcessing handles at most 26 sequences ($A to $Z), got 27"
+        );
+
+        let serialized = serde_json::to_string(&processor).unwrap();
+        assert!(serialized.contains(r#""multi":[[{"SpecialToken""#));
+        assert_eq!(
+            serde_json::from_str::<TemplateProcessing>(&serialized).unwrap(),
+            processor
+        );
+    }
+
+    #[test]
+    fn multi_must_use_all_sequences() {
+        let processor = TemplateProcessing::builder()
+            .try_multi("$A $B $D")
+            .unwrap()
+            .build();
+        assert_eq!(
+            processor,
+            Err("Template for `multi` must use all of its 4 sequences".into())
+        );
+
+        let processor = TemplateProcessing::builder()
+            .try_multi("$A $B")
+            .unwrap()
+            .build();
+        assert_eq!(
+            processor,
+            Err("Templates for `multi` must use more than two sequences".into())
+        );
+    }
 }
@@ -0,0 +1,222 @@
+use crate::tokenizer::{Encoding, Result};
+use crate::utils::padding::{padding_length, PaddingDirection, PaddingParams};
+
+/// A batch of padded encodings, stored as contiguous row-major arrays.
+///
+/// Each array has the shape `[n_sequences, sequence_length]`, except the offsets which have
+/// the shape `[n_sequences, sequence_length, 2]`. This makes it possible to build tensors
+/// directly from these arrays, without having to go through each `Encoding`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchEncoding {
+    n_sequences: usize,
+    sequence_length: usize,
+    ids: Vec<u32>,
+    type_ids: Vec<u32>,
+    attention_mask: Vec<u32>,
+    special_tokens_mask: Vec<u32>,
+    offsets: Vec<i64>,
+}
+
+impl BatchEncoding {
+    /// Build a `BatchEncoding` from the given encodings, which must all have the same length,
+    /// as after being padded together.
+    pub fn from_encodings(encodings: &[Encoding]) -> Result<Self> {
+        let sequence_length = encodings.first().map_or(0, |e| e.len());
+        if let Some(encoding) = encodings.iter().find(|e| e.len() != sequence_length) {
+            return Err(format!(
+                "All the encodings of a BatchEncoding must have the same length, but got {} \
+                and {}. Make sure they are padded together",
+                sequence_length,
+                encoding.len()
+            )
+            .into());
+        }
+
+        Ok(Self::build(
+            encodings,
+            sequence_length,
+            &PaddingParams::default(),
+        ))
+    }
+
+    /// Build a `BatchEncoding` from the given encodings, padding them together with the given
+    /// params while filling the arrays, instead of padding each `Encoding` beforehand.
+    pub fn from_unpadded(encodings: &[Encoding], params: &PaddingParams) -> Result<Self> {
+        let longest = encodings.iter().map(|e| e.len()).max().unwrap_or(0);
+        let sequence_length = padding_length(longest, params);
+        if longest > sequence_length {
+            return Err(format!(
+                "The encodings of a BatchEncoding can't be longer than the padding length, \
+                but got {} for {}. Make sure they are truncated",
+                longest, sequence_length
+            )
+            .into());
+        }
+
+        Ok(Self::build(encodings, sequence_length, params))
+    }
+
+    /// Fill the arrays with the given encodings, padding those shorter than `sequence_length`
+    fn build(encodings: &[Encoding], sequence_length: usize, params: &PaddingParams) -> Self {
+        let size = encodings.len() * sequence_length;
+        let mut batch = BatchEncoding {
+            n_sequences: encodings.len(),
+            sequence_length,
+            ids: Vec::with_capacity(size),
+            type_ids: Vec::with_capacity(size),
+            attention_mask: Vec::with_capacity(size),
+            special_tokens_mask: Vec::with_capacity(size),
+            offsets: Vec::with_capacity(size * 2),
+        };
+        for encoding in encodings {
+            let pad_length = sequence_length - encoding.len();
+            if let PaddingDirection::Left = params.direction {
+                batch.push_padding(pad_length, params);
+            }
+            batch.ids.extend_from_slice(encoding.get_ids());
+            batch.type_ids.extend_from_slice(encoding.get_type_ids());
+            batch
+                .attention_mask
+                .extend_from_slice(encoding.get_attention_mask());
+            batch
+                .special_tokens_mask
+                .extend_from_slice(encoding.get_special_tokens_mask());
+            for (start, end) in encoding.get_offsets() {
+                batch.offsets.push(*start as i64);
+                batch.offsets.push(*end as i64);
+            }
+            if let PaddingDirection::Right = params.direction {
+                batch.push_padding(pad_length, params);
+            }
+        }
+
+        batch
+    }
+
+    fn push_padding(&mut self, pad_length: usize, params: &PaddingParams) {
+        let len = self.ids.len() + pad_length;
+        self.ids.resize(len, params.pad_id);
+        self.type_ids.resize(len, params.pad_type_id);
+        self.attention_mask.resize(len, 0);
+        self.special_tokens_mask.resize(len, 1);
+        self.offsets.resize(len * 2, 0);
+    }
+
+    /// The shape of the arrays, as `[n_sequences, sequence_length]`
+    pub fn shape(&self) -> [usize; 2] {
+        [self.n_sequences, self.sequence_length]
+    }
+
+    pub fn len(&self) -> usize {
+        self.n_sequences
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n_sequences == 0
+    }
+
+    pub fn get_ids(&self) -> &[u32] {
+        &self.ids
+    }
+
+    pub fn get_type_ids(&self) -> &[u32] {
+        &self.type_ids
+    }
+
+    pub fn get_attention_mask(&self) -> &[u32] {
+        &self.attention_mask
+    }
+
+    pub fn get_special_tokens_mask(&self) -> &[u32] {
+        &self.special_tokens_mask
+    }
+
+    /// The offsets, with the shape `[n_sequences, sequence_length, 2]`
+    pub fn get_offsets(&self) -> &[i64] {
+        &self.offsets
+    }
+
+    /// Consume the batch and return its arrays, as `(ids, type_ids, attention_mask,
+    /// special_tokens_mask, offsets)`, to hand them over without copying them
+    #[allow(clippy::type_complexity)]
+    pub fn into_arrays(self) -> (Vec<u32>, Vec<u32>, Vec<u32>, Vec<u32>, Vec<i64>) {
+        (
+            self.ids,
+            self.type_ids,
+            self.attention_mask,
+            self.special_tokens_mask,
+            self.offsets,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Token;
+
+    #[test]
+    fn from_encodings() {
+        let mut first = Encoding::from_tokens(
+            vec![
+                Token::new(1, "Hello".into(), (0, 5)),
+                Token::new(2, "there".into(), (6, 11)),
+            ],
+            0,
+        );
+        let mut second = Encoding::from_tokens(vec![Token::new(3, "Hi".into(), (0, 2))], 1);
+
+        assert!(BatchEncoding::from_encodings(&[first.clone(), second.clone()]).is_err());
+
+        second.pad(2, 0, 0, "[PAD]", crate::PaddingDirection::Right);
+        first.pad(2, 0, 0, "[PAD]", crate::PaddingDirection::Right);
+        let batch = BatchEncoding::from_encodings(&[first, second]).unwrap();
+        assert_eq!(batch.shape(), [2, 2]);
+        assert_eq!(batch.get_ids(), &[1, 2, 3, 0]);
+        assert_eq!(batch.get_type_ids(), &[0, 0, 1, 0]);
+        assert_eq!(batch.get_attention_mask(), &[1, 1, 1, 0]);
+        assert_eq!(batch.get_special_tokens_mask(), &[0, 0, 0, 1]);
+        assert_eq!(batch.get_offsets(), &[0, 5, 6, 11, 0, 2, 0, 0]);
+
+        let empty = BatchEncoding::from_encodings(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.shape(), [0, 0]);
+    }
+
+    #[test]
+    fn from_unpadded() {
+        let first = Encoding::from_tokens(
+            vec![
+                Token::new(1, "Hello".into(), (0, 5)),
+                Token::new(2, "there".into(), (6, 11)),
+            ],
+            0,
+        );
+        let second = Encoding::from_tokens(vec![Token::new(3, "Hi".into(), (0, 2))], 1);
+        let mut params = PaddingParams {
+            pad_id: 9,
+            ..Default::default()
+        };
+
+        // Same as padding the encodings before building the batch
+        let mut encodings = vec![first.clone(), second.clone()];
+        crate::pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(
+            BatchEncoding::from_unpadded(&[first.clone(), second.clone()], &params).unwrap(),
+            BatchEncoding::from_encodings(&encodings).unwrap()
+        );
+
+        params.direction = PaddingDirection::Left;
+        params.pad_to_multiple_of = Some(3);
+        let batch =
+            BatchEncoding::from_unpadded(&[first.clone(), second.clone()], &params).unwrap();
+        assert_eq!(batch.shape(), [2, 3]);
+        assert_eq!(batch.get_ids(), &[9, 1, 2, 9, 9, 3]);
+        assert_eq!(batch.get_attention_mask(), &[0, 1, 1, 0, 0, 1]);
+        assert_eq!(batch.get_offsets(), &[0, 0, 0, 5, 6, 11, 0, 0, 0, 0, 0, 2]);
+
+        params.strategy = crate::PaddingStrategy::Fixed(1);
+        params.pad_to_multiple_of = None;
+        assert!(BatchEncoding::from_unpadded(&[first, second], &params).is_err());
+    }
+}
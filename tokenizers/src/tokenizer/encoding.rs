@@ -1,7 +1,22 @@
 use crate::parallelism::*;
 use crate::tokenizer::{Offsets, Token};
 use crate::utils::padding::PaddingDirection;
+use crate::utils::truncation::TruncationDirection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Contiguous tokens of a sequence in an `Encoding`, which may only be a window of this
+/// sequence. A sequence has several of them when some of its tokens were removed from the
+/// middle, like with the `HeadTail` truncation.
+#[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceWindow {
+    /// The range of these tokens in the `Encoding`
+    pub range: Range<usize>,
+    /// The index in the original sequence of the first token of the range. It is `0`,
+    /// unless the window was produced by the truncation.
+    pub start: usize,
+}
 
 /// Represents the output of a `Tokenizer`.
 #[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +35,9 @@ pub struct Encoding {
     special_tokens_mask: Vec<u32>,
     /// Mask identifying padding tokens for the attention mechanism
     attention_mask: Vec<u32>,
+    /// The windows of each sequence in this `Encoding`, in order, by sequence id
+    #[serde(default)]
+    windows: HashMap<usize, Vec<SequenceWindow>>,
     /// A list of overflowing Encoding generated when we got truncated
     overflowing: Vec<Encoding>,
 }
@@ -43,6 +61,7 @@ impl Encoding {
             offsets,
             special_tokens_mask,
             attention_mask,
+            windows: HashMap::new(),
             overflowing,
         }
     }
@@ -56,6 +75,7 @@ impl Encoding {
             offsets: Vec::with_capacity(len),
             special_tokens_mask: Vec::with_capacity(len),
             attention_mask: Vec::with_capacity(len),
+            windows: HashMap::new(),
             overflowing: vec![],
         }
     }
@@ -84,6 +104,7 @@ impl Encoding {
             type_ids: vec![type_id; length],
             attention_mask: vec![1; length],
             special_tokens_mask: vec![0; length],
+            windows: HashMap::new(),
             overflowing: vec![],
         }
     }
@@ -132,6 +153,80 @@ impl Encoding {
         &self.attention_mask
     }
 
+    /// Mark all the tokens of this `Encoding`, and of its overflowings, as belonging to the
+    /// sequence with the given id. The `Tokenizer` does it for each input sequence, before
+    /// the truncation and the post-processing, which then keep track of its window.
+    pub fn set_sequence_id(&mut self, sequence_id: usize) {
+        self.windows = HashMap::new();
+        self.windows.insert(
+            sequence_id,
+            vec![SequenceWindow {
+                range: 0..self.len(),
+                start: 0,
+            }],
+        );
+        for overflowing in &mut self.overflowing {
+            overflowing.set_sequence_id(sequence_id);
+        }
+    }
+
+    /// Get the windows of the sequence with the given id, in order. This is empty if the
+    /// sequence has no token in this `Encoding`.
+    pub fn get_windows(&self, sequence_id: usize) -> &[SequenceWindow] {
+        self.windows.get(&sequence_id).map_or(&[], |w| w.as_slice())
+    }
+
+    /// Get the index, in the original sequence with the given id, of its first token in this
+    /// `Encoding`. This is `0`, unless it was produced by the truncation, as an overflowing
+    /// window or by removing the first tokens.
+    pub fn get_window_start(&self, sequence_id: usize) -> usize {
+        self.get_windows(sequence_id).first().map_or(0, |w| w.start)
+    }
+
+    /// Get the sequence id of the given token, along with its index in this sequence,
+    /// accounting for its window. The tokens added by the post-processing or the padding
+    /// belong to no sequence.
+    pub fn token_to_sequence_index(&self, token: usize) -> Option<(usize, usize)> {
+        self.windows.iter().find_map(|(id, windows)| {
+            windows
+                .iter()
+                .find(|w| w.range.contains(&token))
+                .map(|w| (*id, w.start + token - w.range.start))
+        })
+    }
+
+    /// Add the given windows of a sequence, whose range starts at the given position. A window
+    /// that follows the last one of the sequence, both here and in the sequence, extends it.
+    fn push_windows<I>(&mut self, sequence_id: usize, windows: I, position: usize)
+    where
+        I: IntoIterator<Item = SequenceWindow>,
+    {
+        let existing = self.windows.entry(sequence_id).or_default();
+        for window in windows {
+            let range = window.range.start + position..window.range.end + position;
+            match existing.last_mut() {
+                Some(last)
+                    if last.range.end == range.start
+                        && last.start + last.range.len() == window.start =>
+                {
+                    last.range.end = range.end
+                }
+                _ => existing.push(SequenceWindow {
+                    range,
+                    start: window.start,
+                }),
+            }
+        }
+    }
+
+    /// Add the windows of the given `Encoding`, whose tokens start at the given position in
+    /// this one
+    pub(crate) fn add_windows_from(&mut self, encoding: &Encoding, position: usize) {
+        for (id, windows) in &encoding.windows {
+            self.push_windows(*id, windows.iter().cloned(), position);
+        }
+    }
+
     pub fn get_overflowing(&self) -> &Vec<Encoding> {
         &self.overflowing
     }
@@ -217,11 +312,34 @@ impl Encoding {
             .flatten()
     }
 
-    /// Truncate the current `Encoding`.
+    /// Truncate the current `Encoding`, keeping its first `max_len` tokens.
     ///
     /// Panic if `stride >= max_len`
     pub fn truncate(&mut self, max_len: usize, stride: usize) {
-        if max_len >= self.ids.len() {
+        self.truncate_with_direction(max_len, stride, TruncationDirection::Right, false)
+    }
+
+    /// Truncate the current `Encoding` in the given direction.
+    ///
+    /// With `Right`, the first `max_len` tokens are kept, and the overflowing tokens are split
+    /// into windows going forward, each one starting with the last `stride` tokens of the
+    /// previous one. With `Left`, the last `max_len` tokens are kept, and the windows go
+    /// backward, each one ending with the first `stride` tokens of the following one.
+    ///
+    /// With `word_boundaries`, every cut is moved to the closest word boundary that keeps the
+    /// parts under `max_len`, so that the tokens of a word are never split across windows.
+    /// A word longer than what a part can hold is still split between its tokens.
+    ///
+    /// Panic if `stride >= max_len`
+    pub fn truncate_with_direction(
+        &mut self,
+        max_len: usize,
+        stride: usize,
+        direction: TruncationDirection,
+        word_boundaries: bool,
+    ) {
+        let len = self.ids.len();
+        if max_len >= len {
             return;
         }
 
@@ -231,76 +349,178 @@ impl Encoding {
             return;
         }
 
-        // Get the main overflowing part
-        let o_ids = self.ids.split_off(max_len);
-        let o_type_ids = self.type_ids.split_off(max_len);
-        let o_tokens = self.tokens.split_off(max_len);
-        let o_words = self.words.split_off(max_len);
-        let o_offsets = self.offsets.split_off(max_len);
-        let o_spe_toks = self.special_tokens_mask.split_off(max_len);
-        let o_attent = self.attention_mask.split_off(max_len);
-
-        // Now we need to separate the overflowing part into as many Encoding as needed
         assert!(stride < max_len);
-        let part_size = max_len - stride;
-        let mut overflowing = vec![];
-        let mut part_id = 0;
-        let mut prev_encoding: &Encoding = self;
-
-        loop {
-            if part_size * part_id >= o_ids.len() {
-                break;
+        let (kept, overflowing) = match direction {
+            TruncationDirection::Right => {
+                let cut = self.snap_to_word(max_len, 1, word_boundaries);
+                (
+                    self.slice(0..cut),
+                    self.windows(cut..len, max_len, stride, direction, word_boundaries),
+                )
+            }
+            TruncationDirection::Left => {
+                let cut = self.snap_to_word(len - max_len, len - 1, word_boundaries);
+                (
+                    self.slice(cut..len),
+                    self.windows(0..cut, max_len, stride, direction, word_boundaries),
+                )
             }
+        };
 
-            let o = Encoding {
-                ids: get_current_part(&prev_encoding.ids, &o_ids, part_size, part_id, stride),
-                type_ids: get_current_part(
-                    &prev_encoding.type_ids,
-                    &o_type_ids,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                tokens: get_current_part(
-                    &prev_encoding.tokens,
-                    &o_tokens,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                words: get_current_part(&prev_encoding.words, &o_words, part_size, part_id, stride),
-                offsets: get_current_part(
-                    &prev_encoding.offsets,
-                    &o_offsets,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                special_tokens_mask: get_current_part(
-                    &prev_encoding.special_tokens_mask,
-                    &o_spe_toks,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                attention_mask: get_current_part(
-                    &prev_encoding.attention_mask,
-                    &o_attent,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                overflowing: vec![],
-            };
+        *self = kept;
+        self.overflowing = overflowing;
+    }
+
+    /// Truncate the current `Encoding`, keeping its first `head` tokens, and its last
+    /// `max_len - head` ones. The tokens removed from the middle are split into windows
+    /// going forward, like when truncating with the `Right` direction, and `word_boundaries`
+    /// applies to each cut in the same way.
+    ///
+    /// Panic if `stride >= max_len`
+    pub fn truncate_head_tail(
+        &mut self,
+        head: usize,
+        max_len: usize,
+        stride: usize,
+        word_boundaries: bool,
+    ) {
+        let len = self.ids.len();
+        if max_len >= len {
+            return;
+        }
 
-            part_id += 1;
-            overflowing.push(o);
-            prev_encoding = &overflowing.last().unwrap();
+        let head = std::cmp::min(head, max_len);
+        if head == 0 {
+            return self.truncate_with_direction(
+                max_len,
+                stride,
+                TruncationDirection::Left,
+                word_boundaries,
+            );
         }
 
+        assert!(stride < max_len);
+        let head_end = self.snap_to_word(head, 0, word_boundaries);
+        let tail_start = len - (max_len - head);
+        let tail_start = self.snap_to_word(tail_start, len, word_boundaries);
+        let overflowing = self.windows(
+            head_end..tail_start,
+            max_len,
+            stride,
+            TruncationDirection::Right,
+            word_boundaries,
+        );
+
+        let mut kept = self.slice(0..head_end);
+        kept.merge_with(self.slice(tail_start..len), false);
+        *self = kept;
         self.overflowing = overflowing;
     }
 
+    /// Copy the given range of tokens in a new `Encoding`, without any overflowing
+    fn slice(&self, range: Range<usize>) -> Encoding {
+        let windows = self
+            .windows
+            .iter()
+            .filter_map(|(id, windows)| {
+                let windows = windows
+                    .iter()
+                    .filter_map(|w| {
+                        let start = std::cmp::max(w.range.start, range.start);
+                        let end = std::cmp::min(w.range.end, range.end);
+                        if start < end {
+                            Some(SequenceWindow {
+                                range: start - range.start..end - range.start,
+                                start: w.start + start - w.range.start,
+                            })
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if windows.is_empty() {
+                    None
+                } else {
+                    Some((*id, windows))
+                }
+            })
+            .collect();
+
+        Encoding {
+            ids: self.ids[range.clone()].to_vec(),
+            type_ids: self.type_ids[range.clone()].to_vec(),
+            tokens: self.tokens[range.clone()].to_vec(),
+            words: self.words[range.clone()].to_vec(),
+            offsets: self.offsets[range.clone()].to_vec(),
+            special_tokens_mask: self.special_tokens_mask[range.clone()].to_vec(),
+            attention_mask: self.attention_mask[range].to_vec(),
+            windows,
+            overflowing: vec![],
+        }
+    }
+
+    /// Whether cutting before the token at the given position keeps the words whole
+    fn is_word_boundary(&self, pos: usize) -> bool {
+        pos == 0
+            || pos >= self.words.len()
+            || self.words[pos - 1].is_none()
+            || self.words[pos - 1] != self.words[pos]
+    }
+
+    /// Move the given cut position to the closest word boundary, going toward `limit`.
+    /// The position stays as is if there is no boundary on the way, or if `enabled` is false.
+    fn snap_to_word(&self, pos: usize, limit: usize, enabled: bool) -> usize {
+        if !enabled {
+            return pos;
+        }
+        let boundary = if limit <= pos {
+            (limit..=pos).rev().find(|p| self.is_word_boundary(*p))
+        } else {
+            (pos..=limit).find(|p| self.is_word_boundary(*p))
+        };
+        boundary.unwrap_or(pos)
+    }
+
+    /// Split the given range of overflowing tokens into windows of `max_len - stride` tokens,
+    /// each one extended with `stride` tokens of context: those preceding it when going
+    /// forward (`Right`), or those following it when going backward (`Left`).
+    fn windows(
+        &self,
+        range: Range<usize>,
+        max_len: usize,
+        stride: usize,
+        direction: TruncationDirection,
+        word_boundaries: bool,
+    ) -> Vec<Encoding> {
+        let part_size = max_len - stride;
+        let mut windows = vec![];
+        match direction {
+            TruncationDirection::Right => {
+                let mut start = range.start;
+                while start < range.end {
+                    let end = std::cmp::min(start + part_size, range.end);
+                    let end = self.snap_to_word(end, start + 1, word_boundaries);
+                    let context_start = start.saturating_sub(stride);
+                    let context_start = self.snap_to_word(context_start, start, word_boundaries);
+                    windows.push(self.slice(context_start..end));
+                    start = end;
+                }
+            }
+            TruncationDirection::Left => {
+                let mut end = range.end;
+                while end > range.start {
+                    let start = std::cmp::max(end.saturating_sub(part_size), range.start);
+                    let start = self.snap_to_word(start, end - 1, word_boundaries);
+                    let context_end = std::cmp::min(end + stride, self.ids.len());
+                    let context_end = self.snap_to_word(context_end, end, word_boundaries);
+                    windows.push(self.slice(start..context_end));
+                    end = start;
+                }
+            }
+        }
+        windows
+    }
+
     /// Merge all Encodings together
     pub fn merge<I: IntoIterator<Item = Encoding>>(encodings: I, growing_offsets: bool) -> Self {
         let mut encoding = Encoding::default();
@@ -339,7 +559,12 @@ impl Encoding {
             overflowings.push(n_encoding);
         }
 
-        // Finish by merging ourself with the other encoding
+        // Finish by merging ourself with the other encoding. The windows of a sequence that
+        // we already have are added to ours, and extend our last one when they follow it.
+        let position = self.len();
+        for (id, windows) in pair.windows {
+            self.push_windows(id, windows, position);
+        }
         self.ids.extend(pair.ids);
         self.type_ids.extend(pair.type_ids);
         self.tokens.extend(pair.tokens);
@@ -411,6 +636,9 @@ impl Encoding {
                     .map(|_| (0, 0))
                     .chain(self.offsets.drain(..))
                     .collect();
+                for window in self.windows.values_mut().flatten() {
+                    window.range = window.range.start + pad_length..window.range.end + pad_length;
+                }
             }
             PaddingDirection::Right => {
                 self.ids.extend((0..pad_length).map(|_| pad_id));
@@ -455,23 +683,6 @@ impl std::iter::FromIterator<(u32, String, (usize, usize), Option<u32>, u32)> fo
     }
 }
 
-#[inline]
-fn get_current_part<T: Clone>(
-    prev: &[T],
-    current: &[T],
-    size: usize,
-    idx: usize,
-    stride: usize,
-) -> Vec<T> {
-    let curr_slice = if (idx + 1) * size > current.len() {
-        &current[idx * size..]
-    } else {
-        &current[idx * size..(idx + 1) * size]
-    };
-    let prev_slice = &prev[prev.len() - stride..];
-    [prev_slice, curr_slice].concat()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,6 +697,7 @@ mod tests {
             offsets: vec![(0, 6)],
             special_tokens_mask: vec![0],
             attention_mask: vec![1],
+            windows: HashMap::new(),
             overflowing: vec![],
         };
         let b = Encoding {
@@ -496,6 +708,7 @@ mod tests {
             offsets: vec![(0, 6)],
             special_tokens_mask: vec![0],
             attention_mask: vec![1],
+            windows: HashMap::new(),
             overflowing: vec![],
         };
         a.merge_with(b, true);
@@ -510,6 +723,7 @@ mod tests {
                 offsets: vec![(0, 6), (6, 12)],
                 special_tokens_mask: vec![0, 0],
                 attention_mask: vec![1, 1],
+                windows: HashMap::new(),
                 overflowing: vec![],
             }
         );
@@ -529,6 +743,7 @@ mod tests {
             offsets: vec![(0, 5), (6, 11), (11, 12)],
             special_tokens_mask: vec![0, 0, 0],
             attention_mask: vec![1, 1, 1],
+            windows: HashMap::new(),
             overflowing: vec![],
         };
         a.truncate(2, 0);
@@ -543,6 +758,7 @@ mod tests {
                 offsets: vec![(0, 5), (6, 11)],
                 special_tokens_mask: vec![0, 0],
                 attention_mask: vec![1, 1],
+                windows: HashMap::new(),
                 overflowing: vec![Encoding {
                     ids: vec![3],
                     type_ids: vec![0],
@@ -551,6 +767,7 @@ mod tests {
                     offsets: vec![(11, 12)],
                     special_tokens_mask: vec![0],
                     attention_mask: vec![1],
+                    windows: HashMap::new(),
                     overflowing: vec![],
                 }]
             }
@@ -571,6 +788,7 @@ mod tests {
             offsets: vec![(0, 5), (6, 11), (11, 12)],
             special_tokens_mask: vec![0, 0, 0],
             attention_mask: vec![1, 1, 1],
+            windows: HashMap::new(),
             overflowing: vec![],
         };
         a.truncate(0, 0);
@@ -585,6 +803,7 @@ mod tests {
                 offsets: vec![],
                 special_tokens_mask: vec![],
                 attention_mask: vec![],
+                windows: HashMap::new(),
                 overflowing: vec![Encoding {
                     ids: vec![1, 2, 3],
                     type_ids: vec![0, 0, 0],
@@ -597,12 +816,178 @@ mod tests {
                     offsets: vec![(0, 5), (6, 11), (11, 12)],
                     special_tokens_mask: vec![0, 0, 0],
                     attention_mask: vec![1, 1, 1],
+                    windows: HashMap::new(),
                     overflowing: vec![],
                 }]
             }
         );
     }
 
+    fn get_numbers(n: u32) -> Encoding {
+        Encoding::from_tokens(
+            (1..=n)
+                .map(|i| Token::new(i, i.to_string(), (i as usize - 1, i as usize)))
+                .collect(),
+            0,
+        )
+    }
+
+    #[test]
+    fn truncate_left() {
+        let mut a = get_numbers(6);
+        a.truncate_with_direction(3, 1, TruncationDirection::Left, false);
+
+        assert_eq!(a.get_ids(), &[4, 5, 6]);
+        assert_eq!(a.get_offsets(), &[(3, 4), (4, 5), (5, 6)]);
+        // Going backward, each window ends with the first token of the following one
+        let overflowing = a
+            .get_overflowing()
+            .iter()
+            .map(|o| o.get_ids().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(overflowing, vec![vec![2, 3, 4], vec![1, 2]]);
+    }
+
+    #[test]
+    fn truncate_head_tail() {
+        let mut a = get_numbers(6);
+        a.truncate_head_tail(1, 3, 1, false);
+
+        assert_eq!(a.get_ids(), &[1, 5, 6]);
+        assert_eq!(a.get_offsets(), &[(0, 1), (4, 5), (5, 6)]);
+        let overflowing = a
+            .get_overflowing()
+            .iter()
+            .map(|o| o.get_ids().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(overflowing, vec![vec![1, 2, 3], vec![3, 4]]);
+
+        // Without head, this is the same as truncating from the left
+        let mut b = get_numbers(6);
+        b.truncate_head_tail(0, 3, 1, false);
+        let mut c = get_numbers(6);
+        c.truncate_with_direction(3, 1, TruncationDirection::Left, false);
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn sequence_windows() {
+        // A (question, context) pair, where only the context overflows
+        let mut question = get_numbers(2);
+        question.set_sequence_id(0);
+        let mut context = get_numbers(6);
+        context.set_sequence_id(1);
+        context.truncate(3, 1);
+        let starts = context
+            .get_overflowing()
+            .iter()
+            .map(|o| o.get_window_start(1))
+            .collect::<Vec<_>>();
+        assert_eq!(starts, vec![2, 4]);
+
+        // With a [CLS] token before the question, like a post-processor would add
+        let mut encoding = Encoding::from_tokens(vec![Token::new(0, "[CLS]".into(), (0, 0))], 0);
+        encoding.merge_with(question, false);
+        encoding.merge_with(context, false);
+        let window = &encoding.get_overflowing()[0];
+        assert_eq!(window.get_ids(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(window.get_window_start(0), 0);
+        assert_eq!(window.get_window_start(1), 2);
+        assert_eq!(
+            window.get_windows(1),
+            &[SequenceWindow {
+                range: 3..6,
+                start: 2
+            }]
+        );
+        assert_eq!(window.token_to_sequence_index(0), None);
+        assert_eq!(window.token_to_sequence_index(1), Some((0, 0)));
+        assert_eq!(window.token_to_sequence_index(4), Some((1, 3)));
+
+        // Padding on the left moves the windows
+        let mut padded = window.clone();
+        padded.pad(8, 0, 0, "[PAD]", PaddingDirection::Left);
+        assert_eq!(padded.token_to_sequence_index(1), None);
+        assert_eq!(padded.token_to_sequence_index(3), Some((0, 0)));
+        assert_eq!(padded.token_to_sequence_index(6), Some((1, 3)));
+    }
+
+    #[test]
+    fn head_tail_windows() {
+        let mut encoding = get_numbers(6);
+        encoding.set_sequence_id(0);
+        encoding.truncate_head_tail(1, 3, 0, false);
+        assert_eq!(encoding.get_ids(), &[1, 5, 6]);
+
+        // The head and the tail each have their own window
+        assert_eq!(
+            encoding.get_windows(0),
+            &[
+                SequenceWindow {
+                    range: 0..1,
+                    start: 0
+                },
+                SequenceWindow {
+                    range: 1..3,
+                    start: 4
+                }
+            ]
+        );
+        assert_eq!(encoding.get_window_start(0), 0);
+        assert_eq!(encoding.token_to_sequence_index(0), Some((0, 0)));
+        assert_eq!(encoding.token_to_sequence_index(1), Some((0, 4)));
+        assert_eq!(encoding.token_to_sequence_index(2), Some((0, 5)));
+
+        // Which stay apart when merged, after a [CLS] token
+        let mut merged = Encoding::from_tokens(vec![Token::new(0, "[CLS]".into(), (0, 0))], 0);
+        merged.merge_with(encoding, false);
+        assert_eq!(merged.token_to_sequence_index(1), Some((0, 0)));
+        assert_eq!(merged.token_to_sequence_index(2), Some((0, 4)));
+        assert_eq!(merged.get_overflowing()[0].get_window_start(0), 1);
+    }
+
+    #[test]
+    fn truncate_word_boundaries() {
+        let get_words = || {
+            let mut encoding = get_numbers(7);
+            let words = [0, 0, 1, 2, 2, 2, 3];
+            for (word, w) in encoding.get_words_mut().iter_mut().zip(words.iter()) {
+                *word = Some(*w);
+            }
+            encoding
+        };
+        let ids = |encodings: &[Encoding]| {
+            encodings
+                .iter()
+                .map(|o| o.get_ids().to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        let mut a = get_words();
+        a.truncate_with_direction(4, 1, TruncationDirection::Right, true);
+        assert_eq!(a.get_ids(), &[1, 2, 3]);
+        assert_eq!(ids(a.get_overflowing()), vec![vec![3, 4, 5, 6], vec![7]]);
+
+        let mut a = get_words();
+        a.truncate_with_direction(4, 1, TruncationDirection::Left, true);
+        assert_eq!(a.get_ids(), &[4, 5, 6, 7]);
+        assert_eq!(ids(a.get_overflowing()), vec![vec![1, 2, 3]]);
+
+        let mut a = get_words();
+        a.truncate_head_tail(1, 4, 0, true);
+        assert_eq!(a.get_ids(), &[7]);
+        assert_eq!(ids(a.get_overflowing()), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        // A word longer than `max_len` still gets split
+        let mut a = get_words();
+        a.truncate_with_direction(2, 0, TruncationDirection::Right, true);
+        assert_eq!(a.get_ids(), &[1, 2]);
+        assert_eq!(
+            ids(a.get_overflowing()),
+            vec![vec![3], vec![4, 5], vec![6, 7]]
+        );
+    }
+
     #[test]
     fn mappings() {
         let encoding = Encoding {
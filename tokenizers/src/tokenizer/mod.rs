@@ -14,12 +14,13 @@ use std::{
     fmt,
     fs::{read_to_string, File},
     io::prelude::*,
-    io::BufReader,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
 };
 
 use indicatif::{ProgressBar, ProgressStyle};
+use rand::RngCore;
+use rand_chacha::ChaCha20Rng;
 use serde::de::DeserializeOwned;
 use serde::export::Formatter;
 use serde::{Deserialize, Serialize};
@@ -28,23 +29,36 @@ use crate::decoders::DecoderWrapper;
 use crate::models::ModelWrapper;
 use crate::normalizers::NormalizerWrapper;
 use crate::pre_tokenizers::PreTokenizerWrapper;
+use crate::processors::chat::ChatTemplate;
 use crate::processors::PostProcessorWrapper;
+use crate::utils::compression;
+use crate::utils::nbest_combinations;
 use crate::utils::parallelism::*;
+use crate::utils::seeded_rng;
 
 mod added_vocabulary;
+mod batch_encoding;
 mod encoding;
 pub mod normalizer;
 pub mod pattern;
 pub mod pre_tokenizer;
 mod serialization;
+mod word_counts;
 
 pub use crate::utils::iter::LinesWithEnding;
 pub use crate::utils::padding::{pad_encodings, PaddingDirection, PaddingParams, PaddingStrategy};
-pub use crate::utils::truncation::{truncate_encodings, TruncationParams, TruncationStrategy};
+pub use crate::utils::truncation::{
+    flatten_overflowing, truncate_encodings, truncate_multi_encodings, FlattenedBatch,
+    TruncationDirection, TruncationError, TruncationParams, TruncationStrategy,
+};
 pub use added_vocabulary::*;
+pub use batch_encoding::*;
 pub use encoding::*;
-pub use normalizer::{NormalizedString, OffsetReferential, SplitDelimiterBehavior};
+pub use normalizer::{
+    NormalizedString, OffsetReferential, ReplacementPiece, SplitDelimiterBehavior,
+};
 pub use pre_tokenizer::*;
+pub use word_counts::*;
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -69,6 +83,27 @@ pub trait Model {
     /// Tokenize the given sequence into multiple underlying `Token`. The `offsets` on the `Token`
     /// are expected to be relative to the given sequence.
     fn tokenize(&self, sequence: &str) -> Result<Vec<Token>>;
+    /// Tokenize the given sequence into its `n` best alternative tokenizations, along with
+    /// their score (higher is better). Models that have a single possible tokenization
+    /// return it alone, with a score of `0.0`.
+    fn tokenize_nbest(&self, sequence: &str, n: usize) -> Result<Vec<(Vec<Token>, f64)>> {
+        if n == 0 {
+            return Ok(vec![]);
+        }
+        Ok(vec![(self.tokenize(sequence)?, 0.0)])
+    }
+    /// Tokenize the given sequence, drawing from the given random number generator for any
+    /// randomness involved (like the BPE dropout or the Unigram sampling). Deterministic
+    /// models simply tokenize the sequence.
+    fn tokenize_with_rng(&self, sequence: &str, _rng: &mut dyn RngCore) -> Result<Vec<Token>> {
+        self.tokenize(sequence)
+    }
+    /// Draw the seed of the next encode call, if this model has a seed for its randomness.
+    /// All the words of an input are then tokenized with a single random number generator
+    /// built from this seed.
+    fn next_seed(&self) -> Option<u64> {
+        None
+    }
     /// Find the ID associated to a string token
     fn token_to_id(&self, token: &str) -> Option<u32>;
     /// Find the string token associated to an ID
@@ -94,6 +129,29 @@ pub trait PostProcessor {
         pair_encoding: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding>;
+    /// Returns the number of tokens that will be added during the processing step, when
+    /// processing the given number of sequences
+    fn added_tokens_multi(&self, n_sequences: usize) -> usize {
+        self.added_tokens(n_sequences > 1)
+    }
+    /// Process any number of encodings and returns a new merged one. By default, only
+    /// one or two encodings are supported, and they are given to `process`.
+    fn process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let mut encodings = encodings.into_iter();
+        match (encodings.next(), encodings.next(), encodings.next()) {
+            (Some(encoding), pair_encoding, None) => {
+                self.process(encoding, pair_encoding, add_special_tokens)
+            }
+            (None, _, _) => Err("PostProcessor expected at least one sequence".into()),
+            (Some(_), _, Some(_)) => {
+                Err("This PostProcessor can't process more than two sequences".into())
+            }
+        }
+    }
 }
 impl dyn PostProcessor {
     pub fn default_process(
@@ -109,11 +167,33 @@ impl dyn PostProcessor {
             }
         }
     }
+
+    /// Merge all the given encodings in order, without adding anything
+    pub fn default_process_multi(
+        encodings: Vec<Encoding>,
+        _add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let mut encodings = encodings.into_iter();
+        let mut encoding = encodings
+            .next()
+            .ok_or("PostProcessor expected at least one sequence")?;
+        for other in encodings {
+            encoding.merge_with(other, false);
+        }
+        Ok(encoding)
+    }
 }
 
 /// A `Decoder` has the responsibility to merge the given `Vec<String>` in a `String`.
 pub trait Decoder {
     fn decode(&self, tokens: Vec<String>) -> Result<String>;
+
+    /// The per-token stage of the decoding: it takes the tokens and returns the decoded
+    /// tokens, which lets decoders be chained (see `decoders::sequence::Sequence`). By
+    /// default, the tokens are all merged in a single one using `decode`.
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(vec![self.decode(tokens)?])
+    }
 }
 
 /// A `Trainer` has the responsibility to train a model. We feed it with lines/sentences
@@ -211,6 +291,19 @@ impl<'s> From<&'s [Cow<'s, str>]> for InputSequence<'s> {
 pub enum EncodeInput<'s> {
     Single(InputSequence<'s>),
     Dual(InputSequence<'s>, InputSequence<'s>),
+    /// Any number of sequences, where the sequence at index `i` gets `i` as type id
+    Multi(Vec<InputSequence<'s>>),
+}
+
+impl<'s> EncodeInput<'s> {
+    /// Returns all the sequences of this input, in order
+    pub fn into_sequences(self) -> Vec<InputSequence<'s>> {
+        match self {
+            EncodeInput::Single(s1) => vec![s1],
+            EncodeInput::Dual(s1, s2) => vec![s1, s2],
+            EncodeInput::Multi(sequences) => sequences,
+        }
+    }
 }
 
 impl<'s, I: Into<InputSequence<'s>>> From<I> for EncodeInput<'s> {
@@ -229,6 +322,17 @@ where
     }
 }
 
+impl<'s, I1, I2, I3> From<(I1, I2, I3)> for EncodeInput<'s>
+where
+    I1: Into<InputSequence<'s>>,
+    I2: Into<InputSequence<'s>>,
+    I3: Into<InputSequence<'s>>,
+{
+    fn from(input: (I1, I2, I3)) -> Self {
+        EncodeInput::Multi(vec![input.0.into(), input.1.into(), input.2.into()])
+    }
+}
+
 #[derive(Debug)]
 pub struct BuilderError(String);
 
@@ -254,6 +358,8 @@ pub struct TokenizerBuilder<M, N, PT, PP, D> {
 
     truncation: Option<TruncationParams>,
     padding: Option<PaddingParams>,
+
+    chat_template: Option<ChatTemplate>,
 }
 
 impl<M, N, PT, PP, D> Default for TokenizerBuilder<M, N, PT, PP, D>
@@ -288,6 +394,7 @@ where
             added_vocabulary: AddedVocabulary::new(),
             truncation: None,
             padding: None,
+            chat_template: None,
         }
     }
 
@@ -308,6 +415,7 @@ where
             added_vocabulary: self.added_vocabulary,
             truncation: self.truncation,
             padding: self.padding,
+            chat_template: self.chat_template,
         })
     }
 
@@ -352,6 +460,12 @@ where
         self.padding = padding;
         self
     }
+
+    /// Set the chat template.
+    pub fn with_chat_template(mut self, chat_template: Option<ChatTemplate>) -> Self {
+        self.chat_template = chat_template;
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -407,6 +521,7 @@ where
             added_vocabulary: t.added_vocabulary,
             padding: t.padding,
             truncation: t.truncation,
+            chat_template: t.chat_template,
         })
     }
 }
@@ -447,6 +562,9 @@ pub struct TokenizerImpl<M, N, PT, PP, D> {
     // General processing parameters
     truncation: Option<TruncationParams>,
     padding: Option<PaddingParams>,
+
+    // Formatting of conversations
+    chat_template: Option<ChatTemplate>,
 }
 
 impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
@@ -470,6 +588,8 @@ where
 
             truncation: None,
             padding: None,
+
+            chat_template: None,
         }
     }
 
@@ -528,6 +648,12 @@ where
         &self.model
     }
 
+    /// Get a mutable reference to the model, to update its options (like the sampling
+    /// of a `Unigram` model for example)
+    pub fn get_model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
     /// Set the truncation parameters
     pub fn with_truncation(&mut self, trunc: Option<TruncationParams>) -> &mut Self {
         self.truncation = trunc;
@@ -560,6 +686,17 @@ where
         self.padding.as_mut()
     }
 
+    /// Set the chat template, used to format conversations in `encode_chat`
+    pub fn with_chat_template(&mut self, chat_template: Option<ChatTemplate>) -> &mut Self {
+        self.chat_template = chat_template;
+        self
+    }
+
+    /// Get the currently set chat template
+    pub fn get_chat_template(&self) -> Option<&ChatTemplate> {
+        self.chat_template.as_ref()
+    }
+
     /// Get the vocabulary
     pub fn get_vocab(&self, with_added_tokens: bool) -> HashMap<String, u32> {
         let mut final_vocab = self.model.get_vocab().clone();
@@ -603,8 +740,9 @@ where
         sequence: InputSequence,
         type_id: u32,
         offsets_type: OffsetType,
+        mut rng: Option<&mut ChaCha20Rng>,
     ) -> Result<Encoding> {
-        let encode = |is_pre_tokenized, subseq_idx, subseq| -> Result<Encoding> {
+        let mut encode = |is_pre_tokenized, subseq_idx, subseq| -> Result<Encoding> {
             let normalized = self
                 .added_vocabulary
                 .extract_and_normalize(self.normalizer.as_ref(), subseq);
@@ -618,6 +756,7 @@ where
                     None
                 },
                 offsets_type,
+                rng.as_deref_mut(),
             )?;
 
             Ok(subseq_encoding)
@@ -643,6 +782,57 @@ where
         }
     }
 
+    /// Encode a single sequence in its `n` best alternative encodings
+    fn encode_single_sequence_nbest(
+        &self,
+        sequence: InputSequence,
+        type_id: u32,
+        offsets_type: OffsetType,
+        n: usize,
+    ) -> Result<Vec<(Encoding, f64)>> {
+        let encode = |is_pre_tokenized, subseq_idx, subseq| -> Result<Vec<(Encoding, f64)>> {
+            let normalized = self
+                .added_vocabulary
+                .extract_and_normalize(self.normalizer.as_ref(), subseq);
+            let pre_tokenized = self.do_pre_tokenize(normalized)?;
+            self.do_tokenize_nbest(
+                pre_tokenized,
+                type_id,
+                if is_pre_tokenized {
+                    Some(subseq_idx as u32)
+                } else {
+                    None
+                },
+                offsets_type,
+                n,
+            )
+        };
+
+        let subsequences = match sequence {
+            InputSequence::PreTokenized(seq) => seq
+                .iter()
+                .enumerate()
+                .map(|(i, sequence)| encode(true, i, sequence))
+                .collect::<Result<Vec<_>>>()?,
+            InputSequence::PreTokenizedOwned(seq) => seq
+                .iter()
+                .enumerate()
+                .map(|(i, sequence)| encode(true, i, sequence))
+                .collect::<Result<Vec<_>>>()?,
+            InputSequence::PreTokenizedCow(seq) => seq
+                .iter()
+                .enumerate()
+                .map(|(i, sequence)| encode(true, i, sequence))
+                .collect::<Result<Vec<_>>>()?,
+            InputSequence::Raw(seq) => return encode(false, 0, seq.as_ref()),
+        };
+
+        Ok(nbest_combinations(subsequences, n)
+            .into_iter()
+            .map(|(encodings, score)| (encodings.into_iter().collect(), score))
+            .collect())
+    }
+
     /// Encode the given input. This method accepts both single sequences, as well as pair
     /// sequences. Also, a sequence can be a string, or already pre-tokenized input directly:
     ///
@@ -669,21 +859,8 @@ where
     where
         E: Into<EncodeInput<'s>>,
     {
-        // Extract sequences from the EncodeInput
-        let (sequence, pair) = match input.into() {
-            EncodeInput::Single(s1) => (s1, None),
-            EncodeInput::Dual(s1, s2) => (s1, Some(s2)),
-        };
-
-        // Encode each sequence
-        let encoding = self.encode_single_sequence(sequence, 0, OffsetType::Byte)?;
-        let pair_encoding = match pair {
-            Some(sequence) => Some(self.encode_single_sequence(sequence, 1, OffsetType::Byte)?),
-            None => None,
-        };
-
-        // And finally post process
-        self.post_process(encoding, pair_encoding, add_special_tokens)
+        let seed = self.model.next_seed();
+        self.encode_seeded_with_offsets(input, add_special_tokens, seed, 0, OffsetType::Byte)
     }
 
     /// Encode the given input, using offsets relative to chars instead of bytes.
@@ -713,21 +890,264 @@ where
     where
         E: Into<EncodeInput<'s>>,
     {
-        // Extract sequences from the EncodeInput
-        let (sequence, pair) = match input.into() {
-            EncodeInput::Single(s1) => (s1, None),
-            EncodeInput::Dual(s1, s2) => (s1, Some(s2)),
-        };
+        let seed = self.model.next_seed();
+        self.encode_seeded_with_offsets(input, add_special_tokens, seed, 0, OffsetType::Char)
+    }
 
-        // Encode each sequence
-        let encoding = self.encode_single_sequence(sequence, 0, OffsetType::Char)?;
-        let pair_encoding = match pair {
-            Some(sequence) => Some(self.encode_single_sequence(sequence, 1, OffsetType::Char)?),
-            None => None,
-        };
+    /// Encode the given input using the given `seed` for any randomness of the model (like
+    /// the BPE dropout), instead of the one of the model. The same input encoded with the
+    /// same seed always gives the same `Encoding`, on any platform.
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::bpe::BPE;
+    /// # let vocab = [("a".into(), 0), ("b".into(), 1), ("ab".into(), 2)].iter().cloned().collect();
+    /// # let merges = vec![("a".into(), "b".into())];
+    /// let bpe = BPE::builder()
+    ///     .vocab_and_merges(vocab, merges)
+    ///     .dropout(0.5)
+    ///     .build()
+    ///     .unwrap();
+    /// let tokenizer = Tokenizer::new(bpe);
+    ///
+    /// let encoding = tokenizer.encode_with_seed("abab", false, 42).unwrap();
+    /// assert_eq!(tokenizer.encode_with_seed("abab", false, 42).unwrap(), encoding);
+    /// ```
+    pub fn encode_with_seed<'s, E>(
+        &self,
+        input: E,
+        add_special_tokens: bool,
+        seed: u64,
+    ) -> Result<Encoding>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        self.encode_seeded_with_offsets(input, add_special_tokens, Some(seed), 0, OffsetType::Byte)
+    }
+
+    /// Encode the given input using the given `seed`, with offsets relative to chars instead
+    /// of bytes. See `encode_with_seed` for more details.
+    pub fn encode_char_offsets_with_seed<'s, E>(
+        &self,
+        input: E,
+        add_special_tokens: bool,
+        seed: u64,
+    ) -> Result<Encoding>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        self.encode_seeded_with_offsets(input, add_special_tokens, Some(seed), 0, OffsetType::Char)
+    }
+
+    /// Encode the given input, which is the one at `index` in its batch. With a `seed`, all
+    /// the words of the input draw from a single random number generator, built from the
+    /// seed and the index, so the result doesn't depend on the order of the encode calls.
+    fn encode_seeded_with_offsets<'s, E>(
+        &self,
+        input: E,
+        add_special_tokens: bool,
+        seed: Option<u64>,
+        index: usize,
+        offsets_type: OffsetType,
+    ) -> Result<Encoding>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        let mut rng = seed.map(|seed| seeded_rng(seed, index as u64));
+
+        // Encode each sequence of the EncodeInput
+        let encodings = input
+            .into()
+            .into_sequences()
+            .into_iter()
+            .enumerate()
+            .map(|(i, sequence)| {
+                self.encode_single_sequence(sequence, i as u32, offsets_type, rng.as_mut())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // And finally post process
+        self.post_process_multi(encodings, add_special_tokens)
+    }
+
+    /// Encode a conversation, given as a list of `(role, content)` messages, using the chat
+    /// template of this tokenizer. Returns the `Encoding`, along with a mask marking the tokens
+    /// of the turns of the masked roles (usually the assistant) with `1`, for loss masking.
+    ///
+    /// Each message is encoded without any post-processing, truncation or padding, as its own
+    /// sequence: `Encoding::token_to_sequence_index` gives the message of each token, whose
+    /// offsets are relative to the content of this message. When `add_generation_prompt` is
+    /// set, the generation prompt of the template ends the conversation, to prompt for a new
+    /// message.
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::wordlevel::WordLevel;
+    /// # use tokenizers::pre_tokenizers::whitespace::Whitespace;
+    /// # use tokenizers::processors::chat::ChatTemplate;
+    /// # use tokenizers::AddedToken;
+    /// let vocab = vec![("[UNK]", 0), ("Hello", 1), ("Hi", 2)]
+    ///     .into_iter()
+    ///     .map(|(t, i)| (t.to_string(), i))
+    ///     .collect();
+    /// let model = WordLevel::builder()
+    ///     .vocab(vocab)
+    ///     .unk_token("[UNK]".into())
+    ///     .build();
+    /// let mut tokenizer = Tokenizer::new(model);
+    /// tokenizer.with_pre_tokenizer(Whitespace::default());
+    /// tokenizer.add_special_tokens(&[AddedToken::from("<user>", true), AddedToken::from("<bot>", true)]);
+    ///
+    /// let template = ChatTemplate::builder()
+    ///     .try_role("user", "<user> $A", false).unwrap()
+    ///     .try_role("assistant", "<bot> $A", true).unwrap()
+    ///     .special_tokens(vec![("<user>", 3), ("<bot>", 4)])
+    ///     .build()
+    ///     .unwrap();
+    /// tokenizer.with_chat_template(Some(template));
+    ///
+    /// let messages = vec![("user", "Hello"), ("assistant", "Hi")];
+    /// let (encoding, mask) = tokenizer.encode_chat(&messages, false).unwrap();
+    /// assert_eq!(encoding.get_tokens(), ["<user>", "Hello", "<bot>", "Hi"]);
+    /// assert_eq!(mask, [0, 0, 0, 1]);
+    /// ```
+    pub fn encode_chat<R, C>(
+        &self,
+        messages: &[(R, C)],
+        add_generation_prompt: bool,
+    ) -> Result<(Encoding, Vec<u32>)>
+    where
+        R: AsRef<str>,
+        C: AsRef<str>,
+    {
+        self.encode_chat_with_offsets(messages, add_generation_prompt, OffsetType::Byte)
+    }
+
+    /// Encode a conversation, like `encode_chat`.
+    /// The offsets on the `Encoding` will be relative to chars instead of bytes.
+    pub fn encode_chat_char_offsets<R, C>(
+        &self,
+        messages: &[(R, C)],
+        add_generation_prompt: bool,
+    ) -> Result<(Encoding, Vec<u32>)>
+    where
+        R: AsRef<str>,
+        C: AsRef<str>,
+    {
+        self.encode_chat_with_offsets(messages, add_generation_prompt, OffsetType::Char)
+    }
+
+    fn encode_chat_with_offsets<R, C>(
+        &self,
+        messages: &[(R, C)],
+        add_generation_prompt: bool,
+        offsets_type: OffsetType,
+    ) -> Result<(Encoding, Vec<u32>)>
+    where
+        R: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let template = self
+            .chat_template
+            .as_ref()
+            .ok_or("This tokenizer has no chat template")?;
+
+        let mut rng = self.model.next_seed().map(|seed| seeded_rng(seed, 0));
+        let messages = messages
+            .iter()
+            .enumerate()
+            .map(|(i, (role, content))| {
+                let mut encoding = self.encode_single_sequence(
+                    content.as_ref().into(),
+                    0,
+                    offsets_type,
+                    rng.as_mut(),
+                )?;
+                encoding.set_sequence_id(i);
+                Ok((role, encoding))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        template.apply(messages, add_generation_prompt)
+    }
+
+    /// Encode the given input in its `n` best alternative encodings, along with their score
+    /// (higher is better). Each of these encodings goes through the whole pipeline, and
+    /// can be used just like the one returned by `encode`. Only the models that support
+    /// multiple tokenizations (like `Unigram` or `BPE`) can return more than one encoding.
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::unigram::Unigram;
+    /// let pieces = vec![
+    ///     ("<unk>".to_string(), 0.0),
+    ///     ("a".to_string(), -1.0),
+    ///     ("b".to_string(), -1.0),
+    ///     ("ab".to_string(), -1.5),
+    /// ];
+    /// let tokenizer = Tokenizer::new(Unigram::from(pieces, 0).unwrap());
+    ///
+    /// let nbest = tokenizer.encode_nbest("ab", 2, false).unwrap();
+    /// assert_eq!(nbest[0].0.get_tokens(), ["ab"]);
+    /// assert_eq!(nbest[1].0.get_tokens(), ["a", "b"]);
+    /// ```
+    pub fn encode_nbest<'s, E>(
+        &self,
+        input: E,
+        n: usize,
+        add_special_tokens: bool,
+    ) -> Result<Vec<(Encoding, f64)>>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        self.encode_nbest_with_offsets(input, n, add_special_tokens, OffsetType::Byte)
+    }
+
+    /// Encode the given input in its `n` best alternative encodings, using offsets relative
+    /// to chars instead of bytes. See `encode_nbest` for more details.
+    pub fn encode_nbest_char_offsets<'s, E>(
+        &self,
+        input: E,
+        n: usize,
+        add_special_tokens: bool,
+    ) -> Result<Vec<(Encoding, f64)>>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        self.encode_nbest_with_offsets(input, n, add_special_tokens, OffsetType::Char)
+    }
+
+    fn encode_nbest_with_offsets<'s, E>(
+        &self,
+        input: E,
+        n: usize,
+        add_special_tokens: bool,
+        offsets_type: OffsetType,
+    ) -> Result<Vec<(Encoding, f64)>>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        // Encode each sequence of the EncodeInput, and combine their alternatives
+        let alternatives = input
+            .into()
+            .into_sequences()
+            .into_iter()
+            .enumerate()
+            .map(|(i, sequence)| {
+                self.encode_single_sequence_nbest(sequence, i as u32, offsets_type, n)
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         // And finally post process
-        self.post_process(encoding, pair_encoding, add_special_tokens)
+        nbest_combinations(alternatives, n)
+            .into_iter()
+            .map(|(encodings, score)| {
+                Ok((
+                    self.post_process_multi(encodings, add_special_tokens)?,
+                    score,
+                ))
+            })
+            .collect()
     }
 
     /// Decode the given ids, back to a String
@@ -764,11 +1184,39 @@ where
         type_id: u32,
         word_idx: Option<u32>,
         offsets_type: OffsetType,
+        mut rng: Option<&mut ChaCha20Rng>,
     ) -> Result<Encoding> {
         let mut pretokenized: PreTokenizedString = pretokenized.into();
-        pretokenized.tokenize(|normalized| self.model.tokenize(normalized.get()))?;
+        pretokenized.tokenize(|normalized| match rng.as_deref_mut() {
+            Some(rng) => self.model.tokenize_with_rng(normalized.get(), rng),
+            None => self.model.tokenize(normalized.get()),
+        })?;
         pretokenized.into_encoding(word_idx, type_id, offsets_type)
     }
+
+    /// Same as `do_tokenize`, but keeping the `n` best alternative tokenizations
+    fn do_tokenize_nbest<P: Into<PreTokenizedString>>(
+        &self,
+        pretokenized: P,
+        type_id: u32,
+        word_idx: Option<u32>,
+        offsets_type: OffsetType,
+        n: usize,
+    ) -> Result<Vec<(Encoding, f64)>> {
+        let pretokenized: PreTokenizedString = pretokenized.into();
+        pretokenized
+            .tokenize_nbest(n, |normalized| {
+                self.model.tokenize_nbest(normalized.get(), n)
+            })?
+            .into_iter()
+            .map(|(pretokenized, score)| {
+                Ok((
+                    pretokenized.into_encoding(word_idx, type_id, offsets_type)?,
+                    score,
+                ))
+            })
+            .collect()
+    }
 }
 
 impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
@@ -829,6 +1277,27 @@ impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
 where
     PP: PostProcessor,
 {
+    /// The truncation applied by the post processing. The `BatchLongestCapped` padding
+    /// strategy caps the length of each encoding, like a truncation to this length.
+    fn truncation_params(&self) -> Option<TruncationParams> {
+        let cap = match self.padding.as_ref().map(|p| &p.strategy) {
+            Some(PaddingStrategy::BatchLongestCapped(cap)) => Some(*cap),
+            _ => None,
+        };
+        match (&self.truncation, cap) {
+            (Some(trunc), Some(cap)) => Some(TruncationParams {
+                max_length: std::cmp::min(trunc.max_length, cap),
+                ..trunc.clone()
+            }),
+            (Some(trunc), None) => Some(trunc.clone()),
+            (None, Some(cap)) => Some(TruncationParams {
+                max_length: cap,
+                ..Default::default()
+            }),
+            (None, None) => None,
+        }
+    }
+
     /// Post processing logic, handling the case where there is no PostProcessor set
     pub fn post_process(
         &self,
@@ -836,9 +1305,17 @@ where
         pair_encoding: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding> {
+        // 0. Each sequence keeps track of its window from now on
+        let mut encoding = encoding;
+        encoding.set_sequence_id(0);
+        let pair_encoding = pair_encoding.map(|mut pair| {
+            pair.set_sequence_id(1);
+            pair
+        });
+
         // 1. First we truncate if needed
         let (encoding, pair_encoding) = {
-            if let Some(trunc) = &self.truncation {
+            if let Some(trunc) = &self.truncation_params() {
                 let n_added_tokens = if let Some(processor) = &self.post_processor {
                     processor.added_tokens(pair_encoding.is_some())
                 } else {
@@ -847,7 +1324,10 @@ where
 
                 if add_special_tokens && n_added_tokens > 0 {
                     let params = TruncationParams {
-                        max_length: trunc.max_length - n_added_tokens,
+                        max_length: trunc
+                            .max_length
+                            .checked_sub(n_added_tokens)
+                            .ok_or(TruncationError::MaxLengthTooLow)?,
                         ..*trunc
                     };
                     truncate_encodings(encoding, pair_encoding, &params)?
@@ -877,6 +1357,71 @@ where
 
         Ok(final_encoding)
     }
+
+    /// Post processing of any number of encodings. One or two encodings are handled exactly
+    /// like with `post_process`, while more of them get truncated together, before being
+    /// processed by `PostProcessor::process_multi`.
+    pub fn post_process_multi(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        if encodings.len() <= 2 {
+            let mut encodings = encodings.into_iter();
+            let encoding = encodings
+                .next()
+                .ok_or("Post processing expected at least one encoding")?;
+            return self.post_process(encoding, encodings.next(), add_special_tokens);
+        }
+
+        // 0. Each sequence keeps track of its window from now on
+        let mut encodings = encodings;
+        for (i, encoding) in encodings.iter_mut().enumerate() {
+            encoding.set_sequence_id(i);
+        }
+
+        // 1. First we truncate if needed
+        let encodings = if let Some(trunc) = &self.truncation_params() {
+            let n_added_tokens = if let Some(processor) = &self.post_processor {
+                processor.added_tokens_multi(encodings.len())
+            } else {
+                0
+            };
+
+            if add_special_tokens && n_added_tokens > 0 {
+                let params = TruncationParams {
+                    max_length: trunc
+                        .max_length
+                        .checked_sub(n_added_tokens)
+                        .ok_or(TruncationError::MaxLengthTooLow)?,
+                    ..*trunc
+                };
+                truncate_multi_encodings(encodings, &params)?
+            } else {
+                truncate_multi_encodings(encodings, trunc)?
+            }
+        } else {
+            encodings
+        };
+
+        // 2. Then We post process
+        let final_encoding = if let Some(processor) = &self.post_processor {
+            processor.process_multi(encodings, add_special_tokens)?
+        } else {
+            PostProcessor::default_process_multi(encodings, add_special_tokens)?
+        };
+
+        // 3. Then we pad if needed
+        let [final_encoding] = if let Some(params) = &self.padding {
+            let mut arr = [final_encoding];
+            pad_encodings(&mut arr, params)?;
+            arr
+        } else {
+            [final_encoding]
+        };
+
+        Ok(final_encoding)
+    }
 }
 
 impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
@@ -887,6 +1432,27 @@ where
     PP: PostProcessor + Send + Sync,
     D: Decoder + Send + Sync,
 {
+    /// Encode all the inputs in parallel. With a `seed`, each input gets its own random
+    /// number generator, built from the seed and its index in the batch.
+    fn encode_batch_seeded_with_offsets<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+        seed: Option<u64>,
+        offsets_type: OffsetType,
+    ) -> Result<Vec<Encoding>>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        inputs
+            .into_maybe_par_iter()
+            .enumerate()
+            .map(|(i, input)| {
+                self.encode_seeded_with_offsets(input, add_special_tokens, seed, i, offsets_type)
+            })
+            .collect()
+    }
+
     /// Encode all the sentences in parallel, using multiple threads
     pub fn encode_batch<'s, E>(
         &self,
@@ -896,10 +1462,13 @@ where
     where
         E: Into<EncodeInput<'s>> + Send,
     {
-        let mut encodings = inputs
-            .into_maybe_par_iter()
-            .map(|input| self.encode(input, add_special_tokens))
-            .collect::<Result<Vec<Encoding>>>()?;
+        let seed = self.model.next_seed();
+        let mut encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Byte,
+        )?;
 
         if let Some(params) = &self.padding {
             // We do the padding here to make sure we handle the batch padding
@@ -919,10 +1488,13 @@ where
     where
         E: Into<EncodeInput<'s>> + Send,
     {
-        let mut encodings = inputs
-            .into_maybe_par_iter()
-            .map(|input| self.encode_char_offsets(input, add_special_tokens))
-            .collect::<Result<Vec<Encoding>>>()?;
+        let seed = self.model.next_seed();
+        let mut encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Char,
+        )?;
 
         if let Some(params) = &self.padding {
             // We do the padding here to make sure we handle the batch padding
@@ -932,6 +1504,157 @@ where
         Ok(encodings)
     }
 
+    /// Encode all the sentences in parallel, using the given `seed` for any randomness of
+    /// the model instead of the one of the model. Each input draws from its own random number
+    /// generator, built from the seed and its index in the batch, so the result is the same
+    /// whatever the number of threads.
+    pub fn encode_batch_with_seed<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+        seed: u64,
+    ) -> Result<Vec<Encoding>>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let mut encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            Some(seed),
+            OffsetType::Byte,
+        )?;
+
+        if let Some(params) = &self.padding {
+            pad_encodings(&mut encodings, params)?;
+        }
+
+        Ok(encodings)
+    }
+
+    /// Same as `encode_batch_with_seed`, but the offsets on each `Encoding` will be relative
+    /// to chars instead of bytes.
+    pub fn encode_batch_char_offsets_with_seed<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+        seed: u64,
+    ) -> Result<Vec<Encoding>>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let mut encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            Some(seed),
+            OffsetType::Char,
+        )?;
+
+        if let Some(params) = &self.padding {
+            pad_encodings(&mut encodings, params)?;
+        }
+
+        Ok(encodings)
+    }
+
+    /// Encode all the sentences in parallel, using multiple threads, and flatten the result:
+    /// the overflowing windows of each sample are moved into the batch, right after their
+    /// sample, and the whole batch gets padded together.
+    pub fn encode_batch_flattened<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+    ) -> Result<FlattenedBatch>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let seed = self.model.next_seed();
+        let encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Byte,
+        )?;
+
+        let mut batch = flatten_overflowing(encodings);
+        if let Some(params) = &self.padding {
+            pad_encodings(&mut batch.encodings, &params)?;
+        }
+
+        Ok(batch)
+    }
+
+    /// Same as `encode_batch_flattened`, but the offsets on each `Encoding` will be relative
+    /// to chars instead of bytes.
+    pub fn encode_batch_flattened_char_offsets<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+    ) -> Result<FlattenedBatch>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let seed = self.model.next_seed();
+        let encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Char,
+        )?;
+
+        let mut batch = flatten_overflowing(encodings);
+        if let Some(params) = &self.padding {
+            pad_encodings(&mut batch.encodings, &params)?;
+        }
+
+        Ok(batch)
+    }
+
+    /// Encode all the sentences in parallel, using multiple threads, and pack the padded
+    /// result into a `BatchEncoding`. The padding params of the tokenizer are used if any,
+    /// otherwise the batch gets padded to its longest encoding. The padding is done while
+    /// filling the arrays of the batch, without padding each `Encoding` first.
+    pub fn encode_batch_contiguous<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+    ) -> Result<BatchEncoding>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let seed = self.model.next_seed();
+        let encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Byte,
+        )?;
+
+        let default = PaddingParams::default();
+        BatchEncoding::from_unpadded(&encodings, self.padding.as_ref().unwrap_or(&default))
+    }
+
+    /// Same as `encode_batch_contiguous`, but the offsets will be relative to chars instead
+    /// of bytes.
+    pub fn encode_batch_contiguous_char_offsets<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+    ) -> Result<BatchEncoding>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        let seed = self.model.next_seed();
+        let encodings = self.encode_batch_seeded_with_offsets(
+            inputs,
+            add_special_tokens,
+            seed,
+            OffsetType::Char,
+        )?;
+
+        let default = PaddingParams::default();
+        BatchEncoding::from_unpadded(&encodings, self.padding.as_ref().unwrap_or(&default))
+    }
+
     /// Decode all sentences in parallel
     pub fn decode_batch(
         &self,
@@ -947,7 +1670,31 @@ where
             .collect()
     }
 
-    /// Train a model and replace our current Model, using the given Trainer
+    /// Count the words of the given sequence, as they are processed by the given Trainer
+    fn sequence_word_count<MN, T>(
+        &self,
+        trainer: &T,
+        sequence: String,
+    ) -> Result<HashMap<String, u32>>
+    where
+        T: Trainer<Model = MN> + Sync,
+        MN: Model,
+    {
+        let mut words = HashMap::new();
+        let normalized = self.do_normalize(sequence)?;
+        let pre_tokenized = self.do_pre_tokenize(normalized)?;
+        trainer.process_tokens(
+            &mut words,
+            pre_tokenized
+                .get_splits(OffsetReferential::Original, OffsetType::Byte)
+                .into_iter()
+                .map(|(s, _, _)| s.to_owned())
+                .collect(),
+        );
+        Ok(words)
+    }
+
+    /// Count the words of all the given files, using the given Trainer
     fn word_count<MN, T>(&self, trainer: &T, files: Vec<String>) -> Result<HashMap<String, u32>>
     where
         T: Trainer<Model = MN> + Sync,
@@ -976,36 +1723,20 @@ where
         let words = files
             .into_iter()
             .map(|filename| -> Result<HashMap<String, u32>> {
-                let file = File::open(filename)?;
-                let file = BufReader::with_capacity(max_read, file);
+                // Compressed files are decompressed on the fly, and the progress is
+                // measured on the bytes actually read from the file.
+                let pbar = progress.clone();
+                let file = compression::open_file(filename, max_read, move |read| {
+                    if let Some(pbar) = &pbar {
+                        pbar.inc(read as u64);
+                    }
+                })?;
                 // We read new lines using this API instead of the Lines Iterator
                 // on purpose. We want to keep the `\n` and potential `\r` between each lines
                 // We use an iterator to be able to chain with par_bridge.
                 file.lines_with_ending()
                     .maybe_par_bridge()
-                    .map_with(
-                        &progress,
-                        |progress, line| -> Result<HashMap<String, u32>> {
-                            let newline = line?;
-                            let b = newline.len();
-                            let mut words = HashMap::new();
-                            let normalized = self.do_normalize(newline)?;
-                            let pre_tokenized = self.do_pre_tokenize(normalized)?;
-                            trainer.process_tokens(
-                                &mut words,
-                                pre_tokenized
-                                    .get_splits(OffsetReferential::Original, OffsetType::Byte)
-                                    .into_iter()
-                                    .map(|(s, _, _)| s.to_owned())
-                                    .collect(),
-                            );
-
-                            if let Some(pbar) = progress {
-                                pbar.inc(b as u64);
-                            }
-                            Ok(words)
-                        },
-                    )
+                    .map(|line| self.sequence_word_count(trainer, line?))
                     .reduce(
                         || Ok(HashMap::new()),
                         |acc, ws| {
@@ -1032,7 +1763,75 @@ where
         Ok(words)
     }
 
-    /// Train a model and return a new Tokenizer, using the given Trainer
+    /// Count the words of all the sequences yielded by the given iterator, using the given
+    /// Trainer. The optional `length` is the number of sequences, used to show the progress.
+    fn word_count_from_iter<MN, T, I>(
+        &self,
+        trainer: &T,
+        sequences: I,
+        length: Option<usize>,
+    ) -> Result<HashMap<String, u32>>
+    where
+        T: Trainer<Model = MN> + Sync,
+        MN: Model,
+        I: Iterator<Item = String> + Send,
+    {
+        let progress = if trainer.should_show_progress() {
+            let progress =
+                match length {
+                    Some(len) => {
+                        let progress = ProgressBar::new(len as u64);
+                        progress.set_style(ProgressStyle::default_bar().template(
+                            "[{elapsed_precise}] {msg:<40!} {wide_bar} {pos:<9!}/{len:>9!}",
+                        ));
+                        progress.set_draw_delta(len as u64 / 100); // Redraw only every 2%
+                        progress
+                    }
+                    None => {
+                        let progress = ProgressBar::new_spinner();
+                        progress.set_style(
+                            ProgressStyle::default_spinner()
+                                .template("[{elapsed_precise}] {msg:<40!} {spinner} {pos:>9!}"),
+                        );
+                        progress
+                    }
+                };
+            progress.set_message("Reading sequences");
+            Some(progress)
+        } else {
+            None
+        };
+        let words = sequences
+            .maybe_par_bridge()
+            .map_with(
+                &progress,
+                |progress, sequence| -> Result<HashMap<String, u32>> {
+                    let words = self.sequence_word_count(trainer, sequence)?;
+
+                    if let Some(pbar) = progress {
+                        pbar.inc(1);
+                    }
+                    Ok(words)
+                },
+            )
+            .reduce(
+                || Ok(HashMap::new()),
+                |acc, ws| {
+                    let mut acc = acc?;
+                    for (k, v) in ws? {
+                        acc.entry(k).and_modify(|c| *c += v).or_insert(v);
+                    }
+                    Ok(acc)
+                },
+            )?;
+        if let Some(pbar) = progress {
+            pbar.finish();
+        }
+        Ok(words)
+    }
+
+    /// Train a model and return a new Tokenizer, using the given Trainer. The files can be
+    /// gzip or zstd compressed, in which case they get decompressed on the fly.
     pub fn train<T, TM>(
         self,
         trainer: &T,
@@ -1043,7 +1842,58 @@ where
         TM: Model,
     {
         let words = self.word_count(trainer, files)?;
+        self.train_on_words(trainer, words)
+    }
+
+    /// Train a model and return a new Tokenizer, using the given Trainer on all the sequences
+    /// yielded by the given iterator. The optional `length` is the number of sequences, used
+    /// to show the progress.
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::wordlevel::{WordLevel, WordLevelTrainer};
+    /// # use tokenizers::models::TrainerWrapper;
+    /// # use tokenizers::pre_tokenizers::whitespace::Whitespace;
+    /// let mut tokenizer = Tokenizer::new(WordLevel::default());
+    /// tokenizer.with_pre_tokenizer(Whitespace::default());
+    ///
+    /// let trainer: TrainerWrapper = WordLevelTrainer::builder()
+    ///     .show_progress(false)
+    ///     .build()
+    ///     .unwrap()
+    ///     .into();
+    /// let sequences = vec!["Hello there", "Hello you"];
+    /// tokenizer
+    ///     .train_from_iter_and_replace(&trainer, sequences.into_iter().map(String::from), Some(2))
+    ///     .unwrap();
+    /// // `<unk>`, `Hello`, `there` and `you`
+    /// assert_eq!(tokenizer.get_vocab_size(false), 4);
+    /// ```
+    pub fn train_from_iter<T, TM, I>(
+        self,
+        trainer: &T,
+        sequences: I,
+        length: Option<usize>,
+    ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
+    where
+        T: Trainer<Model = TM> + Sync,
+        TM: Model,
+        I: Iterator<Item = String> + Send,
+    {
+        let words = self.word_count_from_iter(trainer, sequences, length)?;
+        self.train_on_words(trainer, words)
+    }
 
+    /// Train a new model on the given word counts, and return a new Tokenizer with it
+    fn train_on_words<T, TM>(
+        self,
+        trainer: &T,
+        words: HashMap<String, u32>,
+    ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
+    where
+        T: Trainer<Model = TM> + Sync,
+        TM: Model,
+    {
         let (model, special_tokens) = trainer.train(words)?;
         let mut new_tok = TokenizerImpl {
             normalizer: self.normalizer,
@@ -1054,6 +1904,7 @@ where
             added_vocabulary: self.added_vocabulary,
             truncation: self.truncation,
             padding: self.padding,
+            chat_template: self.chat_template,
         };
 
         new_tok.add_special_tokens(&special_tokens);
@@ -1074,6 +1925,138 @@ where
 
         Ok(())
     }
+
+    /// Train a model and replace our current Model, using the given Trainer on all the
+    /// sequences yielded by the given iterator. See `train_from_iter` for more details.
+    pub fn train_from_iter_and_replace<T, I>(
+        &mut self,
+        trainer: &T,
+        sequences: I,
+        length: Option<usize>,
+    ) -> Result<()>
+    where
+        T: Trainer<Model = M> + Sync,
+        I: Iterator<Item = String> + Send,
+    {
+        let words = self.word_count_from_iter(trainer, sequences, length)?;
+
+        let (model, special_tokens) = trainer.train(words)?;
+        self.model = model;
+        self.add_special_tokens(&special_tokens);
+
+        Ok(())
+    }
+}
+
+impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
+where
+    M: Model + Send + Sync,
+    N: Normalizer + Serialize + Send + Sync,
+    PT: PreTokenizer + Serialize + Send + Sync,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    /// Identifies the way we process the sequences to count their words, which only depends
+    /// on the normalizer and the pre-tokenizer.
+    fn word_counts_fingerprint(&self) -> Result<String> {
+        Ok(serde_json::to_string(&(
+            &self.normalizer,
+            &self.pre_tokenizer,
+        ))?)
+    }
+
+    /// Count the words of the given files, as they would be counted to train a model with the
+    /// given Trainer. These counts can be saved, and then used with `train_from_counts` to
+    /// train multiple models without reading the files again.
+    pub fn count_words<T, MN>(&self, trainer: &T, files: Vec<String>) -> Result<WordCounts>
+    where
+        T: Trainer<Model = MN> + Sync,
+        MN: Model,
+    {
+        Ok(WordCounts::new(
+            self.word_counts_fingerprint()?,
+            self.word_count(trainer, files)?,
+        ))
+    }
+
+    /// Check that the given word counts were produced by our normalizer and pre-tokenizer
+    fn check_word_counts(&self, counts: &WordCounts) -> Result<()> {
+        let fingerprint = self.word_counts_fingerprint()?;
+        if counts.fingerprint != fingerprint {
+            return Err(Box::new(WordCountsError::FingerprintMismatch {
+                expected: fingerprint,
+                got: counts.fingerprint.clone(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Train a model and return a new Tokenizer, using the given Trainer on some word counts
+    /// produced by `count_words`. These counts must have been produced with the same
+    /// normalizer and pre-tokenizer.
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::wordlevel::{WordLevel, WordLevelTrainer};
+    /// # use tokenizers::models::TrainerWrapper;
+    /// # use tokenizers::tokenizer::WordCounts;
+    /// # use tokenizers::normalizers::utils::Lowercase;
+    /// let mut tokenizer = Tokenizer::new(WordLevel::default());
+    /// let trainer: TrainerWrapper = WordLevelTrainer::builder()
+    ///     .show_progress(false)
+    ///     .build()
+    ///     .unwrap()
+    ///     .into();
+    ///
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # std::fs::write(file.path(), "Hello\nhello\n").unwrap();
+    /// # let files = vec![file.path().to_str().unwrap().to_owned()];
+    /// let counts = tokenizer.count_words(&trainer, files).unwrap();
+    /// # let file = tempfile::NamedTempFile::new().unwrap();
+    /// # let path = file.path();
+    /// counts.save(path, false).unwrap();
+    ///
+    /// let counts = WordCounts::from_file(path).unwrap();
+    /// tokenizer
+    ///     .train_from_counts_and_replace(&trainer, counts.clone())
+    ///     .unwrap();
+    /// assert_eq!(tokenizer.get_vocab_size(false), 3);
+    ///
+    /// // A tokenizer with another normalizer can't use these counts
+    /// tokenizer.with_normalizer(Lowercase);
+    /// assert!(tokenizer.train_from_counts_and_replace(&trainer, counts).is_err());
+    /// ```
+    pub fn train_from_counts<T, TM>(
+        self,
+        trainer: &T,
+        counts: WordCounts,
+    ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
+    where
+        T: Trainer<Model = TM> + Sync,
+        TM: Model,
+    {
+        self.check_word_counts(&counts)?;
+        self.train_on_words(trainer, counts.counts)
+    }
+
+    /// Train a model and replace our current Model, using the given Trainer on some word
+    /// counts produced by `count_words`. See `train_from_counts` for more details.
+    pub fn train_from_counts_and_replace<T>(
+        &mut self,
+        trainer: &T,
+        counts: WordCounts,
+    ) -> Result<()>
+    where
+        T: Trainer<Model = M> + Sync,
+    {
+        self.check_word_counts(&counts)?;
+
+        let (model, special_tokens) = trainer.train(counts.counts)?;
+        self.model = model;
+        self.add_special_tokens(&special_tokens);
+
+        Ok(())
+    }
 }
 
 impl<M, N, PT, PP, D> std::str::FromStr for TokenizerImpl<M, N, PT, PP, D>
@@ -1,5 +1,6 @@
 use crate::pattern::Pattern;
 use crate::{Offsets, Result};
+use serde::{Deserialize, Serialize};
 use std::ops::{Bound, RangeBounds};
 use unicode_normalization_alignments::UnicodeNormalization;
 
@@ -89,7 +90,7 @@ where
 ///  - MergedWithPrevious => `[ "the-", "final-", "-", "countdown" ]`
 ///  - MergedWithNext => `[ "the", "-final", "-", "-countdown" ]`
 ///  - Contiguous => `[ "the", "-", "final", "--", "countdown" ]`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SplitDelimiterBehavior {
     Removed,
     Isolated,
@@ -98,6 +99,15 @@ pub enum SplitDelimiterBehavior {
     Contiguous,
 }
 
+/// A piece of the content used by `NormalizedString::replace_captures`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplacementPiece {
+    /// Some literal text
+    Text(String),
+    /// The text matched by the capture group with this index, if any
+    Group(usize),
+}
+
 /// A `NormalizedString` takes care of processing an "original" string to modify
 /// it and obtain a "normalized" string. It keeps both version of the string,
 /// alignments information between both and provides an interface to retrieve
@@ -309,6 +319,38 @@ impl NormalizedString {
         })
     }
 
+    /// Apply the given function to a slice of the current NormalizedString, and replace this
+    /// part of the normalized string with the result, while updating the alignments.
+    /// If the range is not on char boundaries, return an error
+    pub fn map_range<T, F>(&mut self, range: Range<T>, f: F) -> Result<()>
+    where
+        T: RangeBounds<usize> + Clone,
+        F: FnOnce(&mut NormalizedString) -> Result<()>,
+    {
+        let n_range = match self.validate_range(range) {
+            Some(Range::Normalized(range)) => Some(range),
+            Some(range) => self.convert_offsets(range),
+            None => None,
+        }
+        .ok_or("NormalizedString: the range to map must be on char boundaries")?;
+        let mut slice = self
+            .slice(Range::Normalized(n_range.clone()))
+            .ok_or("NormalizedString: the range to map must be on char boundaries")?;
+
+        f(&mut slice)?;
+
+        let shift = slice.original_shift - self.original_shift;
+        self.alignments.splice(
+            n_range.clone(),
+            slice
+                .alignments
+                .into_iter()
+                .map(|(start, end)| (start + shift, end + shift)),
+        );
+        self.normalized.replace_range(n_range, &slice.normalized);
+        Ok(())
+    }
+
     /// Applies transformations to the current normalized version of the string,
     /// while updating the alignments.
     /// This method expect an Iterator yielding each char of the new normalized string
@@ -593,6 +635,56 @@ impl NormalizedString {
         Ok(())
     }
 
+    /// Replace anything that matches the regex with the given content, in which the capture
+    /// groups get substituted. The characters coming from a capture group keep their own
+    /// alignments, while the literal text gets aligned with the whole match.
+    pub fn replace_captures(&mut self, regex: &onig::Regex, content: &[ReplacementPiece]) {
+        let mut normalized = String::with_capacity(self.normalized.len());
+        let mut alignments = Vec::with_capacity(self.alignments.len());
+        let mut prev = 0;
+        for captures in regex.captures_iter(&self.normalized) {
+            let (start, end) = match captures.pos(0) {
+                Some(pos) => pos,
+                None => continue,
+            };
+            normalized.push_str(&self.normalized[prev..start]);
+            alignments.extend_from_slice(&self.alignments[prev..start]);
+
+            let align = if start < end {
+                self.alignments[start..end]
+                    .iter()
+                    .fold((usize::MAX, 0), |(s, e), (start, end)| {
+                        (std::cmp::min(s, *start), std::cmp::max(e, *end))
+                    })
+            } else if start > 0 {
+                // An empty match is like an insertion, aligned with the previous char
+                self.alignments[start - 1]
+            } else {
+                (0, 0)
+            };
+            for piece in content {
+                match piece {
+                    ReplacementPiece::Text(text) => {
+                        normalized.push_str(text);
+                        alignments.extend(std::iter::repeat(align).take(text.len()));
+                    }
+                    ReplacementPiece::Group(group) => {
+                        if let Some((start, end)) = captures.pos(*group) {
+                            normalized.push_str(&self.normalized[start..end]);
+                            alignments.extend_from_slice(&self.alignments[start..end]);
+                        }
+                    }
+                }
+            }
+            prev = end;
+        }
+        normalized.push_str(&self.normalized[prev..]);
+        alignments.extend_from_slice(&self.alignments[prev..]);
+
+        self.normalized = normalized;
+        self.alignments = alignments;
+    }
+
     /// Clear the normalized part of the string
     pub fn clear(&mut self) -> usize {
         let len = self.len();
@@ -1392,6 +1484,62 @@ mod tests {
         assert_eq!(s.get(), "_Hello_friend_");
     }
 
+    #[test]
+    fn replace_captures() {
+        let content = vec![
+            ReplacementPiece::Group(2),
+            ReplacementPiece::Text("/".into()),
+            ReplacementPiece::Group(1),
+        ];
+        let re = onig::Regex::new(r"(\d+)-(\d+)").unwrap();
+        let mut s = NormalizedString::from("on 12-3!");
+        s.replace_captures(&re, &content);
+        assert_eq!(s.get(), "on 3/12!");
+        // Each digit keeps its own alignment, while the literal covers the whole match
+        assert_eq!(s.get_range_original(Range::Normalized(3..4)), Some("3"));
+        assert_eq!(s.get_range_original(Range::Normalized(4..5)), Some("12-3"));
+        assert_eq!(s.get_range_original(Range::Normalized(5..7)), Some("12"));
+        assert_eq!(s.get_range_original(Range::Normalized(7..8)), Some("!"));
+
+        // Collapse repeated chars
+        let re = onig::Regex::new(r"(\s)\s+").unwrap();
+        let mut s = NormalizedString::from("Hello   friend");
+        s.replace_captures(&re, &[ReplacementPiece::Group(1)]);
+        assert_eq!(s.get(), "Hello friend");
+        assert_eq!(s.get_range_original(Range::Normalized(5..6)), Some(" "));
+        assert_eq!(
+            s.get_range_original(Range::Normalized(6..12)),
+            Some("friend")
+        );
+    }
+
+    #[test]
+    fn map_range() {
+        let mut s = NormalizedString::from("Hello FRIEND ﬁle");
+        s.map_range(Range::Normalized(6..12), |n| {
+            n.lowercase();
+            Ok(())
+        })
+        .unwrap();
+        s.map_range(Range::Original(13..), |n| {
+            n.nfkc();
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(s.get(), "Hello friend file");
+        assert_eq!(
+            s.get_range_original(Range::Normalized(6..12)),
+            Some("FRIEND")
+        );
+        assert_eq!(s.get_range_original(Range::Normalized(13..15)), Some("ﬁ"));
+        assert_eq!(s.get_range_original(Range::Normalized(15..17)), Some("le"));
+
+        assert!(s.map_range(Range::Normalized(14..), |_| Ok(())).is_ok());
+        assert!(NormalizedString::from("ﬁ")
+            .map_range(Range::Normalized(1..), |_| Ok(()))
+            .is_err());
+    }
+
     #[test]
     fn split() {
         use SplitDelimiterBehavior::*;
@@ -1,3 +1,4 @@
+use crate::utils::nbest_combinations;
 use crate::{
     normalizer::Range, Encoding, NormalizedString, OffsetReferential, Offsets, Result, Token,
 };
@@ -114,9 +115,9 @@ impl PreTokenizedString {
 
     /// Tokenize all the splits that do not have attached `Tokens`, using the provided
     /// `tokenize` function
-    pub fn tokenize<F>(&mut self, tokenize: F) -> Result<()>
+    pub fn tokenize<F>(&mut self, mut tokenize: F) -> Result<()>
     where
-        F: Fn(&NormalizedString) -> Result<Vec<Token>>,
+        F: FnMut(&NormalizedString) -> Result<Vec<Token>>,
     {
         for split in self.splits.iter_mut().filter(|s| s.tokens.is_none()) {
             split.tokens = Some(tokenize(&split.normalized)?);
@@ -125,6 +126,37 @@ impl PreTokenizedString {
         Ok(())
     }
 
+    /// Tokenize all the splits that do not have attached `Tokens`, using the provided
+    /// `tokenize` function which returns scored alternatives for each of them.
+    /// This returns the `n` best combinations of these alternatives, as tokenized
+    /// `PreTokenizedString` along with their score.
+    pub fn tokenize_nbest<F>(&self, n: usize, tokenize: F) -> Result<Vec<(Self, f64)>>
+    where
+        F: Fn(&NormalizedString) -> Result<Vec<(Vec<Token>, f64)>>,
+    {
+        let alternatives = self
+            .splits
+            .iter()
+            .filter(|s| s.tokens.is_none())
+            .map(|split| tokenize(&split.normalized))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(nbest_combinations(alternatives, n)
+            .into_iter()
+            .map(|(tokens, score)| {
+                let mut pretokenized = self.clone();
+                let splits = pretokenized
+                    .splits
+                    .iter_mut()
+                    .filter(|s| s.tokens.is_none());
+                for (split, tokens) in splits.zip(tokens) {
+                    split.tokens = Some(tokens);
+                }
+                (pretokenized, score)
+            })
+            .collect())
+    }
+
     /// Transform the current `PreTokenizedString` into an `Encoding`.
     ///
     /// If a `word_idx` is provided, any word in the generated `Encoding`
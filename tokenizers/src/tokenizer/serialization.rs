@@ -24,7 +24,8 @@ where
     where
         S: Serializer,
     {
-        let mut tokenizer = serializer.serialize_struct("Tokenizer", 9)?;
+        let n_fields = if self.chat_template.is_some() { 10 } else { 9 };
+        let mut tokenizer = serializer.serialize_struct("Tokenizer", n_fields)?;
 
         // Start by adding the current version
         tokenizer.serialize_field("version", SERIALIZATION_VERSION)?;
@@ -43,6 +44,11 @@ where
         tokenizer.serialize_field("decoder", &self.decoder)?;
         tokenizer.serialize_field("model", &self.model)?;
 
+        // The chat template is only added when provided
+        if let Some(chat_template) = &self.chat_template {
+            tokenizer.serialize_field("chat_template", chat_template)?;
+        }
+
         tokenizer.end()
     }
 }
@@ -71,6 +77,7 @@ where
                 "post_processor",
                 "decoder",
                 "model",
+                "chat_template",
             ],
             TokenizerVisitor(
                 PhantomData,
@@ -143,6 +150,9 @@ where
                 "post_processor" => {
                     builder = builder.with_post_processor(map.next_value()?);
                 }
+                "chat_template" => {
+                    builder = builder.with_chat_template(map.next_value()?);
+                }
                 _ => {}
             };
         }
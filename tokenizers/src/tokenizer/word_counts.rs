@@ -0,0 +1,96 @@
+use crate::tokenizer::Result;
+use crate::utils::ordered_map;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{read_to_string, File};
+use std::io::prelude::*;
+use std::path::Path;
+
+/// The words of a corpus with their number of occurrences, as used to train a model.
+///
+/// Counting the words is usually the longest part of the training, so these counts can be
+/// saved, and used to train multiple models without reading the whole corpus again.
+/// They keep a `fingerprint` of the normalizer and pre-tokenizer that produced them, to avoid
+/// training a tokenizer that doesn't process its input in the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordCounts {
+    /// The serialized normalizer and pre-tokenizer that produced these counts
+    pub fingerprint: String,
+    /// The number of occurrences of each word
+    #[serde(serialize_with = "ordered_map")]
+    pub counts: HashMap<String, u32>,
+}
+
+impl WordCounts {
+    pub fn new(fingerprint: String, counts: HashMap<String, u32>) -> Self {
+        Self {
+            fingerprint,
+            counts,
+        }
+    }
+
+    /// Load the word counts saved at the given path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save these word counts at the given path
+    pub fn save<P: AsRef<Path>>(&self, path: P, pretty: bool) -> Result<()> {
+        let serialized = if pretty {
+            serde_json::to_string_pretty(self)?
+        } else {
+            serde_json::to_string(self)?
+        };
+
+        let mut file = File::create(path)?;
+        file.write_all(serialized.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum WordCountsError {
+    /// The word counts were produced by another normalizer or pre-tokenizer.
+    FingerprintMismatch { expected: String, got: String },
+}
+
+impl std::fmt::Display for WordCountsError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WordCountsError::FingerprintMismatch { expected, got } => write!(
+                fmt,
+                "Word counts error: The counts were produced by a different normalizer or \
+                pre-tokenizer (expected {}, got {})",
+                expected, got
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WordCountsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn save_and_load() {
+        let counts = WordCounts::new(
+            "[null,null]".into(),
+            vec![("b".to_string(), 1), ("a".to_string(), 2)]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(
+            serde_json::to_string(&counts).unwrap(),
+            r#"{"fingerprint":"[null,null]","counts":{"a":2,"b":1}}"#
+        );
+
+        let file = NamedTempFile::new().unwrap();
+        counts.save(file.path(), false).unwrap();
+        assert_eq!(WordCounts::from_file(file.path()).unwrap(), counts);
+    }
+}
@@ -0,0 +1,283 @@
+//! Grouping of the inputs by length, to build mini-batches with as little padding as possible.
+//!
+//! The inputs are read by chunks of `buffer_size`, encoded and sorted by length, before being
+//! split into batches that respect `max_tokens`, padding included. The batches of each chunk
+//! can then be shuffled, so that the lengths don't always come in the same order.
+use crate::parallelism::*;
+use crate::tokenizer::{
+    Decoder, EncodeInput, Encoding, Model, Normalizer, PostProcessor, PreTokenizer, Result,
+    TokenizerImpl,
+};
+use crate::utils::padding::{pad_encodings, padding_length, PaddingParams};
+use crate::utils::seeded_rng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha20Rng;
+use std::collections::VecDeque;
+use std::iter::Enumerate;
+
+#[derive(Debug, Clone)]
+pub struct BatchingParams {
+    /// The maximum number of tokens in a batch, padding included. A batch always contains
+    /// at least one input, even if it is longer than this.
+    pub max_tokens: usize,
+    /// The number of inputs encoded and sorted together before being split into batches
+    pub buffer_size: usize,
+    /// If provided, the batches of each buffer are shuffled using this seed
+    pub seed: Option<u64>,
+}
+
+impl Default for BatchingParams {
+    fn default() -> Self {
+        Self {
+            max_tokens: 4096,
+            buffer_size: 1000,
+            seed: None,
+        }
+    }
+}
+
+/// A padded mini-batch, with the index of each of its inputs in the original stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    pub indices: Vec<usize>,
+    pub encodings: Vec<Encoding>,
+}
+
+/// An iterator over the padded mini-batches of a stream of inputs, grouped by length
+pub struct LengthGroupedBatches<'t, I, M, N, PT, PP, D> {
+    tokenizer: &'t TokenizerImpl<M, N, PT, PP, D>,
+    inputs: Enumerate<I>,
+    add_special_tokens: bool,
+    params: BatchingParams,
+    padding: PaddingParams,
+    rng: Option<ChaCha20Rng>,
+    batches: VecDeque<Batch>,
+}
+
+/// Encode the given inputs with the tokenizer, and group them by length in padded batches.
+///
+/// The batches are padded with the padding params of the tokenizer if any, or to the longest
+/// encoding of each batch otherwise.
+pub fn length_grouped_batches<'s, 't, I, E, M, N, PT, PP, D>(
+    tokenizer: &'t TokenizerImpl<M, N, PT, PP, D>,
+    inputs: I,
+    add_special_tokens: bool,
+    params: BatchingParams,
+) -> LengthGroupedBatches<'t, I, M, N, PT, PP, D>
+where
+    I: Iterator<Item = E>,
+    E: Into<EncodeInput<'s>> + Send,
+    M: Model + Send + Sync,
+    N: Normalizer + Send + Sync,
+    PT: PreTokenizer + Send + Sync,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    LengthGroupedBatches {
+        tokenizer,
+        inputs: inputs.enumerate(),
+        add_special_tokens,
+        padding: tokenizer.get_padding().cloned().unwrap_or_default(),
+        rng: params.seed.map(|seed| seeded_rng(seed, 0)),
+        params,
+        batches: VecDeque::new(),
+    }
+}
+
+impl<'s, 't, I, E, M, N, PT, PP, D> LengthGroupedBatches<'t, I, M, N, PT, PP, D>
+where
+    I: Iterator<Item = E>,
+    E: Into<EncodeInput<'s>> + Send,
+    M: Model + Send + Sync,
+    N: Normalizer + Send + Sync,
+    PT: PreTokenizer + Send + Sync,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    /// Encode the next buffer of inputs, and split it into batches
+    fn fill_batches(&mut self) -> Result<()> {
+        let buffer = self
+            .inputs
+            .by_ref()
+            .take(std::cmp::max(self.params.buffer_size, 1))
+            .collect::<Vec<_>>();
+
+        let tokenizer = self.tokenizer;
+        let add_special_tokens = self.add_special_tokens;
+        let mut encodings = buffer
+            .into_maybe_par_iter()
+            .map(|(index, input)| Ok((index, tokenizer.encode(input, add_special_tokens)?)))
+            .collect::<Result<Vec<(usize, Encoding)>>>()?;
+        encodings.sort_by_key(|(index, encoding)| (encoding.len(), *index));
+
+        let mut batches = vec![];
+        let mut current: Vec<(usize, Encoding)> = vec![];
+        for (index, encoding) in encodings {
+            // Sorted by length, the current encoding is always the longest of the batch. The
+            // encodings longer than the padding length are left as is.
+            let length = std::cmp::max(
+                padding_length(encoding.len(), &self.padding),
+                encoding.len(),
+            );
+            if !current.is_empty() && (current.len() + 1) * length > self.params.max_tokens {
+                batches.push(std::mem::take(&mut current));
+            }
+            current.push((index, encoding));
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+
+        if let Some(rng) = self.rng.as_mut() {
+            batches.shuffle(rng);
+        }
+
+        for batch in batches {
+            let (indices, mut encodings): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+            pad_encodings(&mut encodings, &self.padding)?;
+            self.batches.push_back(Batch { indices, encodings });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'s, 't, I, E, M, N, PT, PP, D> Iterator for LengthGroupedBatches<'t, I, M, N, PT, PP, D>
+where
+    I: Iterator<Item = E>,
+    E: Into<EncodeInput<'s>> + Send,
+    M: Model + Send + Sync,
+    N: Normalizer + Send + Sync,
+    PT: PreTokenizer + Send + Sync,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    type Item = Result<Batch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batches.is_empty() {
+            if let Err(e) = self.fill_batches() {
+                return Some(Err(e));
+            }
+        }
+        self.batches.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wordlevel::WordLevel;
+    use crate::pre_tokenizers::whitespace::Whitespace;
+    use crate::Tokenizer;
+
+    fn get_tokenizer() -> Tokenizer {
+        let vocab = vec![("[UNK]", 0), ("a", 1)]
+            .into_iter()
+            .map(|(t, i)| (t.to_string(), i))
+            .collect();
+        let model = WordLevel::builder()
+            .vocab(vocab)
+            .unk_token("[UNK]".into())
+            .build();
+        let mut tokenizer = Tokenizer::new(model);
+        tokenizer.with_pre_tokenizer(Whitespace::default());
+        tokenizer
+    }
+
+    fn get_inputs() -> Vec<String> {
+        vec![5, 1, 4, 2, 1, 5, 3]
+            .into_iter()
+            .map(|n| vec!["a"; n].join(" "))
+            .collect()
+    }
+
+    #[test]
+    fn group_by_length() {
+        let tokenizer = get_tokenizer();
+        let params = BatchingParams {
+            max_tokens: 8,
+            buffer_size: 4,
+            seed: None,
+        };
+
+        let batches = length_grouped_batches(&tokenizer, get_inputs().into_iter(), true, params)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let indices = batches
+            .iter()
+            .map(|b| b.indices.clone())
+            .collect::<Vec<_>>();
+        // The first buffer has the lengths [5, 1, 4, 2], and the second one [1, 5, 3]
+        assert_eq!(
+            indices,
+            vec![vec![1, 3], vec![2], vec![0], vec![4, 6], vec![5]]
+        );
+        for batch in &batches {
+            let len = batch.encodings[0].len();
+            assert!(batch.encodings.iter().all(|e| e.len() == len));
+        }
+        assert_eq!(batches[0].encodings[0].get_attention_mask(), &[1, 0]);
+    }
+
+    #[test]
+    fn shuffle_with_seed() {
+        let tokenizer = get_tokenizer();
+        let params = BatchingParams {
+            max_tokens: 1,
+            buffer_size: 7,
+            seed: Some(42),
+        };
+
+        let get_indices = |params: BatchingParams| {
+            length_grouped_batches(&tokenizer, get_inputs().into_iter(), true, params)
+                .flat_map(|b| b.unwrap().indices)
+                .collect::<Vec<_>>()
+        };
+        let shuffled = get_indices(params.clone());
+        assert_eq!(shuffled, get_indices(params.clone()));
+
+        // Without the seed, the inputs come sorted by length
+        let unshuffled = get_indices(BatchingParams {
+            seed: None,
+            ..params
+        });
+        assert_eq!(unshuffled, vec![1, 4, 3, 6, 2, 0, 5]);
+        assert_ne!(shuffled, unshuffled);
+
+        let mut sorted = shuffled.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn max_tokens_with_padding() {
+        let mut tokenizer = get_tokenizer();
+        tokenizer.with_padding(Some(PaddingParams {
+            pad_to_multiple_of: Some(4),
+            ..Default::default()
+        }));
+        let params = BatchingParams {
+            max_tokens: 8,
+            buffer_size: 7,
+            seed: None,
+        };
+
+        let batches = length_grouped_batches(&tokenizer, get_inputs().into_iter(), true, params)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let indices = batches
+            .iter()
+            .map(|b| b.indices.clone())
+            .collect::<Vec<_>>();
+        // The lengths get padded to 4 or 8, which leaves room for two inputs at most
+        assert_eq!(
+            indices,
+            vec![vec![1, 2], vec![3, 4], vec![6], vec![0], vec![5]]
+        );
+        for batch in &batches {
+            let len = batch.encodings[0].len();
+            assert!(batch.encodings.len() * len <= 8);
+        }
+    }
+}
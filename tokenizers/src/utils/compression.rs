@@ -0,0 +1,136 @@
+//! Transparent decompression of the files we read, detecting their compression format
+//! from their first bytes.
+use flate2::bufread::MultiGzDecoder;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// The compression formats we can detect and decompress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detect the compression format from the magic bytes at the start of the given data
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// A reader that calls the given callback with the number of bytes of each read
+struct CallbackReader<R, F> {
+    inner: R,
+    callback: F,
+}
+
+impl<R: Read, F: FnMut(usize)> Read for CallbackReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        (self.callback)(read);
+        Ok(read)
+    }
+}
+
+/// Open the given file for reading, and decompress its content on the fly if it is gzip or
+/// zstd compressed. The given `on_read` callback receives the number of bytes read from the
+/// file itself, which are the compressed ones for a compressed file.
+pub fn open_file<P, F>(path: P, capacity: usize, on_read: F) -> io::Result<Box<dyn BufRead + Send>>
+where
+    P: AsRef<Path>,
+    F: FnMut(usize) + Send + 'static,
+{
+    let file = CallbackReader {
+        inner: File::open(path)?,
+        callback: on_read,
+    };
+    let mut file = BufReader::with_capacity(capacity, file);
+
+    // Peek at the first bytes to detect the compression, without consuming them
+    let compression = Compression::detect(file.fill_buf()?);
+    Ok(match compression {
+        Compression::None => Box::new(file),
+        Compression::Gzip => Box::new(BufReader::with_capacity(
+            capacity,
+            MultiGzDecoder::new(file),
+        )),
+        Compression::Zstd => Box::new(BufReader::with_capacity(
+            capacity,
+            zstd::stream::read::Decoder::with_buffer(file)?,
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use std::io::Write;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tempfile::NamedTempFile;
+
+    const CONTENT: &str = "Hello there\nGeneral Kenobi\r\n";
+
+    fn read_all(file: &NamedTempFile) -> (String, usize) {
+        let read = Arc::new(AtomicUsize::new(0));
+        let r = read.clone();
+        let mut reader = open_file(file.path(), 1024, move |n| {
+            r.fetch_add(n, Ordering::SeqCst);
+        })
+        .unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        let read = read.load(Ordering::SeqCst);
+        (content, read)
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Compression::detect(b"Hello"), Compression::None);
+        assert_eq!(Compression::detect(b""), Compression::None);
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+        assert_eq!(
+            Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]),
+            Compression::Zstd
+        );
+    }
+
+    #[test]
+    fn plain_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(CONTENT.as_bytes()).unwrap();
+
+        assert_eq!(read_all(&file), (CONTENT.to_owned(), CONTENT.len()));
+    }
+
+    #[test]
+    fn gzip_file() {
+        let mut encoder = GzEncoder::new(vec![], GzCompression::default());
+        encoder.write_all(CONTENT.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&compressed).unwrap();
+
+        assert_eq!(read_all(&file), (CONTENT.to_owned(), compressed.len()));
+    }
+
+    #[test]
+    fn zstd_file() {
+        let compressed = zstd::stream::encode_all(CONTENT.as_bytes(), 0).unwrap();
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&compressed).unwrap();
+
+        assert_eq!(read_all(&file), (CONTENT.to_owned(), compressed.len()));
+    }
+}
@@ -1,11 +1,17 @@
+pub mod batching;
 pub mod cache;
+pub mod compression;
 pub mod iter;
 pub mod padding;
 pub mod parallelism;
 pub mod truncation;
 
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{self, AtomicU64};
 
 pub fn ordered_map<S, K, V>(
     value: &HashMap<K, V>,
@@ -20,6 +26,91 @@ where
     ordered.serialize(serializer)
 }
 
+/// Combine the given lists of scored candidates, by picking one candidate in each list,
+/// and keep the `n` best combinations. The score of a combination is the sum of the scores
+/// of its candidates.
+pub(crate) fn nbest_combinations<T: Clone>(
+    lists: Vec<Vec<(T, f64)>>,
+    n: usize,
+) -> Vec<(Vec<T>, f64)> {
+    let mut combinations: Vec<(Vec<T>, f64)> = vec![(vec![], 0.0)];
+    combinations.truncate(n);
+
+    for list in lists {
+        let mut next = Vec::with_capacity(combinations.len() * list.len());
+        for (combination, score) in &combinations {
+            for (candidate, candidate_score) in &list {
+                let mut combination = combination.clone();
+                combination.push(candidate.clone());
+                next.push((combination, score + candidate_score));
+            }
+        }
+        next.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        next.truncate(n);
+        combinations = next;
+    }
+
+    combinations
+}
+
+/// Build the random number generator used to encode the input at `index` of a batch, with
+/// the given `seed`. ChaCha generates the same numbers on every platform, so a given seed
+/// always leads to the same results.
+pub(crate) fn seeded_rng(seed: u64, index: u64) -> ChaCha20Rng {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    rng.set_stream(index);
+    rng
+}
+
+/// The seeds of the successive encode calls of a model, drawn from the seed of the model.
+/// Each call thus gets a different segmentation, while a fresh model with the same seed
+/// replays the exact same sequence of segmentations.
+pub(crate) struct SeedSequence {
+    seed: u64,
+    calls: AtomicU64,
+}
+
+impl SeedSequence {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            calls: AtomicU64::new(0),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Draw the seed of the next encode call
+    pub fn next_seed(&self) -> u64 {
+        let call = self.calls.fetch_add(1, atomic::Ordering::Relaxed);
+        seeded_rng(self.seed, call).next_u64()
+    }
+}
+
+impl Clone for SeedSequence {
+    // The clone continues the sequence from where it currently is
+    fn clone(&self) -> Self {
+        Self {
+            seed: self.seed,
+            calls: AtomicU64::new(self.calls.load(atomic::Ordering::Relaxed)),
+        }
+    }
+}
+
+impl PartialEq for SeedSequence {
+    fn eq(&self, other: &Self) -> bool {
+        self.seed == other.seed
+    }
+}
+
+impl std::fmt::Debug for SeedSequence {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.seed.fmt(fmt)
+    }
+}
+
 #[macro_use]
 macro_rules! impl_enum_from (
     ($from_ty:ty, $enum:ty, $variant:ident) => {
@@ -73,3 +164,26 @@ macro_rules! impl_serde_unit_struct (
         }
     }
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nbest_combinations() {
+        let lists = vec![
+            vec![("a", -1.0), ("b", -2.0)],
+            vec![("c", -0.5), ("d", -3.0)],
+        ];
+        assert_eq!(
+            nbest_combinations(lists.clone(), 3),
+            vec![
+                (vec!["a", "c"], -1.5),
+                (vec!["b", "c"], -2.5),
+                (vec!["a", "d"], -4.0),
+            ]
+        );
+        assert_eq!(nbest_combinations(lists, 0), vec![]);
+        assert_eq!(nbest_combinations::<&str>(vec![], 2), vec![(vec![], 0.0)]);
+    }
+}
@@ -45,6 +45,13 @@ impl Default for PaddingParams {
 pub enum PaddingStrategy {
     BatchLongest,
     Fixed(usize),
+    /// Pad to the smallest of these lengths that fits the longest encoding of the batch, or
+    /// to the longest encoding if none of them fits
+    Buckets(Vec<usize>),
+    /// Pad to the longest encoding of the batch, but never beyond the given length. The
+    /// `Tokenizer` truncates the longer encodings to it before the post-processing, like with
+    /// a `TruncationParams::max_length`.
+    BatchLongestCapped(usize),
 }
 
 pub fn pad_encodings(encodings: &mut [Encoding], params: &PaddingParams) -> Result<()> {
@@ -52,20 +59,12 @@ pub fn pad_encodings(encodings: &mut [Encoding], params: &PaddingParams) -> Resu
         return Ok(());
     }
 
-    let mut pad_length = match params.strategy {
-        PaddingStrategy::Fixed(size) => size,
-        PaddingStrategy::BatchLongest => encodings
-            .maybe_par_iter()
-            .map(|e| e.get_ids().len())
-            .max()
-            .unwrap(),
-    };
-
-    if let Some(multiple) = params.pad_to_multiple_of {
-        if multiple > 0 && pad_length % multiple > 0 {
-            pad_length += multiple - pad_length % multiple;
-        }
-    }
+    let longest = encodings
+        .maybe_par_iter()
+        .map(|e| e.get_ids().len())
+        .max()
+        .unwrap();
+    let pad_length = padding_length(longest, params);
 
     encodings.maybe_par_iter_mut().for_each(|encoding| {
         encoding.pad(
@@ -80,10 +79,37 @@ pub fn pad_encodings(encodings: &mut [Encoding], params: &PaddingParams) -> Resu
     Ok(())
 }
 
+/// The length to which a batch gets padded, given the length of its longest encoding
+pub(crate) fn padding_length(longest: usize, params: &PaddingParams) -> usize {
+    let mut pad_length = match &params.strategy {
+        PaddingStrategy::Fixed(size) => *size,
+        PaddingStrategy::BatchLongest => longest,
+        PaddingStrategy::Buckets(buckets) => buckets
+            .iter()
+            .filter(|bucket| **bucket >= longest)
+            .min()
+            .copied()
+            .unwrap_or(longest),
+        PaddingStrategy::BatchLongestCapped(cap) => std::cmp::min(longest, *cap),
+    };
+
+    if let Some(multiple) = params.pad_to_multiple_of {
+        if multiple > 0 && pad_length % multiple > 0 {
+            pad_length += multiple - pad_length % multiple;
+        }
+    }
+
+    if let PaddingStrategy::BatchLongestCapped(cap) = params.strategy {
+        pad_length = std::cmp::min(pad_length, cap);
+    }
+
+    pad_length
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tokenizer::Encoding;
+    use crate::tokenizer::{Encoding, Token};
 
     #[test]
     fn pad_to_multiple() {
@@ -136,4 +162,71 @@ mod tests {
         params.pad_to_multiple_of = Some(0);
         pad_encodings(&mut encodings, &params).unwrap();
     }
+
+    fn get_batch(lengths: &[u32]) -> Vec<Encoding> {
+        lengths
+            .iter()
+            .map(|len| {
+                Encoding::from_tokens(
+                    (0..*len)
+                        .map(|i| Token::new(i, i.to_string(), (0, 0)))
+                        .collect(),
+                    0,
+                )
+            })
+            .collect()
+    }
+
+    fn lengths(encodings: &[Encoding]) -> Vec<usize> {
+        encodings.iter().map(|e| e.len()).collect()
+    }
+
+    #[test]
+    fn pad_to_buckets() {
+        let mut params = PaddingParams {
+            strategy: PaddingStrategy::Buckets(vec![16, 4, 8]),
+            ..Default::default()
+        };
+
+        let mut encodings = get_batch(&[3, 5]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![8, 8]);
+
+        let mut encodings = get_batch(&[4, 1]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![4, 4]);
+
+        // Too long for any bucket
+        let mut encodings = get_batch(&[17, 1]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![17, 17]);
+
+        params.pad_to_multiple_of = Some(6);
+        let mut encodings = get_batch(&[3, 5]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![12, 12]);
+    }
+
+    #[test]
+    fn pad_batch_longest_capped() {
+        let mut params = PaddingParams {
+            strategy: PaddingStrategy::BatchLongestCapped(6),
+            ..Default::default()
+        };
+
+        let mut encodings = get_batch(&[3, 5]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![5, 5]);
+
+        // The longer encodings are left as is, the tokenizer truncates them beforehand
+        let mut encodings = get_batch(&[3, 14]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![6, 14]);
+
+        // Never beyond the cap, even with a multiple
+        params.pad_to_multiple_of = Some(4);
+        let mut encodings = get_batch(&[3, 5]);
+        pad_encodings(&mut encodings, &params).unwrap();
+        assert_eq!(lengths(&encodings), vec![6, 6]);
+    }
 }
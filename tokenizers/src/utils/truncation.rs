@@ -8,6 +8,12 @@ pub struct TruncationParams {
     pub max_length: usize,
     pub strategy: TruncationStrategy,
     pub stride: usize,
+    #[serde(default)]
+    pub direction: TruncationDirection,
+    /// Whether to move each cut to a word boundary, to avoid splitting the tokens of a word
+    /// between the truncated sequence and its overflowing windows
+    #[serde(default)]
+    pub word_boundaries: bool,
 }
 
 impl Default for TruncationParams {
@@ -16,6 +22,32 @@ impl Default for TruncationParams {
             max_length: 512,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
+        }
+    }
+}
+
+/// The side from which the tokens are removed when truncating.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TruncationDirection {
+    /// Remove the first tokens, keeping the last ones
+    Left,
+    /// Remove the last tokens, keeping the first ones
+    Right,
+}
+
+impl Default for TruncationDirection {
+    fn default() -> Self {
+        TruncationDirection::Right
+    }
+}
+
+impl std::convert::AsRef<str> for TruncationDirection {
+    fn as_ref(&self) -> &str {
+        match self {
+            TruncationDirection::Left => "left",
+            TruncationDirection::Right => "right",
         }
     }
 }
@@ -55,6 +87,12 @@ pub enum TruncationStrategy {
     LongestFirst,
     OnlyFirst,
     OnlySecond,
+    /// Distribute the length like `LongestFirst`, but truncate each sequence in its middle,
+    /// keeping its first `head` tokens and as many of its last tokens as possible. The
+    /// `direction` is ignored with this strategy.
+    HeadTail {
+        head: usize,
+    },
 }
 
 impl std::convert::AsRef<str> for TruncationStrategy {
@@ -63,19 +101,35 @@ impl std::convert::AsRef<str> for TruncationStrategy {
             TruncationStrategy::LongestFirst => "longest_first",
             TruncationStrategy::OnlyFirst => "only_first",
             TruncationStrategy::OnlySecond => "only_second",
+            TruncationStrategy::HeadTail { .. } => "head_tail",
         }
     }
 }
 
+/// Truncate the given encoding to `max_len`, as specified by the given params
+fn truncate_encoding(encoding: &mut Encoding, max_len: usize, params: &TruncationParams) {
+    match params.strategy {
+        TruncationStrategy::HeadTail { head } => {
+            encoding.truncate_head_tail(head, max_len, params.stride, params.word_boundaries)
+        }
+        _ => encoding.truncate_with_direction(
+            max_len,
+            params.stride,
+            params.direction,
+            params.word_boundaries,
+        ),
+    }
+}
+
 pub fn truncate_encodings(
     mut encoding: Encoding,
     mut pair_encoding: Option<Encoding>,
     params: &TruncationParams,
 ) -> Result<(Encoding, Option<Encoding>)> {
     if params.max_length == 0 {
-        encoding.truncate(0, params.stride);
+        truncate_encoding(&mut encoding, 0, params);
         if let Some(other_encoding) = pair_encoding.as_mut() {
-            other_encoding.truncate(0, params.stride);
+            truncate_encoding(other_encoding, 0, params);
         }
         return Ok((encoding, pair_encoding));
     }
@@ -92,7 +146,7 @@ pub fn truncate_encodings(
     };
 
     match params.strategy {
-        TruncationStrategy::LongestFirst => {
+        TruncationStrategy::LongestFirst | TruncationStrategy::HeadTail { .. } => {
             if let Some(other_encoding) = pair_encoding.as_mut() {
                 // Assuming n1 <= n2, there are 3 cases
                 // Case 1:
@@ -135,10 +189,10 @@ pub fn truncate_encodings(
                 if swap {
                     mem::swap(&mut n1, &mut n2);
                 }
-                encoding.truncate(n1, params.stride);
-                other_encoding.truncate(n2, params.stride);
+                truncate_encoding(&mut encoding, n1, params);
+                truncate_encoding(other_encoding, n2, params);
             } else {
-                encoding.truncate(total_length - to_remove, params.stride);
+                truncate_encoding(&mut encoding, total_length - to_remove, params);
             }
         }
         TruncationStrategy::OnlyFirst | TruncationStrategy::OnlySecond => {
@@ -152,7 +206,7 @@ pub fn truncate_encodings(
 
             let target_len = target.get_ids().len();
             if target_len > to_remove {
-                target.truncate(target_len - to_remove, params.stride);
+                truncate_encoding(target, target_len - to_remove, params);
             } else {
                 return Err(Box::new(TruncationError::SequenceTooShort));
             }
@@ -161,6 +215,130 @@ pub fn truncate_encodings(
     Ok((encoding, pair_encoding))
 }
 
+/// Truncate any number of encodings so that their total length respects `max_length`.
+///
+/// With `LongestFirst`, the budget is distributed across all the sequences: the shortest ones
+/// are kept entirely, while the longest ones are truncated to the same length, the remainder
+/// going to the longest of them.
+pub fn truncate_multi_encodings(
+    mut encodings: Vec<Encoding>,
+    params: &TruncationParams,
+) -> Result<Vec<Encoding>> {
+    if params.max_length == 0 {
+        for encoding in encodings.iter_mut() {
+            truncate_encoding(encoding, 0, params);
+        }
+        return Ok(encodings);
+    }
+
+    let lengths = encodings
+        .iter()
+        .map(|e| e.get_ids().len())
+        .collect::<Vec<_>>();
+    let total_length: usize = lengths.iter().sum();
+    let to_remove = if total_length > params.max_length {
+        total_length - params.max_length
+    } else {
+        return Ok(encodings);
+    };
+
+    match params.strategy {
+        TruncationStrategy::LongestFirst | TruncationStrategy::HeadTail { .. } => {
+            // Find the largest length `cap` such that truncating all the sequences
+            // to `cap` respects the max length
+            let capped_length =
+                |cap: usize| -> usize { lengths.iter().map(|l| cmp::min(*l, cap)).sum() };
+            let (mut low, mut high) = (0, params.max_length);
+            while low < high {
+                let cap = (low + high + 1) / 2;
+                if capped_length(cap) <= params.max_length {
+                    low = cap;
+                } else {
+                    high = cap - 1;
+                }
+            }
+            let cap = low;
+            let mut remaining = params.max_length - capped_length(cap);
+
+            // Then give the remaining tokens to the longest sequences, the last ones first
+            // when they have the same length, like with a pair
+            let mut order = (0..lengths.len()).collect::<Vec<_>>();
+            order.sort_by_key(|i| (cmp::Reverse(lengths[*i]), cmp::Reverse(*i)));
+            let mut targets = lengths
+                .iter()
+                .map(|l| cmp::min(*l, cap))
+                .collect::<Vec<_>>();
+            for i in order {
+                if remaining == 0 {
+                    break;
+                }
+                if lengths[i] > cap {
+                    targets[i] += 1;
+                    remaining -= 1;
+                }
+            }
+
+            for (encoding, target) in encodings.iter_mut().zip(targets) {
+                truncate_encoding(encoding, target, params);
+            }
+        }
+        TruncationStrategy::OnlyFirst | TruncationStrategy::OnlySecond => {
+            let index = if params.strategy == TruncationStrategy::OnlyFirst {
+                0
+            } else {
+                1
+            };
+            let target = encodings
+                .get_mut(index)
+                .ok_or_else(|| Box::new(TruncationError::SecondSequenceNotProvided))?;
+
+            let target_len = target.get_ids().len();
+            if target_len > to_remove {
+                truncate_encoding(target, target_len - to_remove, params);
+            } else {
+                return Err(Box::new(TruncationError::SequenceTooShort));
+            }
+        }
+    }
+    Ok(encodings)
+}
+
+/// A batch of encodings where the overflowing windows of each sample have been moved into
+/// the batch itself, right after their sample, keeping track of the sample they come from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlattenedBatch {
+    /// The encodings of the samples, each one followed by its overflowing windows
+    pub encodings: Vec<Encoding>,
+    /// The index of the sample of each encoding
+    pub overflow_to_sample_mapping: Vec<usize>,
+}
+
+impl FlattenedBatch {
+    /// The index of the first token of each encoding in the sequence with the given id of
+    /// its sample
+    pub fn window_starts(&self, sequence_id: usize) -> Vec<usize> {
+        self.encodings
+            .iter()
+            .map(|e| e.get_window_start(sequence_id))
+            .collect()
+    }
+}
+
+/// Flatten the given batch of encodings, moving their overflowing windows into the batch
+pub fn flatten_overflowing(encodings: Vec<Encoding>) -> FlattenedBatch {
+    let mut batch = FlattenedBatch::default();
+    for (sample, mut encoding) in encodings.into_iter().enumerate() {
+        let overflowing = encoding.take_overflowing();
+        batch.overflow_to_sample_mapping.push(sample);
+        batch.encodings.push(encoding);
+        for window in overflowing {
+            batch.overflow_to_sample_mapping.push(sample);
+            batch.encodings.push(window);
+        }
+    }
+    batch
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +450,8 @@ mod tests {
             max_length: 7,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
         };
 
         truncate_and_assert(get_empty(), get_empty(), &params, 0, 0);
@@ -301,10 +481,167 @@ mod tests {
             max_length: 0,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
         };
 
         truncate_and_assert(get_empty(), get_short(), &params, 0, 0);
         truncate_and_assert(get_medium(), get_medium(), &params, 0, 0);
         truncate_and_assert(get_long(), get_long(), &params, 0, 0);
     }
+
+    fn truncate_multi_and_assert(
+        encodings: Vec<Encoding>,
+        params: &TruncationParams,
+        lengths: Vec<usize>,
+    ) {
+        let encodings = truncate_multi_encodings(encodings, params).unwrap();
+        assert_eq!(
+            encodings
+                .iter()
+                .map(|e| e.get_ids().len())
+                .collect::<Vec<_>>(),
+            lengths
+        );
+    }
+
+    #[test]
+    fn truncate_multi_encodings_longest_first() {
+        let params = TruncationParams {
+            max_length: 7,
+            strategy: TruncationStrategy::LongestFirst,
+            stride: 0,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
+        };
+
+        truncate_multi_and_assert(
+            vec![get_short(), get_empty(), get_medium()],
+            &params,
+            vec![2, 0, 4],
+        );
+        truncate_multi_and_assert(
+            vec![get_short(), get_long(), get_medium()],
+            &params,
+            vec![2, 3, 2],
+        );
+        truncate_multi_and_assert(
+            vec![get_long(), get_long(), get_long()],
+            &params,
+            vec![2, 2, 3],
+        );
+        truncate_multi_and_assert(
+            vec![get_medium(), get_long(), get_short()],
+            &TruncationParams {
+                max_length: 9,
+                ..params.clone()
+            },
+            vec![3, 4, 2],
+        );
+        // Same as with a pair
+        truncate_multi_and_assert(vec![get_long(), get_medium()], &params, vec![4, 3]);
+        truncate_multi_and_assert(vec![get_medium(), get_medium()], &params, vec![3, 4]);
+    }
+
+    #[test]
+    fn truncate_multi_encodings_only_first() {
+        let params = TruncationParams {
+            max_length: 7,
+            strategy: TruncationStrategy::OnlyFirst,
+            stride: 0,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
+        };
+
+        truncate_multi_and_assert(
+            vec![get_long(), get_short(), get_short()],
+            &params,
+            vec![3, 2, 2],
+        );
+        assert!(
+            truncate_multi_encodings(vec![get_short(), get_long(), get_short()], &params).is_err()
+        );
+    }
+
+    #[test]
+    fn truncate_encodings_direction_and_head_tail() {
+        let params = TruncationParams {
+            max_length: 7,
+            strategy: TruncationStrategy::LongestFirst,
+            stride: 0,
+            direction: TruncationDirection::Left,
+            word_boundaries: false,
+        };
+        let (e1, e2) = truncate_encodings(get_long(), Some(get_medium()), &params).unwrap();
+        assert_eq!(e1.get_ids(), &[11, 12, 13, 14]);
+        assert_eq!(e2.unwrap().get_ids(), &[4, 5, 6]);
+
+        let params = TruncationParams {
+            strategy: TruncationStrategy::HeadTail { head: 2 },
+            ..params
+        };
+        let (e1, e2) = truncate_encodings(get_long(), Some(get_medium()), &params).unwrap();
+        assert_eq!(e1.get_ids(), &[7, 8, 13, 14]);
+        assert_eq!(e1.get_overflowing()[0].get_ids(), &[9, 10, 11, 12]);
+        assert_eq!(e2.unwrap().get_ids(), &[3, 4, 6]);
+
+        truncate_multi_and_assert(
+            vec![get_long(), get_long(), get_long()],
+            &params,
+            vec![2, 2, 3],
+        );
+    }
+
+    #[test]
+    fn truncation_params_serde() {
+        let params = TruncationParams {
+            max_length: 7,
+            strategy: TruncationStrategy::HeadTail { head: 2 },
+            stride: 1,
+            direction: TruncationDirection::Left,
+            word_boundaries: true,
+        };
+        let serialized = serde_json::to_string(&params).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"max_length":7,"strategy":{"HeadTail":{"head":2}},"stride":1,"direction":"Left","word_boundaries":true}"#
+        );
+        let params: TruncationParams = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(params.strategy, TruncationStrategy::HeadTail { head: 2 });
+
+        assert!(params.word_boundaries);
+
+        // The direction defaults to `Right`, without snapping to words
+        let params: TruncationParams =
+            serde_json::from_str(r#"{"max_length":7,"strategy":"LongestFirst","stride":0}"#)
+                .unwrap();
+        assert_eq!(params.direction, TruncationDirection::Right);
+        assert!(!params.word_boundaries);
+    }
+
+    #[test]
+    fn flatten_overflowing_windows() {
+        let params = TruncationParams {
+            max_length: 3,
+            strategy: TruncationStrategy::LongestFirst,
+            stride: 1,
+            direction: TruncationDirection::Right,
+            word_boundaries: false,
+        };
+        let mut long = get_long();
+        long.set_sequence_id(0);
+        let (long, _) = truncate_encodings(long, None, &params).unwrap();
+        let mut medium = get_medium();
+        medium.set_sequence_id(0);
+        let (medium, _) = truncate_encodings(medium, None, &params).unwrap();
+
+        let batch = flatten_overflowing(vec![long, get_short(), medium]);
+        assert_eq!(batch.overflow_to_sample_mapping, vec![0, 0, 0, 0, 1, 2, 2]);
+        assert_eq!(batch.window_starts(0), vec![0, 2, 4, 6, 0, 0, 2]);
+        assert_eq!(batch.encodings[1].get_ids(), &[9, 10, 11]);
+        assert!(batch
+            .encodings
+            .iter()
+            .all(|e| e.get_overflowing().is_empty()));
+    }
 }
@@ -1,9 +1,11 @@
 use tokenizers::decoders::wordpiece::WordPiece as WordPieceDecoder;
 use tokenizers::models::bpe::BPE;
+use tokenizers::models::wordlevel::WordLevel;
 use tokenizers::models::wordpiece::WordPiece;
 use tokenizers::normalizers::bert::BertNormalizer;
 use tokenizers::pre_tokenizers::bert::BertPreTokenizer;
 use tokenizers::pre_tokenizers::byte_level::ByteLevel;
+use tokenizers::pre_tokenizers::whitespace::Whitespace;
 use tokenizers::processors::bert::BertProcessing;
 use tokenizers::tokenizer::{Model, Tokenizer};
 
@@ -30,6 +32,25 @@ pub fn get_byte_level(add_prefix_space: bool, trim_offsets: bool) -> Tokenizer {
     tokenizer
 }
 
+/// A small WordLevel tokenizer, with the special tokens `[UNK]`, `[SEP]`, `[CLS]` and `[PAD]`
+/// (ids 0 to 3) and the words `a`, `b`, `c` and `d`
+#[allow(dead_code)]
+pub fn get_word_level() -> Tokenizer {
+    let vocab = ["[UNK]", "[SEP]", "[CLS]", "[PAD]", "a", "b", "c", "d"]
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.to_string(), i as u32))
+        .collect();
+    let model = WordLevel::builder()
+        .vocab(vocab)
+        .unk_token("[UNK]".into())
+        .build();
+    let mut tokenizer = Tokenizer::new(model);
+    tokenizer.with_pre_tokenizer(Whitespace::default());
+
+    tokenizer
+}
+
 #[allow(dead_code)]
 pub fn get_bert_wordpiece() -> WordPiece {
     WordPiece::from_file("data/bert-base-uncased-vocab.txt")
@@ -1,7 +1,8 @@
 mod common;
 
 use common::*;
-use tokenizers::tokenizer::AddedToken;
+use tokenizers::processors::chat::ChatTemplate;
+use tokenizers::tokenizer::{AddedToken, PaddingParams, TruncationParams, TruncationStrategy};
 
 macro_rules! check_offsets {
     ($input: expr, $output:expr, $offset:expr, $result:expr) => {
@@ -113,6 +114,35 @@ fn byte_level_double_sequence() {
     );
 }
 
+#[test]
+fn byte_level_multi_sequence() {
+    let input_a = "My name is Anthony";
+    let input_b = "What is my name?";
+    let input_c = "Anthony";
+
+    let tokenizer = get_byte_level(true, true);
+    let output = tokenizer
+        .encode((input_a, input_b, input_c), false)
+        .unwrap();
+
+    assert_eq!(
+        output.get_offsets(),
+        &[
+            (0, 2),
+            (3, 7),
+            (8, 10),
+            (11, 18),
+            (0, 4),
+            (5, 7),
+            (8, 10),
+            (11, 15),
+            (15, 16),
+            (0, 7)
+        ]
+    );
+    assert_eq!(output.get_type_ids(), &[0, 0, 0, 0, 1, 1, 1, 1, 1, 2]);
+}
+
 #[test]
 fn byte_level_pre_tokenized_sequence() {
     let input = ["My", "name", "is", "Anthonino"];
@@ -191,3 +221,81 @@ fn split_on_added_tokens_bert() {
         ]
     );
 }
+
+#[test]
+fn bert_flattened_overflowing() {
+    let mut tokenizer = get_bert();
+    tokenizer
+        .with_truncation(Some(TruncationParams {
+            max_length: 8,
+            strategy: TruncationStrategy::OnlySecond,
+            stride: 1,
+            ..Default::default()
+        }))
+        .with_padding(Some(PaddingParams::default()));
+
+    let batch = tokenizer
+        .encode_batch_flattened(vec![("Who?", "My name is John"), ("Hi", "John")], true)
+        .unwrap();
+
+    assert_eq!(batch.overflow_to_sample_mapping, vec![0, 0, 1]);
+    assert_eq!(
+        batch.encodings[1].get_tokens(),
+        &["[CLS]", "who", "?", "[SEP]", "is", "john", "[SEP]", "[PAD]"]
+    );
+    // The window of the second sequence starts at its third token
+    assert_eq!(batch.window_starts(0), vec![0, 0, 0]);
+    assert_eq!(batch.window_starts(1), vec![0, 2, 0]);
+    assert_eq!(batch.encodings[1].token_to_sequence_index(0), None);
+    assert_eq!(batch.encodings[1].token_to_sequence_index(2), Some((0, 1)));
+    assert_eq!(batch.encodings[1].token_to_sequence_index(4), Some((1, 2)));
+    assert_eq!(batch.encodings[1].token_to_sequence_index(7), None);
+    assert!(batch.encodings.iter().all(|e| e.len() == 8));
+}
+
+#[test]
+fn chat_messages_offsets() {
+    let mut tokenizer = get_word_level();
+    tokenizer.with_chat_template(Some(
+        ChatTemplate::builder()
+            .try_prefix("[CLS]")
+            .unwrap()
+            .try_role("user", "$A [SEP]", false)
+            .unwrap()
+            .try_role("assistant", "$A [SEP]", true)
+            .unwrap()
+            .special_tokens(vec![("[CLS]", 2), ("[SEP]", 1)])
+            .build()
+            .unwrap(),
+    ));
+
+    let messages = vec![("user", "a b"), ("assistant", "c"), ("user", "d a")];
+    let (encoding, _) = tokenizer.encode_chat(&messages, false).unwrap();
+    assert_eq!(
+        encoding.get_tokens(),
+        ["[CLS]", "a", "b", "[SEP]", "c", "[SEP]", "d", "a", "[SEP]"]
+    );
+    // Each message is its own sequence, with offsets relative to its content
+    let messages_index = (0..encoding.len())
+        .map(|token| encoding.token_to_sequence_index(token))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages_index,
+        [
+            None,
+            Some((0, 0)),
+            Some((0, 1)),
+            None,
+            Some((1, 0)),
+            None,
+            Some((2, 0)),
+            Some((2, 1)),
+            None
+        ]
+    );
+    let token = 7;
+    let (message, _) = encoding.token_to_sequence_index(token).unwrap();
+    let offsets = encoding.token_to_chars(token).unwrap();
+    assert_eq!(&messages[message].1[offsets.0..offsets.1], "a");
+    assert_eq!(offsets, (2, 3));
+}
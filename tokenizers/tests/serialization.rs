@@ -15,6 +15,7 @@ use tokenizers::pre_tokenizers::delimiter::CharDelimiterSplit;
 use tokenizers::pre_tokenizers::whitespace::Whitespace;
 use tokenizers::pre_tokenizers::PreTokenizerWrapper;
 use tokenizers::processors::bert::BertProcessing;
+use tokenizers::processors::chat::ChatTemplate;
 use tokenizers::processors::PostProcessorWrapper;
 use tokenizers::{Tokenizer, TokenizerImpl};
 
@@ -194,6 +195,27 @@ fn tokenizer() {
     assert_eq!(serde_json::to_string(&de).unwrap(), ser);
 }
 
+#[test]
+fn tokenizer_chat_template() {
+    let mut tokenizer = Tokenizer::new(WordPiece::default());
+    let ser = serde_json::to_string(&tokenizer).unwrap();
+    assert!(!ser.contains("chat_template"));
+
+    let template = ChatTemplate::builder()
+        .try_role("user", "<user> $A", false)
+        .unwrap()
+        .try_role("assistant", "<bot> $A", true)
+        .unwrap()
+        .special_tokens(vec![("<user>", 0), ("<bot>", 1)])
+        .build()
+        .unwrap();
+    tokenizer.with_chat_template(Some(template.clone()));
+    let ser = serde_json::to_string(&tokenizer).unwrap();
+    let de: Tokenizer = serde_json::from_str(&ser).unwrap();
+    assert_eq!(de.get_chat_template(), Some(&template));
+    assert_eq!(serde_json::to_string(&de).unwrap(), ser);
+}
+
 #[test]
 fn test_deserialize_long_file() {
     let _tokenizer = Tokenizer::from_file("data/albert-base-v1-tokenizer.json").unwrap();
@@ -0,0 +1,65 @@
+mod common;
+
+use common::*;
+use tokenizers::processors::bert::BertProcessing;
+use tokenizers::processors::template::TemplateProcessing;
+use tokenizers::tokenizer::{PaddingParams, PaddingStrategy, TruncationParams};
+
+#[test]
+fn multi_max_length_too_low() {
+    let mut tokenizer = get_word_level();
+    tokenizer
+        .with_post_processor(
+            TemplateProcessing::builder()
+                .try_single("[CLS] $A [SEP]")
+                .unwrap()
+                .try_pair("[CLS] $A [SEP] $B:1 [SEP]:1")
+                .unwrap()
+                .try_multi("[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2")
+                .unwrap()
+                .special_tokens(vec![("[CLS]", 2), ("[SEP]", 1)])
+                .build()
+                .unwrap(),
+        )
+        .with_truncation(Some(TruncationParams {
+            max_length: 3,
+            ..Default::default()
+        }));
+
+    // The 4 special tokens of 3 sequences can't fit in 3 tokens
+    assert!(tokenizer.encode(("a", "b", "c"), true).is_err());
+    assert_eq!(tokenizer.encode(("a", "b", "c"), false).unwrap().len(), 3);
+}
+
+#[test]
+fn batch_longest_capped_truncates_before_post_processing() {
+    let mut tokenizer = get_word_level();
+    tokenizer
+        .with_post_processor(BertProcessing::new(
+            ("[SEP]".into(), 1),
+            ("[CLS]".into(), 2),
+        ))
+        .with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongestCapped(4),
+            pad_id: 3,
+            ..Default::default()
+        }));
+
+    // The special tokens are kept, and the overflowing tokens get their own window
+    let encodings = tokenizer.encode_batch(vec!["a b c d", "a"], true).unwrap();
+    assert_eq!(encodings[0].get_tokens(), &["[CLS]", "a", "b", "[SEP]"]);
+    assert_eq!(
+        encodings[0].get_overflowing()[0].get_tokens(),
+        &["[CLS]", "c", "d", "[SEP]"]
+    );
+    assert_eq!(encodings[1].get_tokens(), &["[CLS]", "a", "[SEP]", "[PAD]"]);
+
+    // With a truncation, the smallest of both lengths applies
+    tokenizer.with_truncation(Some(TruncationParams {
+        max_length: 3,
+        ..Default::default()
+    }));
+    let encodings = tokenizer.encode_batch(vec!["a b c d", "a"], true).unwrap();
+    assert_eq!(encodings[0].get_tokens(), &["[CLS]", "a", "[SEP]"]);
+    assert_eq!(encodings[1].get_tokens(), &["[CLS]", "a", "[SEP]"]);
+}
@@ -7,7 +7,9 @@ use std::path::Path;
 use tokenizers::models::unigram::Lattice;
 use tokenizers::models::unigram::Unigram;
 use tokenizers::models::unigram::UnigramTrainer;
+use tokenizers::pre_tokenizers::whitespace::WhitespaceSplit;
 use tokenizers::tokenizer::{Model, Trainer};
+use tokenizers::Tokenizer;
 
 #[test]
 fn test_unigram_from_file() {
@@ -101,3 +103,37 @@ fn test_sample() {
         }
     }
 }
+
+#[test]
+fn test_encode_nbest() {
+    let pieces = vec![
+        ("<unk>".to_string(), 0.0),
+        ("a".to_string(), -1.0),
+        ("b".to_string(), -1.0),
+        ("ab".to_string(), -1.5),
+    ];
+    let mut tokenizer = Tokenizer::new(Unigram::from(pieces, 0).unwrap());
+    tokenizer.with_pre_tokenizer(WhitespaceSplit);
+
+    // The alternatives of each word get combined
+    let nbest = tokenizer.encode_nbest("ab ab", 3, false).unwrap();
+    let tokens = nbest
+        .iter()
+        .map(|(encoding, _)| encoding.get_tokens().to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        vec![vec!["ab", "ab"], vec!["ab", "a", "b"], vec!["a", "b", "ab"],]
+    );
+    let scores = nbest.iter().map(|(_, score)| *score).collect::<Vec<_>>();
+    assert_eq!(scores, vec![-3.0, -3.5, -3.5]);
+    assert_eq!(nbest[1].0.get_offsets(), &[(0, 2), (3, 4), (4, 5)]);
+    assert_eq!(nbest[1].0.get_words(), &[Some(0), Some(1), Some(1)]);
+
+    // And so do the ones of the pair
+    let nbest = tokenizer.encode_nbest(("ab", "b"), 2, false).unwrap();
+    assert_eq!(nbest.len(), 2);
+    assert_eq!(nbest[0].0.get_tokens(), ["ab", "b"]);
+    assert_eq!(nbest[0].0.get_type_ids(), [0, 1]);
+    assert_eq!(nbest[1].0.get_tokens(), ["a", "b", "b"]);
+}